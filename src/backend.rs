@@ -0,0 +1,180 @@
+//! Backends used by the [Renderer](../renderer/struct.Renderer.html) to interact with a document
+//!
+//! The renderer itself only keeps track of definitions and names, all the actual element
+//! creation and manipulation goes through a [Backend](trait.Backend.html).
+//! By default the [DomBackend](struct.DomBackend.html) is used, which talks to the DOM of the webpage using web_sys.
+//! Outside of a browser the [HeadlessBackend](struct.HeadlessBackend.html) can be used,
+//! which keeps the whole document in memory.
+
+use svg_definitions::prelude::*;
+
+use crate::errors::RendererError;
+
+mod dom;
+mod headless;
+
+pub use dom::{DomBackend, DomListener};
+pub use headless::{HeadlessBackend, HeadlessElement, HeadlessListener, HEADLESS_ROOT_ID};
+
+/// Abstraction over the document a [Renderer](../renderer/struct.Renderer.html) renders into
+///
+/// # Note
+/// All methods take `&self`, since elements are handles into a document which is shared,
+/// in the same way as elements within the DOM.
+pub trait Backend {
+    /// Handle to a single element within the document
+    type Element: Clone;
+
+    /// An event listener added to an element, the listener is removed from the element when this is dropped
+    type Listener;
+
+    /// Creates a new SVG element with a certain tag name, which is not yet attached to the document
+    fn create_element(&self, tag_name: &str) -> Result<Self::Element, RendererError>;
+
+    /// Will return the element with a certain id attribute, if it is attached to the document
+    fn get_element_by_id(&self, id: &str) -> Option<Self::Element>;
+
+    /// Will return the tag name of an element
+    fn tag_name(&self, element: &Self::Element) -> String;
+
+    /// Sets an attribute of an element to a certain value
+    fn set_attribute(
+        &self,
+        element: &Self::Element,
+        name: &str,
+        value: &str,
+    ) -> Result<(), RendererError>;
+
+    /// Will return the value of an attribute of an element
+    fn get_attribute(&self, element: &Self::Element, name: &str) -> Option<String>;
+
+    /// Will return all attributes of an element as (name, value) pairs
+    fn attributes(&self, element: &Self::Element) -> Vec<(String, String)>;
+
+    /// Removes an attribute from an element
+    fn remove_attribute(&self, element: &Self::Element, name: &str) -> Result<(), RendererError>;
+
+    /// Sets the inner text of an element, which replaces the children of the element
+    fn set_text(&self, element: &Self::Element, text: &str) -> Result<(), RendererError>;
+
+    /// Will return the inner text of an element, if it has any
+    fn text(&self, element: &Self::Element) -> Option<String>;
+
+    /// Appends a child to the end of the children of a parent,
+    /// a child which already has a parent is moved
+    fn append_child(
+        &self,
+        parent: &Self::Element,
+        child: &Self::Element,
+    ) -> Result<(), RendererError>;
+
+    /// Appends multiple children to the end of the children of a parent
+    ///
+    /// # Note
+    /// By default the children are appended one by one,
+    /// a backend can override this to append them in a single operation.
+    fn append_children(
+        &self,
+        parent: &Self::Element,
+        children: &[Self::Element],
+    ) -> Result<(), RendererError> {
+        for child in children.iter() {
+            self.append_child(parent, child)?;
+        }
+
+        Ok(())
+    }
+
+    /// Inserts a child before another child of a parent, a child which already has a parent is moved
+    fn insert_before(
+        &self,
+        parent: &Self::Element,
+        child: &Self::Element,
+        reference: &Self::Element,
+    ) -> Result<(), RendererError>;
+
+    /// Replaces a child of a parent with another element, a new child which already has a parent is moved
+    fn replace_child(
+        &self,
+        parent: &Self::Element,
+        new_child: &Self::Element,
+        old_child: &Self::Element,
+    ) -> Result<(), RendererError>;
+
+    /// Removes a child from a parent
+    fn remove_child(
+        &self,
+        parent: &Self::Element,
+        child: &Self::Element,
+    ) -> Result<(), RendererError>;
+
+    /// Removes all children of an element
+    fn remove_children(&self, element: &Self::Element) -> Result<(), RendererError>;
+
+    /// Will return the parent of an element
+    fn parent_element(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    /// Will return all children of an element which are elements
+    fn children(&self, element: &Self::Element) -> Vec<Self::Element>;
+
+    /// Will return the first child of an element which is an element
+    fn first_element_child(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    /// Will return the position and size of an element on the screen as `(x, y, width, height)` in client pixels,
+    /// or `None` when the backend does not show the document on a screen
+    ///
+    /// # Note
+    /// By default this returns `None`, the renderer then uses the width and height attributes of the svg.
+    fn client_rect(&self, _element: &Self::Element) -> Option<(f32, f32, f32, f32)> {
+        None
+    }
+
+    /// Will return whether two handles point to the same element
+    ///
+    /// # Note
+    /// By default this returns `false`, so a [resync](../renderer/struct.Renderer.html#method.resync)
+    /// drops the event listeners of every named item, as it cannot tell whether their elements were replaced.
+    fn same_element(&self, _first: &Self::Element, _second: &Self::Element) -> bool {
+        false
+    }
+
+    /// Adds a listener for a pointer event to an element. The handler receives the position of the pointer
+    /// in the coordinates of the viewbox of `svg` and how far the wheel is scrolled for a wheel event.
+    fn add_listener(
+        &self,
+        svg: &Self::Element,
+        element: &Self::Element,
+        event_type: &str,
+        handler: Box<dyn FnMut(Point2D, Point2D)>,
+    ) -> Result<Self::Listener, RendererError>;
+}
+
+/// Converts a SVGElem to an element within the backend
+pub(crate) fn to_element<B: Backend>(
+    backend: &B,
+    svg_elem: &SVGElem,
+) -> Result<B::Element, RendererError> {
+    let elem = backend.create_element(&svg_elem.get_tag_name().to_string()[..])?;
+
+    // Sorted, so the same figure always results in the same element
+    let mut attributes: Vec<(String, &String)> = svg_elem
+        .get_attributes()
+        .iter()
+        .map(|(attr, value)| (attr.to_string(), value))
+        .collect();
+    attributes.sort();
+
+    for (attr, value) in attributes.iter() {
+        backend.set_attribute(&elem, &attr[..], &value[..])?;
+    }
+
+    for child in svg_elem.get_children().iter() {
+        backend.append_child(&elem, &to_element(backend, child)?)?;
+    }
+
+    if let Some(inner) = svg_elem.get_inner() {
+        backend.set_text(&elem, inner)?;
+    }
+
+    Ok(elem)
+}
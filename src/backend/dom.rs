@@ -0,0 +1,271 @@
+use svg_definitions::Point2D;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+
+use crate::errors::DomError::*;
+use crate::errors::RendererError;
+use crate::errors::RendererError::*;
+use crate::SVG_NS;
+
+use super::Backend;
+
+/// Backend which renders into the DOM of the webpage using web_sys
+#[derive(Clone)]
+pub struct DomBackend {
+    document: web_sys::Document,
+}
+
+impl DomBackend {
+    /// Creates a new DOM backend from the document of the current window
+    ///
+    /// # Note
+    /// Outside of a browser this will always fail with `DomError::NoWindow`
+    pub fn new() -> Result<DomBackend, RendererError> {
+        if cfg!(not(target_arch = "wasm32")) {
+            return Err(Dom(NoWindow));
+        }
+
+        let window = web_sys::window().ok_or(Dom(NoWindow))?;
+        let document = window.document().ok_or(Dom(NoDocument))?;
+
+        Ok(DomBackend { document })
+    }
+
+    /// Will return the document this backend renders into
+    pub fn document(&self) -> &web_sys::Document {
+        &self.document
+    }
+}
+
+/// Event listener added by the [DomBackend](struct.DomBackend.html), which is removed from its element when dropped
+pub struct DomListener {
+    element: web_sys::Element,
+    event_type: String,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for DomListener {
+    fn drop(&mut self) {
+        // Removing can only fail when the element is gone, in which case the listener is gone as well
+        let _ = self.element.remove_event_listener_with_callback(
+            &self.event_type[..],
+            self.closure.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+/// Converts a position within the browser window into the coordinates of the viewbox of a svg
+fn client_to_viewbox(svg: &web_sys::Element, (x, y): Point2D) -> Point2D {
+    svg.dyn_ref::<web_sys::SvgGraphicsElement>()
+        .and_then(|svg| svg.get_screen_ctm())
+        .and_then(|matrix| matrix.inverse().ok())
+        .map(|matrix| {
+            (
+                matrix.a() * x + matrix.c() * y + matrix.e(),
+                matrix.b() * x + matrix.d() * y + matrix.f(),
+            )
+        })
+        .unwrap_or((x, y))
+}
+
+impl Backend for DomBackend {
+    type Element = web_sys::Element;
+    type Listener = DomListener;
+
+    fn create_element(&self, tag_name: &str) -> Result<web_sys::Element, RendererError> {
+        self.document
+            .create_element_ns(Some(SVG_NS), tag_name)
+            .map_err(|_| Dom(UncreatableNSElement))
+    }
+
+    fn get_element_by_id(&self, id: &str) -> Option<web_sys::Element> {
+        self.document.get_element_by_id(id)
+    }
+
+    fn tag_name(&self, element: &web_sys::Element) -> String {
+        element.tag_name()
+    }
+
+    fn set_attribute(
+        &self,
+        element: &web_sys::Element,
+        name: &str,
+        value: &str,
+    ) -> Result<(), RendererError> {
+        element
+            .set_attribute(name, value)
+            .map_err(|_| Dom(UnsetableAttribute(String::from(name), String::from(value))))
+    }
+
+    fn get_attribute(&self, element: &web_sys::Element, name: &str) -> Option<String> {
+        element.get_attribute(name)
+    }
+
+    fn attributes(&self, element: &web_sys::Element) -> Vec<(String, String)> {
+        let attributes = element.attributes();
+
+        (0..attributes.length())
+            .filter_map(|index| attributes.item(index))
+            .map(|attribute| (attribute.name(), attribute.value()))
+            .collect()
+    }
+
+    fn remove_attribute(
+        &self,
+        element: &web_sys::Element,
+        name: &str,
+    ) -> Result<(), RendererError> {
+        element
+            .remove_attribute(name)
+            .map_err(|_| Dom(UnsetableAttribute(String::from(name), String::new())))
+    }
+
+    fn set_text(&self, element: &web_sys::Element, text: &str) -> Result<(), RendererError> {
+        element.set_inner_html(text);
+        Ok(())
+    }
+
+    fn text(&self, element: &web_sys::Element) -> Option<String> {
+        // Only direct text, the text of children belongs to the children
+        if element.child_element_count() > 0 {
+            return None;
+        }
+
+        element.text_content().filter(|text| !text.is_empty())
+    }
+
+    fn append_child(
+        &self,
+        parent: &web_sys::Element,
+        child: &web_sys::Element,
+    ) -> Result<(), RendererError> {
+        parent
+            .append_child(child)
+            .map_err(|_| Dom(UnappendableElement))
+            .map(|_| ())
+    }
+
+    fn append_children(
+        &self,
+        parent: &web_sys::Element,
+        children: &[web_sys::Element],
+    ) -> Result<(), RendererError> {
+        // A fragment only causes a single change to the document
+        let fragment = self.document.create_document_fragment();
+
+        for child in children.iter() {
+            fragment
+                .append_child(child)
+                .map_err(|_| Dom(UnappendableElement))?;
+        }
+
+        parent
+            .append_child(&fragment)
+            .map_err(|_| Dom(UnappendableElement))
+            .map(|_| ())
+    }
+
+    fn insert_before(
+        &self,
+        parent: &web_sys::Element,
+        child: &web_sys::Element,
+        reference: &web_sys::Element,
+    ) -> Result<(), RendererError> {
+        parent
+            .insert_before(child, Some(reference))
+            .map_err(|_| Dom(UnappendableElement))
+            .map(|_| ())
+    }
+
+    fn replace_child(
+        &self,
+        parent: &web_sys::Element,
+        new_child: &web_sys::Element,
+        old_child: &web_sys::Element,
+    ) -> Result<(), RendererError> {
+        parent
+            .replace_child(new_child, old_child)
+            .map_err(|_| Dom(UnappendableElement))
+            .map(|_| ())
+    }
+
+    fn remove_child(
+        &self,
+        parent: &web_sys::Element,
+        child: &web_sys::Element,
+    ) -> Result<(), RendererError> {
+        parent
+            .remove_child(child)
+            .map_err(|_| Dom(UnremoveableChild))
+            .map(|_| ())
+    }
+
+    fn remove_children(&self, element: &web_sys::Element) -> Result<(), RendererError> {
+        element.set_inner_html("");
+        Ok(())
+    }
+
+    fn parent_element(&self, element: &web_sys::Element) -> Option<web_sys::Element> {
+        element.parent_element()
+    }
+
+    fn children(&self, element: &web_sys::Element) -> Vec<web_sys::Element> {
+        let children = element.children();
+
+        (0..children.length())
+            .filter_map(|index| children.item(index))
+            .collect()
+    }
+
+    fn first_element_child(&self, element: &web_sys::Element) -> Option<web_sys::Element> {
+        element.first_element_child()
+    }
+
+    fn same_element(&self, first: &web_sys::Element, second: &web_sys::Element) -> bool {
+        first == second
+    }
+
+    fn client_rect(&self, element: &web_sys::Element) -> Option<(f32, f32, f32, f32)> {
+        let rect = element.get_bounding_client_rect();
+
+        Some((
+            rect.x() as f32,
+            rect.y() as f32,
+            rect.width() as f32,
+            rect.height() as f32,
+        ))
+    }
+
+    fn add_listener(
+        &self,
+        svg: &web_sys::Element,
+        element: &web_sys::Element,
+        event_type: &str,
+        mut handler: Box<dyn FnMut(Point2D, Point2D)>,
+    ) -> Result<DomListener, RendererError> {
+        let svg = svg.clone();
+
+        let closure = Closure::wrap(Box::new(move |event: web_sys::Event| {
+            let wheel_delta = event
+                .dyn_ref::<web_sys::WheelEvent>()
+                .map(|event| (event.delta_x() as f32, event.delta_y() as f32))
+                .unwrap_or((0.0, 0.0));
+
+            // Pointer and wheel events are mouse events as well
+            if let Some(event) = event.dyn_ref::<web_sys::MouseEvent>() {
+                let client = (event.client_x() as f32, event.client_y() as f32);
+                handler(client_to_viewbox(&svg, client), wheel_delta);
+            }
+        }) as Box<dyn FnMut(web_sys::Event)>);
+
+        element
+            .add_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref())
+            .map_err(|_| Dom(UnlistenableEvent))?;
+
+        Ok(DomListener {
+            element: element.clone(),
+            event_type: String::from(event_type),
+            closure,
+        })
+    }
+}
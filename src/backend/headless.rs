@@ -0,0 +1,451 @@
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::{Rc, Weak};
+
+use svg_definitions::Point2D;
+
+use crate::errors::DomError::*;
+use crate::errors::RendererError;
+use crate::errors::RendererError::*;
+use crate::SVG_NS;
+
+use super::Backend;
+
+/// The id of the parent element every [HeadlessBackend](struct.HeadlessBackend.html) is created with
+pub const HEADLESS_ROOT_ID: &str = "headless-root";
+
+/// The handler of an event listener, which receives the position and the wheel delta
+type Handler = RefCell<Box<dyn FnMut(Point2D, Point2D)>>;
+
+struct Node {
+    tag_name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<HeadlessElement>,
+    parent: Weak<RefCell<Node>>,
+    text: Option<String>,
+
+    /// The event listeners by event type, a listener is gone once its handler is dropped
+    listeners: Vec<(String, Weak<Handler>)>,
+}
+
+/// Handle to an element kept in memory by the [HeadlessBackend](struct.HeadlessBackend.html)
+#[derive(Clone)]
+pub struct HeadlessElement(Rc<RefCell<Node>>);
+
+impl HeadlessElement {
+    fn new(tag_name: &str) -> HeadlessElement {
+        HeadlessElement(Rc::new(RefCell::new(Node {
+            tag_name: String::from(tag_name),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            parent: Weak::new(),
+            text: None,
+            listeners: Vec::new(),
+        })))
+    }
+
+    /// Will return the tag name of the element
+    pub fn tag_name(&self) -> String {
+        self.0.borrow().tag_name.clone()
+    }
+
+    /// Will return the value of an attribute of the element
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        self.0
+            .borrow()
+            .attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Will return the children of the element
+    pub fn children(&self) -> Vec<HeadlessElement> {
+        self.0.borrow().children.clone()
+    }
+
+    /// Will return the parent of the element
+    pub fn parent(&self) -> Option<HeadlessElement> {
+        self.0.borrow().parent.upgrade().map(HeadlessElement)
+    }
+
+    /// Calls the listeners of an event on the element and, when the event bubbles, on all its ancestors.
+    /// As there is no screen, the position is already in the coordinates of the viewbox.
+    ///
+    /// # Arguments
+    /// * `event_type` - The type of the event, e.g. "click"
+    /// * `bubbles` - Whether the listeners of the ancestors are called as well
+    /// * `position` - The position of the pointer
+    /// * `wheel_delta` - How far the wheel is scrolled, `(0.0, 0.0)` for events other than "wheel"
+    pub fn dispatch_event(
+        &self,
+        event_type: &str,
+        bubbles: bool,
+        position: Point2D,
+        wheel_delta: Point2D,
+    ) {
+        let mut element = Some(self.clone());
+
+        while let Some(current) = element {
+            // The handlers are collected first, so they can change the document
+            let handlers: Vec<Rc<Handler>> = current
+                .0
+                .borrow()
+                .listeners
+                .iter()
+                .filter(|(listener_type, _)| listener_type == event_type)
+                .filter_map(|(_, handler)| handler.upgrade())
+                .collect();
+
+            for handler in handlers {
+                // A handler which is already running, because it dispatched this event itself, is skipped
+                if let Ok(mut handler) = handler.try_borrow_mut() {
+                    (handler)(position, wheel_delta);
+                }
+            }
+
+            element = if bubbles { current.parent() } else { None };
+        }
+    }
+
+    /// Will return whether both handles point to the same element
+    pub fn ptr_eq(&self, other: &HeadlessElement) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Serializes the element and all its children to a SVG string
+    ///
+    /// # Note
+    /// A `svg` element without namespace will automatically get the SVG namespace,
+    /// so the output can be used as a standalone SVG document.
+    pub fn to_svg_string(&self) -> String {
+        let mut output = String::new();
+        self.write_svg(&mut output, true);
+        output
+    }
+
+    fn write_svg(&self, output: &mut String, is_top: bool) {
+        let node = self.0.borrow();
+
+        output.push('<');
+        output.push_str(&node.tag_name);
+
+        if is_top
+            && node.tag_name == "svg"
+            && !node.attributes.iter().any(|(key, _)| key == "xmlns")
+        {
+            write!(output, " xmlns=\"{}\"", SVG_NS).unwrap();
+        }
+
+        for (key, value) in node.attributes.iter() {
+            write!(output, " {}=\"{}\"", key, escape(value)).unwrap();
+        }
+
+        if node.children.is_empty() && node.text.is_none() {
+            output.push_str("/>");
+            return;
+        }
+
+        output.push('>');
+
+        if let Some(text) = &node.text {
+            output.push_str(&escape(text));
+        }
+
+        for child in node.children.iter() {
+            child.write_svg(output, false);
+        }
+
+        write!(output, "</{}>", node.tag_name).unwrap();
+    }
+
+    fn find_by_id(&self, id: &str) -> Option<HeadlessElement> {
+        if self.get_attribute("id").as_deref() == Some(id) {
+            return Some(self.clone());
+        }
+
+        self.0
+            .borrow()
+            .children
+            .iter()
+            .find_map(|child| child.find_by_id(id))
+    }
+
+    fn detach(&self) {
+        self.0.borrow_mut().parent = Weak::new();
+    }
+
+    /// Will return whether the element is the same as or an ancestor of another element
+    fn is_inclusive_ancestor_of(&self, other: &HeadlessElement) -> bool {
+        let mut element = Some(other.clone());
+
+        while let Some(current) = element {
+            if current.ptr_eq(self) {
+                return true;
+            }
+
+            element = current.parent();
+        }
+
+        false
+    }
+
+    /// Will return the index of a child of the element
+    fn index_of(&self, child: &HeadlessElement) -> Option<usize> {
+        self.0
+            .borrow()
+            .children
+            .iter()
+            .position(|element| element.ptr_eq(child))
+    }
+}
+
+/// Escapes the characters which are not allowed within XML attributes or text
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Event listener added by the [HeadlessBackend](struct.HeadlessBackend.html), which is removed when dropped
+pub struct HeadlessListener {
+    _handler: Rc<Handler>,
+}
+
+/// Backend which keeps the whole document in memory, so it can be used outside of a browser
+///
+/// The resulting SVG can be serialized with
+/// [to_svg_string](../renderer/struct.Renderer.html#method.to_svg_string).
+pub struct HeadlessBackend {
+    /// The parent element of the svg
+    root: HeadlessElement,
+}
+
+impl HeadlessBackend {
+    /// Creates a new headless backend with an empty parent element with the id `root_id`
+    pub fn new(root_id: &str) -> HeadlessBackend {
+        let root = HeadlessElement::new("div");
+        root.0
+            .borrow_mut()
+            .attributes
+            .push((String::from("id"), String::from(root_id)));
+
+        HeadlessBackend { root }
+    }
+
+    /// Will return the parent element of the svg
+    pub fn root(&self) -> &HeadlessElement {
+        &self.root
+    }
+}
+
+impl Default for HeadlessBackend {
+    fn default() -> HeadlessBackend {
+        HeadlessBackend::new(HEADLESS_ROOT_ID)
+    }
+}
+
+impl Backend for HeadlessBackend {
+    type Element = HeadlessElement;
+    type Listener = HeadlessListener;
+
+    fn create_element(&self, tag_name: &str) -> Result<HeadlessElement, RendererError> {
+        Ok(HeadlessElement::new(tag_name))
+    }
+
+    fn get_element_by_id(&self, id: &str) -> Option<HeadlessElement> {
+        self.root.find_by_id(id)
+    }
+
+    fn tag_name(&self, element: &HeadlessElement) -> String {
+        element.tag_name()
+    }
+
+    fn set_attribute(
+        &self,
+        element: &HeadlessElement,
+        name: &str,
+        value: &str,
+    ) -> Result<(), RendererError> {
+        let mut node = element.0.borrow_mut();
+
+        match node.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old_value)) => *old_value = String::from(value),
+            None => node
+                .attributes
+                .push((String::from(name), String::from(value))),
+        }
+
+        Ok(())
+    }
+
+    fn get_attribute(&self, element: &HeadlessElement, name: &str) -> Option<String> {
+        element.get_attribute(name)
+    }
+
+    fn attributes(&self, element: &HeadlessElement) -> Vec<(String, String)> {
+        element.0.borrow().attributes.clone()
+    }
+
+    fn remove_attribute(&self, element: &HeadlessElement, name: &str) -> Result<(), RendererError> {
+        element
+            .0
+            .borrow_mut()
+            .attributes
+            .retain(|(key, _)| key != name);
+
+        Ok(())
+    }
+
+    fn set_text(&self, element: &HeadlessElement, text: &str) -> Result<(), RendererError> {
+        let mut node = element.0.borrow_mut();
+
+        // Like setting the inner html, the text replaces the children
+        node.children.drain(..).for_each(|child| child.detach());
+        node.text = Some(String::from(text));
+
+        Ok(())
+    }
+
+    fn text(&self, element: &HeadlessElement) -> Option<String> {
+        element.0.borrow().text.clone()
+    }
+
+    fn append_child(
+        &self,
+        parent: &HeadlessElement,
+        child: &HeadlessElement,
+    ) -> Result<(), RendererError> {
+        // Like the DOM, an element can not become a descendant of itself
+        if child.is_inclusive_ancestor_of(parent) {
+            return Err(Dom(UnappendableElement));
+        }
+
+        if let Some(old_parent) = child.parent() {
+            self.remove_child(&old_parent, child)?;
+        }
+
+        child.0.borrow_mut().parent = Rc::downgrade(&parent.0);
+        parent.0.borrow_mut().children.push(child.clone());
+
+        Ok(())
+    }
+
+    fn insert_before(
+        &self,
+        parent: &HeadlessElement,
+        child: &HeadlessElement,
+        reference: &HeadlessElement,
+    ) -> Result<(), RendererError> {
+        // Everything is checked before the child is detached, so a failed call changes nothing
+        if parent.index_of(reference).is_none() || child.is_inclusive_ancestor_of(parent) {
+            return Err(Dom(UnappendableElement));
+        }
+
+        if child.ptr_eq(reference) {
+            return Ok(());
+        }
+
+        if let Some(old_parent) = child.parent() {
+            self.remove_child(&old_parent, child)?;
+        }
+
+        let index = parent.index_of(reference).ok_or(Dom(UnappendableElement))?;
+
+        child.0.borrow_mut().parent = Rc::downgrade(&parent.0);
+        parent.0.borrow_mut().children.insert(index, child.clone());
+
+        Ok(())
+    }
+
+    fn replace_child(
+        &self,
+        parent: &HeadlessElement,
+        new_child: &HeadlessElement,
+        old_child: &HeadlessElement,
+    ) -> Result<(), RendererError> {
+        // Everything is checked before the new child is detached, so a failed call changes nothing
+        if parent.index_of(old_child).is_none() || new_child.is_inclusive_ancestor_of(parent) {
+            return Err(Dom(UnappendableElement));
+        }
+
+        if new_child.ptr_eq(old_child) {
+            return Ok(());
+        }
+
+        if let Some(old_parent) = new_child.parent() {
+            self.remove_child(&old_parent, new_child)?;
+        }
+
+        let index = parent.index_of(old_child).ok_or(Dom(UnappendableElement))?;
+
+        new_child.0.borrow_mut().parent = Rc::downgrade(&parent.0);
+        let mut node = parent.0.borrow_mut();
+        std::mem::replace(&mut node.children[index], new_child.clone()).detach();
+
+        Ok(())
+    }
+
+    fn remove_child(
+        &self,
+        parent: &HeadlessElement,
+        child: &HeadlessElement,
+    ) -> Result<(), RendererError> {
+        let mut node = parent.0.borrow_mut();
+
+        let index = node
+            .children
+            .iter()
+            .position(|element| element.ptr_eq(child))
+            .ok_or(Dom(UnremoveableChild))?;
+
+        node.children.remove(index).detach();
+
+        Ok(())
+    }
+
+    fn remove_children(&self, element: &HeadlessElement) -> Result<(), RendererError> {
+        let mut node = element.0.borrow_mut();
+
+        node.children.drain(..).for_each(|child| child.detach());
+        node.text = None;
+
+        Ok(())
+    }
+
+    fn parent_element(&self, element: &HeadlessElement) -> Option<HeadlessElement> {
+        element.parent()
+    }
+
+    fn children(&self, element: &HeadlessElement) -> Vec<HeadlessElement> {
+        element.children()
+    }
+
+    fn first_element_child(&self, element: &HeadlessElement) -> Option<HeadlessElement> {
+        element.0.borrow().children.first().cloned()
+    }
+
+    fn same_element(&self, first: &HeadlessElement, second: &HeadlessElement) -> bool {
+        first.ptr_eq(second)
+    }
+
+    fn add_listener(
+        &self,
+        _svg: &HeadlessElement,
+        element: &HeadlessElement,
+        event_type: &str,
+        handler: Box<dyn FnMut(Point2D, Point2D)>,
+    ) -> Result<HeadlessListener, RendererError> {
+        let handler = Rc::new(RefCell::new(handler));
+
+        let mut node = element.0.borrow_mut();
+        node.listeners
+            .retain(|(_, handler)| handler.strong_count() > 0);
+        node.listeners
+            .push((String::from(event_type), Rc::downgrade(&handler)));
+
+        Ok(HeadlessListener { _handler: handler })
+    }
+}
@@ -0,0 +1,1133 @@
+//! Bounding boxes and hit testing of figures, computed without a browser
+
+use std::f32::consts::PI;
+
+use svg_definitions::prelude::*;
+
+use crate::style::{get_style_property, Style, VARIABLE_ATTRIBUTES};
+use crate::transform::Transform;
+
+/// An axis-aligned rectangle which encloses a figure
+///
+/// The bounding box of a figure can be computed before it is rendered, e.g. for layout or culling.
+/// It includes the stroke, as far as a round brush of the stroke width reaches,
+/// and the transforms of the figure and all elements within it.
+///
+/// # Note
+/// Text and `<use>` elements are skipped, since their size depends on the browser and the document.
+/// Markers and the corners of miter joins are not included.
+///
+/// # Examples
+/// ```rust
+/// use wasm_svg_graphics::prelude::*;
+///
+/// // A circle with a radius of 10 and a stroke width of 1
+/// let bounds = BoundingBox::of(&SVGDefault::circle(10)).unwrap();
+///
+/// assert_eq!(bounds.min(), (-10.5, -10.5));
+/// assert_eq!(bounds.max(), (10.5, 10.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    min: Point2D,
+    max: Point2D,
+}
+
+impl BoundingBox {
+    /// Creates the bounding box spanned by two opposite corners
+    pub fn new(corner: Point2D, opposite: Point2D) -> BoundingBox {
+        BoundingBox {
+            min: (corner.0.min(opposite.0), corner.1.min(opposite.1)),
+            max: (corner.0.max(opposite.0), corner.1.max(opposite.1)),
+        }
+    }
+
+    /// Creates the bounding box of a rectangle with its top-left corner at `(x, y)`
+    pub fn from_rect(x: f32, y: f32, width: f32, height: f32) -> BoundingBox {
+        BoundingBox::new((x, y), (x + width, y + height))
+    }
+
+    /// Creates the bounding box of a single point
+    pub fn from_point(point: Point2D) -> BoundingBox {
+        BoundingBox {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Will return the bounding box of a figure in its own coordinates,
+    /// or `None` when nothing of the figure would be drawn
+    pub fn of(figure: &SVGElem) -> Option<BoundingBox> {
+        BoundingBox::of_transformed(figure, &Transform::identity())
+    }
+
+    /// Will return the bounding box of a figure placed with a transform,
+    /// which is tighter than transforming the bounding box of the figure
+    pub fn of_transformed(figure: &SVGElem, transform: &Transform) -> Option<BoundingBox> {
+        bounds_of(figure, transform, &Presentation::default())
+    }
+
+    /// Will return the top-left corner
+    pub fn min(&self) -> Point2D {
+        self.min
+    }
+
+    /// Will return the bottom-right corner
+    pub fn max(&self) -> Point2D {
+        self.max
+    }
+
+    /// Will return the left x-coordinate
+    pub fn x(&self) -> f32 {
+        self.min.0
+    }
+
+    /// Will return the top y-coordinate
+    pub fn y(&self) -> f32 {
+        self.min.1
+    }
+
+    /// Will return the width
+    pub fn width(&self) -> f32 {
+        self.max.0 - self.min.0
+    }
+
+    /// Will return the height
+    pub fn height(&self) -> f32 {
+        self.max.1 - self.min.1
+    }
+
+    /// Will return the center
+    pub fn center(&self) -> Point2D {
+        (
+            (self.min.0 + self.max.0) / 2.0,
+            (self.min.1 + self.max.1) / 2.0,
+        )
+    }
+
+    /// Will return the smallest bounding box which encloses both bounding boxes
+    pub fn union(self, other: BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    /// Will return the smallest bounding box which encloses this bounding box and a point
+    pub fn include(self, point: Point2D) -> BoundingBox {
+        self.union(BoundingBox::from_point(point))
+    }
+
+    /// Will return the bounding box grown by `x` on the left and right and by `y` on the top and bottom
+    pub fn expand(self, x: f32, y: f32) -> BoundingBox {
+        BoundingBox::new(
+            (self.min.0 - x, self.min.1 - y),
+            (self.max.0 + x, self.max.1 + y),
+        )
+    }
+
+    /// Will return whether a point is within the bounding box, including its edges
+    pub fn contains(&self, point: Point2D) -> bool {
+        point.0 >= self.min.0
+            && point.0 <= self.max.0
+            && point.1 >= self.min.1
+            && point.1 <= self.max.1
+    }
+
+    /// Will return whether two bounding boxes overlap, touching edges count as overlapping
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.0 <= other.max.0
+            && other.min.0 <= self.max.0
+            && self.min.1 <= other.max.1
+            && other.min.1 <= self.max.1
+    }
+
+    /// Will return the bounding box of the transformed corners of this bounding box
+    pub fn transform(&self, transform: &Transform) -> BoundingBox {
+        let corners = [
+            self.min,
+            (self.max.0, self.min.1),
+            self.max,
+            (self.min.0, self.max.1),
+        ];
+
+        corners[1..].iter().fold(
+            BoundingBox::from_point(transform.apply(corners[0])),
+            |bounds, corner| bounds.include(transform.apply(*corner)),
+        )
+    }
+}
+
+/// A single piece of the outline of a shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Segment {
+    Line(Point2D, Point2D),
+
+    /// (Start, Control 1, Control 2, End)
+    Cubic(Point2D, Point2D, Point2D, Point2D),
+
+    /// Part of an ellipse, with the rotation of the ellipse, the start angle and the swept angle in radians
+    Arc {
+        center: Point2D,
+        radii: Point2D,
+        rotation: f32,
+        start: f32,
+        sweep: f32,
+    },
+}
+
+impl Segment {
+    /// Will return the point at `t`, from `0.0` at the start to `1.0` at the end of the segment
+    pub(crate) fn point_at(&self, t: f32) -> Point2D {
+        match *self {
+            Segment::Line(from, to) => (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t),
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let s = 1.0 - t;
+                let (w0, w1, w2, w3) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+
+                (
+                    w0 * p0.0 + w1 * p1.0 + w2 * p2.0 + w3 * p3.0,
+                    w0 * p0.1 + w1 * p1.1 + w2 * p2.1 + w3 * p3.1,
+                )
+            }
+            Segment::Arc {
+                center,
+                radii,
+                rotation,
+                start,
+                sweep,
+            } => {
+                let (sin, cos) = (start + sweep * t).sin_cos();
+                let (rotation_sin, rotation_cos) = rotation.sin_cos();
+                let (x, y) = (radii.0 * cos, radii.1 * sin);
+
+                (
+                    center.0 + rotation_cos * x - rotation_sin * y,
+                    center.1 + rotation_sin * x + rotation_cos * y,
+                )
+            }
+        }
+    }
+}
+
+/// The outline of a shape, split into subpaths
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Outline {
+    /// Every subpath with whether it is closed
+    pub(crate) subpaths: Vec<(Vec<Segment>, bool)>,
+}
+
+impl Outline {
+    fn polygon(points: &[Point2D], closed: bool) -> Outline {
+        let mut segments: Vec<Segment> = points
+            .windows(2)
+            .map(|pair| Segment::Line(pair[0], pair[1]))
+            .collect();
+
+        if closed && points.len() > 2 {
+            segments.push(Segment::Line(points[points.len() - 1], points[0]));
+        }
+
+        Outline {
+            subpaths: vec![(segments, closed)],
+        }
+    }
+
+    fn ellipse(center: Point2D, radii: Point2D) -> Outline {
+        let arc = Segment::Arc {
+            center,
+            radii,
+            rotation: 0.0,
+            start: 0.0,
+            sweep: 2.0 * PI,
+        };
+
+        Outline {
+            subpaths: vec![(vec![arc], true)],
+        }
+    }
+
+    /// Will return the outline of a shape element, or `None` when the element is not a shape or is not drawn
+    pub(crate) fn of<F: FigureNode>(element: &F) -> Option<Outline> {
+        let number = |name: &str| {
+            element
+                .attribute(name)
+                .as_deref()
+                .and_then(parse_length)
+                .unwrap_or(0.0)
+        };
+
+        match &element.tag_name()[..] {
+            "circle" => {
+                let radius = number("r");
+
+                if radius > 0.0 {
+                    Some(Outline::ellipse(
+                        (number("cx"), number("cy")),
+                        (radius, radius),
+                    ))
+                } else {
+                    None
+                }
+            }
+            "ellipse" => {
+                let radii = (number("rx"), number("ry"));
+
+                if radii.0 > 0.0 && radii.1 > 0.0 {
+                    Some(Outline::ellipse((number("cx"), number("cy")), radii))
+                } else {
+                    None
+                }
+            }
+            "rect" | "image" | "foreignObject" => {
+                let (x, y) = (number("x"), number("y"));
+                let (width, height) = (number("width"), number("height"));
+
+                if width > 0.0 && height > 0.0 {
+                    Some(Outline::polygon(
+                        &[
+                            (x, y),
+                            (x + width, y),
+                            (x + width, y + height),
+                            (x, y + height),
+                        ],
+                        true,
+                    ))
+                } else {
+                    None
+                }
+            }
+            "line" => Some(Outline::polygon(
+                &[(number("x1"), number("y1")), (number("x2"), number("y2"))],
+                false,
+            )),
+            tag @ "polyline" | tag @ "polygon" => {
+                let value = element.attribute("points")?;
+                let mut parser = NumberParser::new(&value[..]);
+                let mut points = Vec::new();
+
+                while let Some(point) = parser.point() {
+                    points.push(point);
+                }
+
+                if points.len() < 2 {
+                    return None;
+                }
+
+                Some(Outline::polygon(&points, tag == "polygon"))
+            }
+            "path" => Some(parse_path(&element.attribute("d")?[..])),
+            _ => None,
+        }
+    }
+
+    /// Will return every subpath as a polyline, where curves are approximated by straight lines
+    fn flatten(&self) -> Vec<Vec<Point2D>> {
+        const STEPS: usize = 16;
+
+        self.subpaths
+            .iter()
+            .filter(|(segments, _)| !segments.is_empty())
+            .map(|(segments, _)| {
+                let mut points = vec![segments[0].point_at(0.0)];
+
+                for segment in segments.iter() {
+                    match segment {
+                        Segment::Line(_, to) => points.push(*to),
+                        _ => points.extend(
+                            (1..=STEPS).map(|step| segment.point_at(step as f32 / STEPS as f32)),
+                        ),
+                    }
+                }
+
+                points
+            })
+            .collect()
+    }
+
+    /// Will return whether a point is within the filled area, where every subpath is closed
+    fn fills(&self, point: Point2D, even_odd: bool) -> bool {
+        let mut winding = 0;
+
+        for polyline in self.flatten() {
+            let edges = polyline
+                .iter()
+                .zip(polyline.iter().skip(1).chain(polyline.first()));
+
+            for (from, to) in edges {
+                let side =
+                    (to.0 - from.0) * (point.1 - from.1) - (point.0 - from.0) * (to.1 - from.1);
+
+                if from.1 <= point.1 && to.1 > point.1 && side > 0.0 {
+                    winding += 1;
+                } else if from.1 > point.1 && to.1 <= point.1 && side < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+
+        if even_odd {
+            winding % 2 != 0
+        } else {
+            winding != 0
+        }
+    }
+
+    /// Will return the distance from a point to the closest point on the outline
+    fn distance(&self, point: Point2D) -> f32 {
+        self.flatten()
+            .iter()
+            .flat_map(|polyline| polyline.windows(2))
+            .map(|edge| distance_to_line(point, edge[0], edge[1]))
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+/// Read access to an element of a figure,
+/// so both figures and elements within the document can be measured
+pub(crate) trait FigureNode {
+    /// Will return the tag name, e.g. "circle"
+    fn tag_name(&self) -> String;
+
+    /// Will return the value of an attribute, e.g. "stroke-width"
+    fn attribute(&self, name: &str) -> Option<String>;
+
+    /// Calls a function for every child element
+    fn for_each_child(&self, f: &mut dyn FnMut(&Self));
+}
+
+impl FigureNode for SVGElem {
+    fn tag_name(&self) -> String {
+        self.get_tag_name().to_string()
+    }
+
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.get_attributes()
+            .iter()
+            .find(|(attribute, _)| attribute.to_string() == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&SVGElem)) {
+        for child in self.get_children().iter() {
+            f(child);
+        }
+    }
+}
+
+/// The presentation an element inherits from the elements it is in
+#[derive(Debug, Clone)]
+pub(crate) struct Presentation {
+    fill: bool,
+    stroke: bool,
+    stroke_width: f32,
+    even_odd: bool,
+    visible: bool,
+
+    /// The CSS variables set within style attributes, e.g. by a [Style](../style/struct.Style.html)
+    variables: Vec<(String, String)>,
+}
+
+impl Default for Presentation {
+    fn default() -> Presentation {
+        Presentation {
+            fill: true,
+            stroke: false,
+            stroke_width: 1.0,
+            even_odd: false,
+            visible: true,
+            variables: Vec::new(),
+        }
+    }
+}
+
+impl Presentation {
+    /// Will return the presentation of an element within an element with this presentation,
+    /// or `None` when the element is not displayed
+    pub(crate) fn of<F: FigureNode>(&self, element: &F) -> Option<Presentation> {
+        let style = element.attribute("style");
+        let mut presentation = self.clone();
+
+        for declaration in style.as_deref().unwrap_or("").split(';') {
+            if let Some((name, value)) = declaration.split_once(':') {
+                let name = name.trim();
+
+                if name.starts_with("--") {
+                    presentation
+                        .variables
+                        .retain(|(variable, _)| variable != name);
+                    presentation
+                        .variables
+                        .push((String::from(name), String::from(value.trim())));
+                }
+            }
+        }
+
+        let property = |name: &str| presentation.property(element, style.as_deref(), name);
+
+        if property("display").as_deref() == Some("none") {
+            return None;
+        }
+
+        let fill = property("fill").map(|paint| paint != "none");
+        let stroke = property("stroke").map(|paint| paint != "none");
+        let stroke_width = property("stroke-width").and_then(|width| parse_length(&width[..]));
+        let even_odd = property("fill-rule").map(|rule| rule == "evenodd");
+        let visible = property("visibility").map(|visibility| visibility == "visible");
+
+        presentation.fill = fill.unwrap_or(presentation.fill);
+        presentation.stroke = stroke.unwrap_or(presentation.stroke);
+        presentation.stroke_width = stroke_width.unwrap_or(presentation.stroke_width);
+        presentation.even_odd = even_odd.unwrap_or(presentation.even_odd);
+        presentation.visible = visible.unwrap_or(presentation.visible);
+
+        Some(presentation)
+    }
+
+    /// Will return the presentation within a use with a style, which sets both presentation attributes and CSS variables
+    pub(crate) fn with_style(&self, style: &Style) -> Presentation {
+        let mut presentation = self.clone();
+
+        for (name, value) in style.attributes().iter() {
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+
+            match *name {
+                "fill" => presentation.fill = value != "none",
+                "stroke" => presentation.stroke = value != "none",
+                "stroke-width" => {
+                    presentation.stroke_width =
+                        parse_length(&value[..]).unwrap_or(presentation.stroke_width)
+                }
+                _ => {}
+            }
+
+            if VARIABLE_ATTRIBUTES.contains(name) {
+                let variable = format!("--{}", name);
+
+                presentation.variables.retain(|(name, _)| *name != variable);
+                presentation.variables.push((variable, value.clone()));
+            }
+        }
+
+        presentation
+    }
+
+    /// Will return the value of a property of an element, where the style attribute takes precedence
+    /// over the presentation attribute. `None` is returned when the value is inherited.
+    fn property<F: FigureNode>(
+        &self,
+        element: &F,
+        style: Option<&str>,
+        name: &str,
+    ) -> Option<String> {
+        let value = get_style_property(style, name).or_else(|| element.attribute(name))?;
+        let value = value.trim();
+
+        if value == "inherit" {
+            return None;
+        }
+
+        // A variable which is not set uses its fallback value
+        match value.strip_prefix("var(") {
+            Some(variable) => {
+                let variable = variable.trim_end_matches(')');
+                let (variable, fallback) = match variable.split_once(',') {
+                    Some((variable, fallback)) => (variable.trim(), Some(fallback.trim())),
+                    None => (variable.trim(), None),
+                };
+
+                self.variables
+                    .iter()
+                    .find(|(name, _)| name == variable)
+                    .map(|(_, value)| value.clone())
+                    .or_else(|| fallback.map(String::from))
+            }
+            None => Some(String::from(value)),
+        }
+    }
+}
+
+/// Will return the transform of an element within an element which is placed with a transform
+fn element_transform<F: FigureNode>(element: &F, transform: &Transform) -> Transform {
+    match element
+        .attribute("transform")
+        .and_then(|value| Transform::parse(&value[..]))
+    {
+        Some(local) => local.then(transform.clone()),
+        None => transform.clone(),
+    }
+}
+
+/// Will return whether an element is drawn as a group of its children
+fn is_group(tag_name: &str) -> bool {
+    matches!(tag_name, "g" | "svg" | "a" | "switch")
+}
+
+/// Will return the bounding box of a figure placed with a transform, which inherits a presentation
+pub(crate) fn bounds_of<F: FigureNode>(
+    figure: &F,
+    transform: &Transform,
+    presentation: &Presentation,
+) -> Option<BoundingBox> {
+    let mut bounds = None;
+    collect(figure, transform, presentation, &mut bounds);
+
+    bounds
+}
+
+/// Adds the bounds of an element and all elements within it
+fn collect<F: FigureNode>(
+    element: &F,
+    transform: &Transform,
+    inherited: &Presentation,
+    bounds: &mut Option<BoundingBox>,
+) {
+    let presentation = match inherited.of(element) {
+        Some(presentation) => presentation,
+        None => return,
+    };
+    let transform = element_transform(element, transform);
+    let tag_name = element.tag_name();
+
+    if is_group(&tag_name[..]) {
+        element.for_each_child(&mut |child| collect(child, &transform, &presentation, bounds));
+        return;
+    }
+
+    let outline = match Outline::of(element) {
+        Some(outline) => outline,
+        None => return,
+    };
+    let matrix = transform.to_matrix();
+
+    let shape = outline
+        .subpaths
+        .iter()
+        .flat_map(|(segments, _)| segments.iter())
+        .filter_map(|segment| segment_bounds(segment, matrix))
+        .fold(None, |shape: Option<BoundingBox>, segment| {
+            Some(shape.map_or(segment, |shape| shape.union(segment)))
+        });
+
+    let shape = match shape {
+        Some(shape) => shape,
+        None => return,
+    };
+
+    // Images and foreign objects are not stroked
+    let stroked = presentation.stroke && !matches!(&tag_name[..], "image" | "foreignObject");
+    let shape = if stroked && presentation.stroke_width > 0.0 {
+        // The extent of a round brush after the transform
+        let [a, b, c, d, _, _] = matrix;
+        let half = presentation.stroke_width / 2.0;
+
+        shape.expand(half * (a * a + c * c).sqrt(), half * (b * b + d * d).sqrt())
+    } else {
+        shape
+    };
+
+    *bounds = Some(bounds.map_or(shape, |bounds| bounds.union(shape)));
+}
+
+/// Will return whether a point hits the fill or stroke of an element placed with a transform.
+/// Like pointer events in a browser, a fill or stroke counts unless it is `none`, even when it is transparent.
+pub(crate) fn hits<F: FigureNode>(
+    element: &F,
+    transform: &Transform,
+    inherited: &Presentation,
+    point: Point2D,
+) -> bool {
+    let presentation = match inherited.of(element) {
+        Some(presentation) => presentation,
+        None => return false,
+    };
+    let transform = element_transform(element, transform);
+    let tag_name = element.tag_name();
+
+    if is_group(&tag_name[..]) {
+        let mut hit = false;
+        element.for_each_child(&mut |child| {
+            hit = hit || hits(child, &transform, &presentation, point);
+        });
+
+        return hit;
+    }
+
+    if !presentation.visible {
+        return false;
+    }
+
+    let (outline, point) = match (Outline::of(element), transform.inverse()) {
+        (Some(outline), Some(inverse)) => (outline, inverse.apply(point)),
+        _ => return false,
+    };
+
+    // Images and foreign objects are hit anywhere within their rectangle
+    if matches!(&tag_name[..], "image" | "foreignObject") {
+        return outline.fills(point, false);
+    }
+
+    let filled = presentation.fill && tag_name != "line";
+    let stroked = presentation.stroke && presentation.stroke_width > 0.0;
+
+    (filled && outline.fills(point, presentation.even_odd))
+        || (stroked && outline.distance(point) <= presentation.stroke_width / 2.0)
+}
+
+/// Will return the bounds of a transformed segment
+fn segment_bounds(segment: &Segment, matrix: [f32; 6]) -> Option<BoundingBox> {
+    let [a, b, c, d, e, f] = matrix;
+    let apply = |(x, y): Point2D| (a * x + c * y + e, b * x + d * y + f);
+
+    match *segment {
+        Segment::Line(from, to) => Some(BoundingBox::new(apply(from), apply(to))),
+        Segment::Cubic(p0, p1, p2, p3) => {
+            // A transformed Bézier curve is the Bézier curve of the transformed control points
+            let (p0, p1, p2, p3) = (apply(p0), apply(p1), apply(p2), apply(p3));
+            let curve = Segment::Cubic(p0, p1, p2, p3);
+
+            let mut bounds = BoundingBox::new(p0, p3);
+            let roots = cubic_extrema(p0.0, p1.0, p2.0, p3.0)
+                .into_iter()
+                .chain(cubic_extrema(p0.1, p1.1, p2.1, p3.1));
+
+            for t in roots {
+                bounds = bounds.include(curve.point_at(t));
+            }
+
+            Some(bounds)
+        }
+        Segment::Arc {
+            center,
+            radii,
+            rotation,
+            start,
+            sweep,
+        } => {
+            // A point of the transformed ellipse is center + u * cos(angle) + v * sin(angle)
+            let (sin, cos) = rotation.sin_cos();
+            let axis_u = (radii.0 * cos, radii.0 * sin);
+            let axis_v = (-radii.1 * sin, radii.1 * cos);
+            let u = (a * axis_u.0 + c * axis_u.1, b * axis_u.0 + d * axis_u.1);
+            let v = (a * axis_v.0 + c * axis_v.1, b * axis_v.0 + d * axis_v.1);
+            let center = apply(center);
+
+            let point = |angle: f32| {
+                let (sin, cos) = angle.sin_cos();
+                (
+                    center.0 + u.0 * cos + v.0 * sin,
+                    center.1 + u.1 * cos + v.1 * sin,
+                )
+            };
+
+            let mut bounds = BoundingBox::new(point(start), point(start + sweep));
+
+            // Each coordinate is extreme where its derivative is zero
+            for extreme in [v.0.atan2(u.0), v.1.atan2(u.1)].iter() {
+                for angle in [*extreme, extreme + PI].iter() {
+                    if is_within_sweep(*angle, start, sweep) {
+                        bounds = bounds.include(point(*angle));
+                    }
+                }
+            }
+
+            Some(bounds)
+        }
+    }
+}
+
+/// Will return the values of `t` within `(0, 1)` where a single coordinate of a cubic Bézier curve is extreme
+fn cubic_extrema(p0: f32, p1: f32, p2: f32, p3: f32) -> Vec<f32> {
+    // The derivative divided by 3 is a * t^2 + b * t + c
+    let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+
+    let roots = if a.abs() < 1e-6 {
+        if b.abs() < 1e-6 {
+            Vec::new()
+        } else {
+            vec![-c / b]
+        }
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+
+        if discriminant < 0.0 {
+            Vec::new()
+        } else {
+            let root = discriminant.sqrt();
+            vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+        }
+    };
+
+    roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0).collect()
+}
+
+/// Will return whether an angle is passed when sweeping from `start` by `sweep` radians
+fn is_within_sweep(angle: f32, start: f32, sweep: f32) -> bool {
+    let offset = if sweep >= 0.0 {
+        angle - start
+    } else {
+        start - angle
+    };
+
+    offset.rem_euclid(2.0 * PI) <= sweep.abs()
+}
+
+/// Will return the distance from a point to a line segment
+fn distance_to_line(point: Point2D, from: Point2D, to: Point2D) -> f32 {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx * dx + dy * dy;
+
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        (((point.0 - from.0) * dx + (point.1 - from.1) * dy) / length).clamp(0.0, 1.0)
+    };
+
+    let (x, y) = (from.0 + t * dx - point.0, from.1 + t * dy - point.1);
+    (x * x + y * y).sqrt()
+}
+
+/// Parses a length in user units, lengths in other units or percentages are not supported
+fn parse_length(value: &str) -> Option<f32> {
+    value.trim().trim_end_matches("px").trim_end().parse().ok()
+}
+
+/// Reads the numbers of path data and points attributes, which may be separated by whitespace, commas or signs
+struct NumberParser<'a> {
+    value: &'a [u8],
+    index: usize,
+}
+
+impl<'a> NumberParser<'a> {
+    fn new(value: &'a str) -> NumberParser<'a> {
+        NumberParser {
+            value: value.as_bytes(),
+            index: 0,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self.index < self.value.len()
+            && (self.value[self.index].is_ascii_whitespace() || self.value[self.index] == b',')
+        {
+            self.index += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.value.get(self.index).cloned()
+    }
+
+    /// Will return the next command letter, when the next item is not a number
+    fn command(&mut self) -> Option<u8> {
+        let command = self.peek().filter(u8::is_ascii_alphabetic)?;
+        self.index += 1;
+
+        Some(command)
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.index;
+        let digits = |parser: &mut NumberParser| {
+            let start = parser.index;
+            while parser.index < parser.value.len() && parser.value[parser.index].is_ascii_digit() {
+                parser.index += 1;
+            }
+            parser.index > start
+        };
+
+        if let Some(b'+') | Some(b'-') = self.value.get(self.index) {
+            self.index += 1;
+        }
+
+        let mut has_digits = digits(self);
+        if self.value.get(self.index) == Some(&b'.') {
+            self.index += 1;
+            has_digits |= digits(self);
+        }
+
+        if !has_digits {
+            self.index = start;
+            return None;
+        }
+
+        if let Some(b'e') | Some(b'E') = self.value.get(self.index) {
+            let mantissa_end = self.index;
+            self.index += 1;
+
+            if let Some(b'+') | Some(b'-') = self.value.get(self.index) {
+                self.index += 1;
+            }
+
+            if !digits(self) {
+                self.index = mantissa_end;
+            }
+        }
+
+        std::str::from_utf8(&self.value[start..self.index])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn point(&mut self) -> Option<Point2D> {
+        Some((self.number()?, self.number()?))
+    }
+
+    /// Reads an arc flag, which does not need to be separated from the next number
+    fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.index += 1;
+
+        Some(flag)
+    }
+}
+
+/// Parses the value of a `d` attribute into an outline,
+/// following the SVG specification everything up to the first error is kept
+pub(crate) fn parse_path(data: &str) -> Outline {
+    let mut parser = NumberParser::new(data);
+    let mut outline = Outline::default();
+    let mut segments = Vec::new();
+
+    let mut current = (0.0, 0.0);
+    let mut subpath_start = (0.0, 0.0);
+    let mut command = None;
+
+    // The last control points, which are reflected by smooth curves
+    let mut cubic_control: Option<Point2D> = None;
+    let mut quad_control: Option<Point2D> = None;
+
+    loop {
+        let next = match parser.command() {
+            Some(next) => next,
+            None if parser.peek().is_none() => break,
+            None => match command {
+                // Numbers without a command repeat the previous command, which is not possible after a close path
+                Some(previous) if previous != b'Z' && previous != b'z' => previous,
+                _ => break,
+            },
+        };
+        command = Some(next);
+
+        let relative = next.is_ascii_lowercase();
+        let offset = if relative { current } else { (0.0, 0.0) };
+        let point =
+            |parser: &mut NumberParser| parser.point().map(|(x, y)| (x + offset.0, y + offset.1));
+
+        let (mut next_cubic, mut next_quad) = (None, None);
+
+        match next.to_ascii_uppercase() {
+            b'M' => {
+                let to = match point(&mut parser) {
+                    Some(to) => to,
+                    None => break,
+                };
+
+                if !segments.is_empty() {
+                    outline
+                        .subpaths
+                        .push((std::mem::take(&mut segments), false));
+                }
+
+                current = to;
+                subpath_start = to;
+
+                // Further coordinate pairs are implicit line commands
+                command = Some(if relative { b'l' } else { b'L' });
+            }
+            b'L' => match point(&mut parser) {
+                Some(to) => {
+                    segments.push(Segment::Line(current, to));
+                    current = to;
+                }
+                None => break,
+            },
+            b'H' => match parser.number() {
+                Some(x) => {
+                    let to = (x + offset.0, current.1);
+                    segments.push(Segment::Line(current, to));
+                    current = to;
+                }
+                None => break,
+            },
+            b'V' => match parser.number() {
+                Some(y) => {
+                    let to = (current.0, y + offset.1);
+                    segments.push(Segment::Line(current, to));
+                    current = to;
+                }
+                None => break,
+            },
+            b'C' | b'S' => {
+                let first = if next.eq_ignore_ascii_case(&b'C') {
+                    match point(&mut parser) {
+                        Some(first) => first,
+                        None => break,
+                    }
+                } else {
+                    cubic_control
+                        .map(|control| (2.0 * current.0 - control.0, 2.0 * current.1 - control.1))
+                        .unwrap_or(current)
+                };
+
+                match (point(&mut parser), point(&mut parser)) {
+                    (Some(second), Some(to)) => {
+                        segments.push(Segment::Cubic(current, first, second, to));
+                        next_cubic = Some(second);
+                        current = to;
+                    }
+                    _ => break,
+                }
+            }
+            b'Q' | b'T' => {
+                let control = if next.eq_ignore_ascii_case(&b'Q') {
+                    match point(&mut parser) {
+                        Some(control) => control,
+                        None => break,
+                    }
+                } else {
+                    quad_control
+                        .map(|control| (2.0 * current.0 - control.0, 2.0 * current.1 - control.1))
+                        .unwrap_or(current)
+                };
+
+                match point(&mut parser) {
+                    Some(to) => {
+                        // A quadratic curve is a cubic curve with both control points at 2/3 towards its control point
+                        let first = (
+                            current.0 + 2.0 / 3.0 * (control.0 - current.0),
+                            current.1 + 2.0 / 3.0 * (control.1 - current.1),
+                        );
+                        let second = (
+                            to.0 + 2.0 / 3.0 * (control.0 - to.0),
+                            to.1 + 2.0 / 3.0 * (control.1 - to.1),
+                        );
+
+                        segments.push(Segment::Cubic(current, first, second, to));
+                        next_quad = Some(control);
+                        current = to;
+                    }
+                    None => break,
+                }
+            }
+            b'A' => {
+                let arc = (
+                    parser.number(),
+                    parser.number(),
+                    parser.number(),
+                    parser.flag(),
+                    parser.flag(),
+                );
+
+                match (arc, point(&mut parser)) {
+                    (
+                        (Some(rx), Some(ry), Some(rotation), Some(large_arc), Some(sweep)),
+                        Some(to),
+                    ) => {
+                        segments.push(arc_segment(
+                            current,
+                            (rx, ry),
+                            rotation,
+                            large_arc,
+                            sweep,
+                            to,
+                        ));
+                        current = to;
+                    }
+                    _ => break,
+                }
+            }
+            b'Z' => {
+                if current != subpath_start {
+                    segments.push(Segment::Line(current, subpath_start));
+                }
+
+                outline.subpaths.push((std::mem::take(&mut segments), true));
+                current = subpath_start;
+            }
+            _ => break,
+        }
+
+        cubic_control = next_cubic;
+        quad_control = next_quad;
+    }
+
+    if !segments.is_empty() {
+        outline.subpaths.push((segments, false));
+    }
+
+    outline
+}
+
+/// Converts an arc command into the center parameterization of the SVG specification
+fn arc_segment(
+    from: Point2D,
+    (rx, ry): Point2D,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Point2D,
+) -> Segment {
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+
+    if rx == 0.0 || ry == 0.0 || from == to {
+        return Segment::Line(from, to);
+    }
+
+    let rotation = rotation.to_radians();
+    let (sin, cos) = rotation.sin_cos();
+
+    // The start point in the coordinates of the ellipse, relative to the middle between the end points
+    let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+    // Radii which are too small are scaled up until the ellipse fits
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+
+    let (cx, cy) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+    let center = (
+        cos * cx - sin * cy + (from.0 + to.0) / 2.0,
+        sin * cx + cos * cy + (from.1 + to.1) / 2.0,
+    );
+
+    let start = ((y1 - cy) / ry).atan2((x1 - cx) / rx);
+    let end = ((-y1 - cy) / ry).atan2((-x1 - cx) / rx);
+
+    let mut swept = end - start;
+    if sweep && swept < 0.0 {
+        swept += 2.0 * PI;
+    } else if !sweep && swept > 0.0 {
+        swept -= 2.0 * PI;
+    }
+
+    Segment::Arc {
+        center,
+        radii: (rx, ry),
+        rotation,
+        start,
+        sweep: swept,
+    }
+}
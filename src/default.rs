@@ -24,6 +24,7 @@ pub fn rect(width: i32, height: i32) -> SVGElem {
 }
 
 /// Creates a default curve with control points 1 and 2 and an end point
+#[allow(clippy::too_many_arguments)]
 pub fn curve(
     sx: i32,
     sy: i32,
@@ -68,6 +69,55 @@ pub fn set_circle_loc(elem: SVGElem, x: i32, y: i32) -> SVGElem {
     elem.set(Attr::Cx, x).set(Attr::Cy, y)
 }
 
+/// Makes the fill, stroke and stroke width of a figure and all its children inheritable
+/// from the [Style](../style/struct.Style.html) of a use, the current values are used when no style is given
+///
+/// # Examples
+/// ```rust
+/// use wasm_svg_graphics::prelude::*;
+///
+/// let mut renderer = SVGRenderer::new_headless()
+///     .expect("Failed to create renderer!");
+///
+/// let circle = SVGDefault::inherit_style(SVGDefault::circle(10));
+///
+/// // Both uses share one definition, but have a different stroke color
+/// renderer.render_styled(circle.clone(), (20.0, 20.0), Style::new().stroke("#ff0000"));
+/// renderer.render(circle, (50.0, 20.0));
+/// ```
+pub fn inherit_style(elem: SVGElem) -> SVGElem {
+    let mut inheritable = SVGElem::new(*elem.get_tag_name());
+    let mut declarations = Vec::new();
+
+    for (attribute, value) in elem.get_attributes().iter() {
+        match attribute {
+            Attr::Fill | Attr::Stroke | Attr::StrokeWidth => declarations.push(format!(
+                "{0}: var(--{0}, {1});",
+                attribute.to_string(),
+                value
+            )),
+            Attr::Style => declarations.push(value.clone()),
+            _ => inheritable = inheritable.set(attribute.clone(), value),
+        }
+    }
+
+    // The order of the attributes is not fixed, so the declarations are sorted to keep equal figures equal
+    declarations.sort();
+    if !declarations.is_empty() {
+        inheritable = inheritable.set(Attr::Style, declarations.join(" "));
+    }
+
+    if let Some(inner) = elem.get_inner() {
+        inheritable = inheritable.set_inner(inner);
+    }
+
+    elem.get_children()
+        .iter()
+        .fold(inheritable, |inheritable, child| {
+            inheritable.append(inherit_style(child.clone()))
+        })
+}
+
 fn as_point_2d(point: (i32, i32)) -> Point2D {
     (point.0 as f32, point.1 as f32)
 }
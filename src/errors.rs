@@ -1,3 +1,5 @@
+//! Errors which can occur while interacting with a [Renderer](../renderer/struct.Renderer.html)
+
 use std::error;
 use std::fmt;
 use std::error::Error;
@@ -24,11 +26,13 @@ pub enum DomError {
 
     NoParent,
 
-    UnremoveableChild
+    UnremoveableChild,
+    UnrequestableAnimationFrame,
+    UnlistenableEvent
 }
 
-impl error::Error for DomError {
-    fn description(&self) -> &str {
+impl DomError {
+    fn message(&self) -> &'static str {
         use DomError::*;
 
         match self {
@@ -41,12 +45,16 @@ impl error::Error for DomError {
             UnfindableId(_) => "Unable to find id in container",
             UnfindableTag(_) => "Unable to find tag in container",
             UnsetableAttribute(_, _) => "Unable to set attribute",
-            IdAlreadyExists(_) => "The given ID already exists within the dom: '{}'",
+            IdAlreadyExists(_) => "The given ID already exists within the dom",
             NoParent => "Container has no parent element",
-            UnremoveableChild => "Unable to remove child"
+            UnremoveableChild => "Unable to remove child",
+            UnrequestableAnimationFrame => "Unable to request animation frame",
+            UnlistenableEvent => "Unable to add event listener to element"
         }
     }
+}
 
+impl error::Error for DomError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         None
     }
@@ -60,10 +68,10 @@ impl fmt::Display for DomError {
             UnfindableId(arg0) |
             UnfindableTag(arg0) |
             IdAlreadyExists(arg0)
-                => write!(f, "Error '{}' with argument '{}'", self, arg0),
+                => write!(f, "Error '{}' with argument '{}'", self.message(), arg0),
             UnsetableAttribute(arg0, arg1)
-                => write!(f, "Error '{}' with arguments '{}' and '{}'", self, arg0, arg1),
-            _ => write!(f, "Error: {}", self)
+                => write!(f, "Error '{}' with arguments '{}' and '{}'", self.message(), arg0, arg1),
+            _ => write!(f, "Error: {}", self.message())
         }
     }
 }
@@ -74,6 +82,31 @@ pub enum RendererError {
     NameAlreadyExists(String),
     NamedNotContainer(String),
     NamedNotUse(String),
+    NamedNotLayer(String),
+
+    /// (Name) The layer would be moved past a layer with another z-index, which is changed with `set_layer_z_index`
+    LayerOutOfOrder(String),
+
+    /// (Name, Sibling)
+    NamedNotSibling(String, String),
+
+    /// (Name, Parent)
+    NamedWithinItself(String, String),
+
+    /// (Name) The transform of the named item, or of the svg for "root", scales by zero
+    SingularTransform(String),
+
+    /// (Figure Id)
+    UnfindableDefinition(u64),
+
+    /// A handle or figure id issued by another renderer was used
+    ForeignHandle,
+
+    /// (Namespace)
+    UnusableNamespace(String),
+
+    /// (Namespace, Other Namespace) The adopted svg contains ids of multiple namespaces
+    AmbiguousNamespace(String, String),
     Dom(DomError)
 }
 
@@ -92,16 +125,36 @@ impl fmt::Display for RendererError {
             NameAlreadyExists(_) => "The name is already being used".to_string(),
             NamedNotContainer(_) => "The name is not being used for a container".to_string(),
             NamedNotUse(_) => "The name is not being used for a use element".to_string(),
+            NamedNotLayer(_) => "The name is not being used for a layer".to_string(),
+            LayerOutOfOrder(_) => "The layer is ordered by its z-index, which is changed with set_layer_z_index".to_string(),
+            NamedNotSibling(_, _) => "The named items do not have the same parent".to_string(),
+            NamedWithinItself(_, _) => "The named item cannot be moved into itself".to_string(),
+            SingularTransform(_) => "The transform is unable to be inverted".to_string(),
+            UnfindableDefinition(_) => "There is no definition for the figure id".to_string(),
+            ForeignHandle => "The handle was issued by another renderer".to_string(),
+            UnusableNamespace(_) => "The namespace cannot be used by the renderer".to_string(),
+            AmbiguousNamespace(_, _) => "The svg contains ids of multiple namespaces".to_string(),
             Dom(dom_error) => dom_error.to_string(),
         };
 
         match self {
             Dom(dom_error) => write!(f, "{}", dom_error),
+            ForeignHandle => write!(f, "Error: {}", description),
             UnfindableName(arg0) |
             NameAlreadyExists(arg0) |
             NamedNotContainer(arg0) |
-            NamedNotUse(arg0)
+            NamedNotUse(arg0) |
+            NamedNotLayer(arg0) |
+            LayerOutOfOrder(arg0) |
+            SingularTransform(arg0) |
+            UnusableNamespace(arg0)
             => write!(f, "Error '{}' with argument '{}'", description, arg0),
+            NamedNotSibling(arg0, arg1) |
+            NamedWithinItself(arg0, arg1) |
+            AmbiguousNamespace(arg0, arg1)
+            => write!(f, "Error '{}' with arguments '{}' and '{}'", description, arg0, arg1),
+            UnfindableDefinition(arg0)
+            => write!(f, "Error '{}' with argument '{:x}'", description, arg0),
         }
     }
 }
\ No newline at end of file
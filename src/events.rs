@@ -0,0 +1,82 @@
+//! Pointer events on named items, see [on_named](../renderer/struct.Renderer.html#method.on_named)
+
+use svg_definitions::Point2D;
+
+/// The kind of pointer event a handler is registered for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerEventKind {
+    /// The item is clicked
+    Click,
+
+    /// A pointer is pressed on the item
+    PointerDown,
+
+    /// A pointer is released on the item
+    PointerUp,
+
+    /// A pointer moves over the item
+    PointerMove,
+
+    /// A pointer enters the item, this is not passed on to the containers of the item
+    PointerEnter,
+
+    /// A pointer leaves the item, this is not passed on to the containers of the item
+    PointerLeave,
+
+    /// The mouse wheel is scrolled over the item
+    Wheel,
+}
+
+impl PointerEventKind {
+    /// Will return the type of the DOM event
+    pub fn event_type(self) -> &'static str {
+        match self {
+            PointerEventKind::Click => "click",
+            PointerEventKind::PointerDown => "pointerdown",
+            PointerEventKind::PointerUp => "pointerup",
+            PointerEventKind::PointerMove => "pointermove",
+            PointerEventKind::PointerEnter => "pointerenter",
+            PointerEventKind::PointerLeave => "pointerleave",
+            PointerEventKind::Wheel => "wheel",
+        }
+    }
+
+    /// Will return whether the event is passed on to the containers of the item it occurred on
+    pub fn bubbles(self) -> bool {
+        !matches!(
+            self,
+            PointerEventKind::PointerEnter | PointerEventKind::PointerLeave
+        )
+    }
+}
+
+/// A pointer event on a named item, which is passed to the handlers of the item
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+    pub(crate) name: String,
+    pub(crate) kind: PointerEventKind,
+    pub(crate) position: Point2D,
+    pub(crate) wheel_delta: Point2D,
+}
+
+impl PointerEvent {
+    /// Will return the name of the named item the handler is registered on
+    pub fn name(&self) -> &str {
+        &self.name[..]
+    }
+
+    /// Will return the kind of the event
+    pub fn kind(&self) -> PointerEventKind {
+        self.kind
+    }
+
+    /// Will return the position of the pointer in the coordinates of the viewbox of the svg
+    pub fn position(&self) -> Point2D {
+        self.position
+    }
+
+    /// Will return how far the wheel is scrolled horizontally and vertically, `(0.0, 0.0)` for other events
+    pub fn wheel_delta(&self) -> Point2D {
+        self.wheel_delta
+    }
+}
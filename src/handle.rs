@@ -0,0 +1,110 @@
+//! Handles to definitions and named items, issued by a [Renderer](../renderer/struct.Renderer.html)
+
+/// Id of a figure definition, returned by [define_render](../renderer/struct.Renderer.html#method.define_render)
+///
+/// # Note
+/// A figure id can only be used with the renderer which issued it.
+/// Using it with another renderer will result in `RendererError::ForeignHandle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FigureId {
+    pub(crate) renderer: u64,
+    pub(crate) hash: u64,
+}
+
+/// Handle to a named item, returned when declaring a named figure or container
+///
+/// A handle can be used everywhere a name can be used, but does not need to be looked up by its name again.
+///
+/// # Note
+/// A handle can only be used with the renderer which issued it.
+/// Using it with another renderer will result in `RendererError::ForeignHandle`.
+/// Once the item is deleted, the handle does not refer to a new item with the same name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    pub(crate) renderer: u64,
+    pub(crate) id_hash: u64,
+    pub(crate) name: String,
+
+    /// Tells the handle apart from the handles of earlier items with the same name
+    pub(crate) generation: u64,
+}
+
+impl NodeHandle {
+    /// Will return the name of the named item
+    pub fn name(&self) -> &str {
+        &self.name[..]
+    }
+}
+
+/// Anything which can be used to refer to a named item, either a name or a [NodeHandle](struct.NodeHandle.html)
+pub trait NameRef {
+    /// Will return the name of the named item
+    fn name(&self) -> &str;
+
+    /// Will return the handle of the named item, if there is one
+    fn handle(&self) -> Option<&NodeHandle> {
+        None
+    }
+}
+
+impl NameRef for str {
+    fn name(&self) -> &str {
+        self
+    }
+}
+
+impl NameRef for String {
+    fn name(&self) -> &str {
+        &self[..]
+    }
+}
+
+impl NameRef for NodeHandle {
+    fn name(&self) -> &str {
+        &self.name[..]
+    }
+
+    fn handle(&self) -> Option<&NodeHandle> {
+        Some(self)
+    }
+}
+
+impl<T: NameRef + ?Sized> NameRef for &T {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn handle(&self) -> Option<&NodeHandle> {
+        (**self).handle()
+    }
+}
+
+/// A named item within the tree of named items, returned by [tree](../renderer/struct.Renderer.html#method.tree)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeItem {
+    pub(crate) handle: NodeHandle,
+    pub(crate) depth: usize,
+    pub(crate) is_container: bool,
+}
+
+impl TreeItem {
+    /// Will return the handle of the named item
+    pub fn handle(&self) -> &NodeHandle {
+        &self.handle
+    }
+
+    /// Will return the name of the named item
+    pub fn name(&self) -> &str {
+        self.handle.name()
+    }
+
+    /// Will return the amount of named containers the item is in, items within the root have a depth of 0
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Will return whether the item is a named container, instead of a named figure
+    pub fn is_container(&self) -> bool {
+        self.is_container
+    }
+}
@@ -77,51 +77,32 @@
 //!
 //! renderer.render(colored_smiley, (25.0, 25.0));
 //! ```
+//!
+//! ## Headless (How to render outside of the browser)
+//! ```rust
+//! use wasm_svg_graphics::prelude::*;
+//!
+//! // Declare a renderer which keeps the svg in memory
+//! let mut renderer = SVGRenderer::new_headless()
+//!     .expect("Failed to create renderer!");
+//!
+//! renderer.render(SVGDefault::circle(10), (20.0, 20.0));
+//!
+//! // Output the svg document, e.g. to save it to a file
+//! let svg = renderer.to_svg_string();
+//! ```
 
-use crate::errors::DomError::*;
-use crate::errors::RendererError;
-use crate::errors::RendererError::*;
-
-use svg_definitions::prelude::*;
-
+pub mod backend;
+pub mod bounds;
 pub mod default;
-mod errors;
+pub mod errors;
+pub mod events;
+pub mod handle;
 pub mod prelude;
 pub mod renderer;
+pub mod style;
+pub mod transform;
 
 const NAME_ID_PREFIX: &str = "named";
 const SHAPE_ID_PREFIX: &str = "figure";
 const SVG_NS: &str = "http://www.w3.org/2000/svg";
-
-fn get_document() -> Result<web_sys::Document, RendererError> {
-    let window = web_sys::window().ok_or(Dom(NoWindow))?;
-
-    window.document().ok_or(Dom(NoDocument))
-}
-
-fn create_element_ns(namespace: &str, name: &str) -> Result<web_sys::Element, RendererError> {
-    get_document()?
-        .create_element_ns(Some(namespace), name)
-        .map_err(|_| Dom(UncreatableNSElement))
-}
-
-fn to_html(svg_elem: &SVGElem) -> web_sys::Element {
-    let elem = create_element_ns(SVG_NS, &svg_elem.get_tag_name().to_string()[..])
-        .expect("Failed to create element");
-
-    svg_elem.get_attributes().iter().for_each(|(attr, value)| {
-        elem.set_attribute(&attr.to_string()[..], &value.to_string()[..])
-            .expect("Failed to set attribute");
-    });
-
-    svg_elem.get_children().iter().for_each(|child| {
-        elem.append_child(&to_html(child))
-            .expect("Failed to append child");
-    });
-
-    if let Some(inner_html) = svg_elem.get_inner() {
-        elem.set_inner_html(inner_html);
-    }
-
-    elem
-}
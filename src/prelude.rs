@@ -1,5 +1,10 @@
 //! Contains some useful definitions for SVG Graphics, also re-exports svg_definitions
 
+pub use crate::bounds::BoundingBox;
 pub use crate::default as SVGDefault;
+pub use crate::events::{PointerEvent, PointerEventKind};
+pub use crate::handle::{FigureId, NodeHandle, TreeItem};
 pub use crate::renderer::Renderer as SVGRenderer;
+pub use crate::style::{HideMode, Style};
+pub use crate::transform::Transform;
 pub use svg_definitions::prelude::*;
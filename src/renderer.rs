@@ -1,19 +1,48 @@
 //! Renderer of SVG Graphics within the webpage, contains definitions and names
 
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map::DefaultHasher;
-use std::collections::BTreeSet;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use svg_definitions::prelude::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
 
+use crate::backend::{to_element, Backend, DomBackend, HeadlessBackend, HEADLESS_ROOT_ID};
+use crate::bounds::{bounds_of, hits, BoundingBox, FigureNode, Presentation};
 use crate::errors::DomError::*;
 use crate::errors::RendererError;
 use crate::errors::RendererError::*;
-use crate::{get_document, NAME_ID_PREFIX};
+use crate::events::{PointerEvent, PointerEventKind};
+use crate::handle::{FigureId, NameRef, NodeHandle, TreeItem};
+use crate::style::{get_style_property, set_style_property, HideMode, Style, VARIABLE_ATTRIBUTES};
+use crate::transform::Transform;
+use crate::{NAME_ID_PREFIX, SHAPE_ID_PREFIX};
+
+use batch::{Batch, ElementKey};
+use spatial::SpatialIndex;
+
+mod batch;
+mod spatial;
 
 const ROOT_NAME: &str = "root";
 
+/// The attribute in which the name of a named element is stored
+const NAME_ATTRIBUTE: &str = "data-name";
+
+/// The attribute in which the z-index of a layer is stored
+const Z_INDEX_ATTRIBUTE: &str = "data-z-index";
+
+/// The attribute in which the key of a definition added with `define_as` is stored
+const DEFINITION_KEY_ATTRIBUTE: &str = "data-key";
+
+/// Used to give every renderer a unique instance id, so handles can be tied to their renderer
+static NEXT_INSTANCE_ID: AtomicU64 = AtomicU64::new(0);
+
 /// The value to which the view box of every svg [Renderer](struct.Renderer.html) will be set to by default.
 /// The viewbox of any [Renderer](struct.Renderer.html),
 /// can be adjusted with the [adjust_viewbox](struct.Renderer.html#method.adjust_viewbox) method.
@@ -23,451 +52,4888 @@ const ROOT_NAME: &str = "root";
 /// `[x, y, width, height]`
 pub const DEFAULT_VIEWBOX: [i32; 4] = [0, 0, 100, 100];
 
-/// Container object used to interact with the SVG Object
-/// Keeps track of definitions and dom root id
-pub struct Renderer {
-    /// The id of the SVG element within the dom
-    dom_root_id: String,
-
-    /// All the already defined SVG definitions
-    figure_defs: BTreeSet<u64>,
-
-    /// All the names in use
-    name_defs: HashMap<String, u64>,
+/// Canonical form of a figure, where the attributes are sorted by name
+///
+/// It can be created from both a figure and an element within the document,
+/// so definitions which are already in the document can be compared to new figures.
+#[derive(PartialEq, Eq, Hash)]
+struct CanonicalFigure {
+    tag_name: String,
+    attributes: Vec<(String, String)>,
+    inner: Option<String>,
+    children: Vec<CanonicalFigure>,
 }
 
-impl Renderer {
-    /// sets the viewbox
-    fn set_view_box(
-        element: &web_sys::Element,
-        x: i32,
-        y: i32,
-        width: i32,
-        height: i32,
-    ) -> Result<(), RendererError> {
-        let value = &format!("{} {} {} {}", x, y, width, height)[..];
+impl CanonicalFigure {
+    fn from_figure(figure: &SVGElem) -> CanonicalFigure {
+        let mut attributes: Vec<(String, String)> = figure
+            .get_attributes()
+            .iter()
+            .map(|(attr, value)| (attr.to_string(), value.clone()))
+            .collect();
+        attributes.sort();
+
+        CanonicalFigure {
+            tag_name: figure.get_tag_name().to_string(),
+            attributes,
+            inner: figure.get_inner().clone(),
+            children: figure
+                .get_children()
+                .iter()
+                .map(CanonicalFigure::from_figure)
+                .collect(),
+        }
+    }
 
-        element.set_attribute("viewBox", value).map_err(|_| {
-            Dom(UnsetableAttribute(
-                String::from("viewBox"),
-                String::from(value),
-            ))
-        })
+    /// Creates the canonical form of an element, ignoring the id attribute of a definition
+    fn from_element<B: Backend>(
+        backend: &B,
+        element: &B::Element,
+        is_def: bool,
+    ) -> CanonicalFigure {
+        let mut attributes: Vec<(String, String)> = backend
+            .attributes(element)
+            .into_iter()
+            .filter(|(name, _)| !is_def || name != "id")
+            .collect();
+        attributes.sort();
+
+        CanonicalFigure {
+            tag_name: backend.tag_name(element),
+            attributes,
+            inner: backend.text(element),
+            children: backend
+                .children(element)
+                .iter()
+                .map(|child| CanonicalFigure::from_element(backend, child, false))
+                .collect(),
+        }
     }
 
-    fn get_hash(figure: &SVGElem) -> u64 {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    fn get_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
 
-        figure.hash(&mut hasher);
+        self.hash(&mut hasher);
 
         hasher.finish()
     }
+}
 
-    fn to_def(figure: SVGElem) -> web_sys::Element {
-        let elem = crate::to_html(&figure);
-        elem.set_id(&Self::get_id_of_figure(Self::get_hash(&figure)));
-        elem
+impl FigureNode for CanonicalFigure {
+    fn tag_name(&self) -> String {
+        self.tag_name.clone()
     }
 
-    /// Will return the parent of the svg
-    fn get_root(&self) -> Result<web_sys::Element, RendererError> {
-        let document = crate::get_document()?;
-
-        document
-            .get_element_by_id(&self.dom_root_id[..])
-            .ok_or(Dom(UnfindableId(self.dom_root_id.clone())))
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.clone())
     }
 
-    /// Will return the svg root
-    fn get_svg_root(&self) -> Result<web_sys::Element, RendererError> {
-        let root = self
-            .get_root()?
-            .first_element_child()
-            .ok_or(Dom(EmptyContainer))?;
-
-        if root.tag_name() != "svg" {
-            return Err(Dom(UnfindableTag(String::from("svg"))));
+    fn for_each_child(&self, f: &mut dyn FnMut(&CanonicalFigure)) {
+        for child in self.children.iter() {
+            f(child);
         }
-
-        Ok(root)
     }
+}
 
-    /// Will return the defs element
-    fn get_defs_root(&self) -> Result<web_sys::Element, RendererError> {
-        let defs = self.get_svg_root()?.first_element_child();
-
-        match defs {
-            None => Err(Dom(EmptyContainer)),
-            Some(root) => {
-                if root.tag_name() != "defs" {
-                    return Err(Dom(UnfindableTag(String::from("defs"))));
-                }
+/// An element within the document, so the geometry of the document can be measured
+struct DocumentNode<'a, B: Backend> {
+    backend: &'a B,
+    element: B::Element,
+}
 
-                Ok(root)
-            }
-        }
+impl<'a, B: Backend> FigureNode for DocumentNode<'a, B> {
+    fn tag_name(&self) -> String {
+        self.backend.tag_name(&self.element)
     }
 
-    /// Returns whether the renderer already has a definition for the shape
-    fn contains_figure(&self, figure: &SVGElem) -> bool {
-        self.contains_id(Self::get_hash(figure))
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.backend.get_attribute(&self.element, name)
     }
 
-    /// Returns whether the renderer already has a definition for the shape
-    fn contains_id(&self, figure_id: u64) -> bool {
-        self.figure_defs.contains(&figure_id)
+    fn for_each_child(&self, f: &mut dyn FnMut(&DocumentNode<'a, B>)) {
+        for element in self.backend.children(&self.element) {
+            f(&DocumentNode {
+                backend: self.backend,
+                element,
+            });
+        }
     }
+}
 
-    /// Adds a def to the binary tree
-    fn add_def(&mut self, figure: SVGElem) -> Result<(), RendererError> {
-        let hash = Self::get_hash(&figure);
+/// A named figure or container, the element is kept so it does not have to be looked up every time
+struct NamedItem<E> {
+    name: String,
+    element: E,
+    is_container: bool,
 
-        self.get_defs_root()?
-            .append_child(&web_sys::Node::from(Self::to_def(figure)))
-            .map_err(|_| Dom(UnappendableElement))?;
+    /// The figure key of the definition used by a named use
+    figure: Option<u64>,
 
-        self.figure_defs.insert(hash);
+    /// The current transform, a use only has a transform attribute when it is not a translation
+    transform: Transform,
 
-        Ok(())
-    }
+    /// The presentation overrides of the item
+    style: Style,
 
-    /// Creates a use element from a def_id and location
-    fn create_use(
-        &self,
-        def_id: &str,
-        location: Point2D,
-    ) -> Result<web_sys::Element, RendererError> {
-        Ok(crate::to_html(
-            &SVGElem::new(Tag::Use)
-                .set(Attr::X, location.0)
-                .set(Attr::Y, location.1)
-                .set(Attr::Href, format!("#{}", def_id)),
-        ))
-    }
+    /// The z-index of a layer, only layers have a z-index
+    z_index: Option<i32>,
 
-    /// Creates a new id string from name
-    fn create_id_string(&mut self, name: &str) -> Result<String, RendererError> {
-        if name == ROOT_NAME {
-            return Err(Dom(IdAlreadyExists(String::from(ROOT_NAME))));
-        }
+    /// The id hash of the named container the item is in, `None` for the root
+    parent: Option<u64>,
 
-        if self.name_defs.contains_key(name) {
-            return Err(UnfindableName(String::from(name)));
-        }
+    /// The id hashes of the named items directly within a named container, in the order they were added
+    children: Vec<u64>,
 
-        let mut s = DefaultHasher::new();
-        name.hash(&mut s);
-        let id_hash = s.finish();
+    /// The generation of the handles of the item, which tells it apart from earlier items with the same name
+    generation: u64,
 
-        let id_string = Renderer::get_id_of_named(&id_hash);
+    /// The transform to the coordinates of the viewbox, including the transforms of the containers the item is in.
+    /// Kept up to date together with the spatial index.
+    world_transform: Transform,
 
-        let element = get_document()?.get_element_by_id(&id_string[..]);
-        match element {
-            Some(_) => Err(Dom(IdAlreadyExists(id_string))),
-            None => {
-                self.name_defs.insert(String::from(name), id_hash);
+    /// The presentation inherited from the styles of the item and the containers it is in.
+    /// Kept up to date together with the spatial index.
+    world_presentation: Presentation,
+}
 
-                Ok(id_string)
-            }
+impl<E> NamedItem<E> {
+    fn new_use(
+        name: &str,
+        element: E,
+        figure: u64,
+        transform: Transform,
+        style: Style,
+    ) -> NamedItem<E> {
+        NamedItem {
+            name: String::from(name),
+            element,
+            is_container: false,
+            figure: Some(figure),
+            transform,
+            style,
+            z_index: None,
+            parent: None,
+            children: Vec::new(),
+            generation: 0,
+            world_transform: Transform::identity(),
+            world_presentation: Presentation::default(),
         }
     }
 
-    /// Will convert a id hash into a HTML id Attribute
-    fn get_id_of_named(id_hash: &u64) -> String {
-        format!("{}-{:x}", NAME_ID_PREFIX, id_hash)
-    }
-
-    fn get_id_of_figure(id_hash: u64) -> String {
-        format!("{}-{}", super::SHAPE_ID_PREFIX, format!("{:x}", id_hash))
-    }
-
-    /// Will retrieve the web_sys element of a named container
-    fn get_named_container(&self, name: &str) -> Result<web_sys::Element, RendererError> {
-        if name == ROOT_NAME {
-            return self.get_svg_root();
-        }
-
-        let id_hash = self.name_defs.get(name);
-
-        if id_hash == None {
-            return Err(UnfindableName(String::from(name)));
-        }
-
-        let id_hash = id_hash.unwrap();
-
-        let container = get_document()?
-            .get_element_by_id(&Renderer::get_id_of_named(id_hash)[..])
-            .ok_or(Dom(UnfindableId(Renderer::get_id_of_named(id_hash))))?;
-
-        if container.tag_name() != "g" {
-            return Err(NamedNotContainer(String::from(name)));
+    fn new_container(name: &str, element: E) -> NamedItem<E> {
+        NamedItem {
+            name: String::from(name),
+            element,
+            is_container: true,
+            figure: None,
+            transform: Transform::identity(),
+            style: Style::default(),
+            z_index: None,
+            parent: None,
+            children: Vec::new(),
+            generation: 0,
+            world_transform: Transform::identity(),
+            world_presentation: Presentation::default(),
         }
-
-        Ok(container)
     }
+}
 
-    /// Will retrieve the web_sys element of a named container
-    fn get_named_item(&self, name: &str) -> Result<web_sys::Element, RendererError> {
-        if name == ROOT_NAME {
-            return self.get_svg_root();
-        }
-
-        let id_hash = self.name_defs.get(name);
+/// The position of a named item within its parent
+struct Siblings<E> {
+    parent: E,
+    element: E,
 
-        if id_hash == None {
-            return Err(UnfindableName(String::from(name)));
-        }
+    /// All other children of the parent, except for the defs
+    others: Vec<E>,
 
-        let id_hash = id_hash.unwrap();
+    /// The index of the named item within the other children
+    index: usize,
+}
 
-        let container = get_document()?
-            .get_element_by_id(&Renderer::get_id_of_named(id_hash)[..])
-            .ok_or(Dom(UnfindableId(Renderer::get_id_of_named(id_hash))))?;
+/// The event listeners of a named item
+struct Listeners<L> {
+    /// The name passed to the handlers, which is shared so it can be changed when the item is renamed
+    name: Rc<RefCell<String>>,
 
-        Ok(container)
-    }
+    listeners: Vec<(PointerEventKind, L)>,
+}
 
-    /// Will add a use element to the root svg
-    fn add_use(&self, def_id: &str, location: Point2D) -> Result<(), RendererError> {
-        let root = self.get_svg_root()?;
-        let use_element = self.create_use(def_id, location)?;
+/// The named items and references within a copy, which are registered once the whole copy succeeded
+struct Copied<E> {
+    /// The handle, the key of the parent and the item of every named copy, parents before their children
+    names: Vec<(NodeHandle, ElementKey, NamedItem<E>)>,
 
-        root.append_child(&use_element)
-            .map_err(|_| Dom(UnappendableElement))
-            .map(|_| ())
-    }
+    /// The figure keys referenced by the uses within the copy
+    references: Vec<u64>,
+}
 
-    /// Will add a use element to the root svg with a name
-    fn add_named_use(
-        &mut self,
-        name: &str,
-        def_id: &str,
-        location: Point2D,
-    ) -> Result<String, RendererError> {
-        let id_string = self.create_id_string(name)?;
+/// A figure which has been added to the definitions
+struct Definition {
+    /// The canonical form of the figure, used to verify a figure with the same hash is actually the same
+    figure: CanonicalFigure,
 
-        let root = self.get_svg_root()?;
-        let use_element = self.create_use(def_id, location)?;
-        use_element.set_id(&id_string[..]);
+    /// The amount of uses referencing the definition
+    uses: usize,
 
-        root.append_child(&use_element)
-            .map_err(|_| Dom(UnappendableElement))?;
+    /// The key of a definition added with `define_as`, such a definition is never shared or collected
+    key: Option<String>,
 
-        Ok(id_string)
-    }
+    /// The hash of the figure, or of the key for a definition added with `define_as`
+    hash: u64,
+}
 
-    /// Will add a use element to a named container
-    fn add_use_to(
-        &mut self,
-        name: &str,
-        def_id: &str,
-        location: Point2D,
-    ) -> Result<(), RendererError> {
-        let container = self.get_named_container(name)?;
-        let use_element = self.create_use(def_id, location)?;
+/// Will return the values of the x and y attributes of a use at a location,
+/// in full precision like a [Transform](../transform/struct.Transform.html) so the location can be read back
+fn format_location(location: Point2D) -> (String, String) {
+    (location.0.to_string(), location.1.to_string())
+}
 
-        container
-            .append_child(&use_element)
-            .map_err(|_| Dom(UnappendableElement))
-            .map(|_| ())
-    }
+/// Will return whether a namespace can be part of the ids of the renderer
+fn is_usable_namespace(namespace: &str) -> bool {
+    !namespace.is_empty() && !namespace.contains(|c: char| c.is_whitespace() || c == '#')
+}
 
-    /// Adjust a named use to another figure
-    fn adjust_use_to(
-        &mut self,
-        name: &str,
-        def_id: &str,
-        location: Point2D,
-    ) -> Result<(), RendererError> {
-        if name == ROOT_NAME {
-            return Err(NamedNotUse(String::from(ROOT_NAME)));
-        }
+/// Will return the namespace of an id in the form `<prefix>-<namespace>-<hash>`
+fn parse_namespace(id: &str, prefix: &str) -> Option<String> {
+    let (namespace, hex) = id
+        .strip_prefix(prefix)?
+        .strip_prefix('-')?
+        .rsplit_once('-')?;
+    u64::from_str_radix(hex, 16).ok()?;
 
-        let id_hash = self.name_defs.get(name);
+    if namespace.is_empty() {
+        return None;
+    }
 
-        if id_hash == None {
-            return Err(UnfindableName(String::from(name)));
-        }
+    Some(String::from(namespace))
+}
 
-        let id_hash = id_hash.unwrap();
+/// Container object used to interact with the SVG Object
+/// Keeps track of definitions and dom root id
+///
+/// # Note
+/// All interaction with the document goes through a [Backend](../backend/trait.Backend.html),
+/// by default this is the [DomBackend](../backend/struct.DomBackend.html).
+pub struct Renderer<B: Backend = DomBackend> {
+    /// The backend used to interact with the document
+    backend: B,
 
-        let use_element = get_document()?
-            .get_element_by_id(&Renderer::get_id_of_named(id_hash)[..])
-            .ok_or(Dom(UnfindableId(Renderer::get_id_of_named(id_hash))))?;
+    /// The unique id of this renderer, used to verify handles
+    instance_id: u64,
 
-        if use_element.tag_name() != "use" {
-            return Err(NamedNotUse(String::from(name)));
-        }
+    /// The id of the SVG element within the dom
+    dom_root_id: String,
 
-        let value = &format!("#{}", def_id)[..];
-        use_element.set_attribute("href", value).map_err(|_| {
-            Dom(UnsetableAttribute(
-                String::from("href"),
-                String::from(value),
-            ))
-        })?;
+    /// The svg element
+    svg_root: B::Element,
 
-        let value = &format!("{:.2}", location.0)[..];
-        use_element
-            .set_attribute("x", value)
-            .map_err(|_| Dom(UnsetableAttribute(String::from("x"), String::from(value))))?;
+    /// The defs element within the svg
+    defs_root: B::Element,
 
-        let value = &format!("{:.2}", location.1)[..];
-        use_element
-            .set_attribute("y", value)
-            .map_err(|_| Dom(UnsetableAttribute(String::from("y"), String::from(value))))?;
+    /// Part of the id of every definition and named item, so multiple renderers can share a document
+    namespace: String,
 
-        Ok(())
-    }
+    /// All the already defined SVG definitions by their figure key
+    ///
+    /// The figure key is the hash of the figure, unless another figure with the same hash
+    /// was already defined, in which case it is the next free key after the hash.
+    figure_defs: BTreeMap<u64, Definition>,
 
-    /// Deletes a use element
-    fn delete_use(&mut self, name: &str) -> Result<(), RendererError> {
-        if name == ROOT_NAME {
-            return Err(NamedNotUse(String::from(ROOT_NAME)));
-        }
+    /// The figure keys of the definitions by their hash, so colliding definitions are found
+    /// without depending on the definitions placed between the hash and their figure key
+    figure_keys: HashMap<u64, Vec<u64>>,
 
-        let id_hash = self.name_defs.get(name);
+    /// Whether definitions are removed as soon as they are no longer used
+    auto_collect_garbage: bool,
 
-        if id_hash == None {
-            return Err(UnfindableName(String::from(name)));
-        }
+    /// The figure keys of the definitions added with `define_as`, by their key
+    keyed_defs: HashMap<String, u64>,
 
-        let id_hash = id_hash.unwrap();
+    /// All the names in use
+    name_defs: HashMap<String, u64>,
 
-        let use_element = get_document()?
-            .get_element_by_id(&Renderer::get_id_of_named(id_hash)[..])
-            .ok_or(Dom(UnfindableId(Renderer::get_id_of_named(id_hash))))?;
+    /// All the named items by their id hash
+    named_items: HashMap<u64, NamedItem<B::Element>>,
 
-        if use_element.tag_name() != "use" {
-            return Err(NamedNotUse(String::from(name)));
-        }
+    /// The generation of the next handle, see `NamedItem::generation`
+    next_generation: Cell<u64>,
 
-        let parent = use_element.parent_element().ok_or(Dom(NoParent))?;
+    /// The event listeners of named items by their id hash, a listener is removed when it is dropped
+    event_listeners: HashMap<u64, Listeners<B::Listener>>,
 
-        parent
-            .remove_child(&use_element)
-            .map_err(|_| Dom(UnremoveableChild))?;
+    /// The bounding boxes of the named figures in the coordinates of the viewbox
+    spatial_index: SpatialIndex,
 
-        Ok(())
-    }
+    /// The queued operations, while batching
+    batch: RefCell<Option<Batch<B::Element>>>,
+}
 
+impl Renderer<DomBackend> {
     /// Create new renderer object
     ///
     /// # Arguments
     /// * `dom_root_id` - The HTMl Attribute ID of the parent element of to be created SVG
     pub fn new(dom_root_id: &str) -> Result<Renderer, RendererError> {
-        let document = crate::get_document()?;
-
-        let root = document
-            .get_element_by_id(dom_root_id)
-            .ok_or(Dom(UnfindableId(String::from(dom_root_id))))?;
-
-        let svg_element = SVGElem::new(Tag::Svg)
-            .set(
-                Attr::ViewBox,
-                format!(
-                    "{} {} {} {}",
-                    DEFAULT_VIEWBOX[0], DEFAULT_VIEWBOX[1], DEFAULT_VIEWBOX[2], DEFAULT_VIEWBOX[3],
-                ),
-            )
-            .append(SVGElem::new(Tag::Defs));
-
-        root.append_child(&crate::to_html(&svg_element))
-            .map_err(|_| Dom(UnappendableElement))?;
-
-        Ok(Renderer {
-            dom_root_id: String::from(dom_root_id),
-            figure_defs: BTreeSet::new(),
-            name_defs: HashMap::new(),
-        })
+        Self::with_backend(DomBackend::new()?, dom_root_id)
     }
 
     /// Creates renderer object from svg element
     ///
-    /// # Note
-    /// * This function will panic if the root of the `svg_elem` is not a TagName::Svg
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the root of the `svg_elem` is not a TagName::Svg
     ///
     /// # Arguments
     /// * `dom_root_id` - The HTMl Attribute ID of the parent element of to be created SVG
     /// * `svg_elem` - The svg element to from the renderer from
     pub fn new_from_svg(dom_root_id: &str, svg_elem: SVGElem) -> Result<Renderer, RendererError> {
-        let document = crate::get_document()?;
+        Self::with_backend_from_svg(DomBackend::new()?, dom_root_id, svg_elem)
+    }
 
-        let root = document
-            .get_element_by_id(dom_root_id)
-            .ok_or(Dom(UnfindableId(String::from(dom_root_id))))?;
+    /// Creates renderer object from a svg which is already in the DOM, e.g. a server-rendered svg.
+    /// The definitions and names are rebuilt from the existing svg, see [resync](#method.resync).
+    ///
+    /// # Note
+    /// The [namespace](#method.namespace) is taken from the ids within the svg,
+    /// so the svg can be adopted under another parent than the one it was created in.
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the parent does not contain a svg
+    /// with a defs element as its first child,
+    /// or `RendererError::AmbiguousNamespace` when the svg contains ids of multiple namespaces
+    ///
+    /// # Arguments
+    /// * `dom_root_id` - The HTMl Attribute ID of the parent element of the existing SVG
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // The svg was rendered on the server within the element with id 'svg_parent_id'
+    /// let mut renderer = SVGRenderer::adopt("svg_parent_id")
+    ///     .expect("Failed to adopt svg!");
+    ///
+    /// // Names declared on the server can be used directly
+    /// renderer.move_named("named_circle", (20.0, 20.0));
+    /// ```
+    pub fn adopt(dom_root_id: &str) -> Result<Renderer, RendererError> {
+        Self::with_backend_adopt(DomBackend::new()?, dom_root_id)
+    }
 
-        if *svg_elem.get_tag_name() != Tag::Svg {
-            panic!("Head is not a Svg Element");
-        }
+    /// Creates renderer object from a svg which is already in the DOM,
+    /// only adopting the definitions and names within a certain namespace.
+    /// See [adopt](#method.adopt).
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the parent does not contain a svg
+    /// with a defs element as its first child,
+    /// or `RendererError::UnusableNamespace` when the namespace cannot be used
+    /// or the svg contains ids of other namespaces only
+    ///
+    /// # Arguments
+    /// * `dom_root_id` - The HTMl Attribute ID of the parent element of the existing SVG
+    /// * `namespace` - The namespace of the definitions and names to adopt
+    pub fn adopt_with_namespace(
+        dom_root_id: &str,
+        namespace: &str,
+    ) -> Result<Renderer, RendererError> {
+        Self::with_backend_adopt_with_namespace(DomBackend::new()?, dom_root_id, namespace)
+    }
 
-        let mut svg_element = SVGElem::new(Tag::Svg).append(SVGElem::new(Tag::Defs));
-        for (attr, value) in svg_elem.get_attributes().iter() {
-            svg_element = svg_element.set(attr.clone(), value);
+    /// Schedules a [flush](#method.flush) of the queued operations on the next animation frame.
+    /// Scheduling multiple times before the next animation frame results in a single flush.
+    ///
+    /// # Arguments
+    /// * `renderer` - The shared renderer to flush
+    ///
+    /// # Note
+    /// This does nothing when the renderer is not batching, see [set_batching](#method.set_batching).
+    /// When the renderer is borrowed mutably as the animation frame occurs, the flush moves to the next animation frame.
+    /// When the flush fails, the queued operations are kept, so the next flush retries them.
+    ///
+    /// # Errors
+    /// Will return `DomError::UnrequestableAnimationFrame` when the animation frame cannot be requested
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer
+    /// let renderer = Rc::new(RefCell::new(
+    ///     SVGRenderer::new("svg_parent_id").expect("Failed to create renderer!"),
+    /// ));
+    ///
+    /// renderer.borrow_mut().set_batching(true);
+    /// renderer
+    ///     .borrow_mut()
+    ///     .render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // --snip, e.g. within a simulation step
+    ///
+    /// renderer.borrow_mut().move_named("named_circle", (20.0, 20.0));
+    /// SVGRenderer::flush_on_animation_frame(&renderer).expect("Failed to request animation frame!");
+    /// ```
+    pub fn flush_on_animation_frame(renderer: &Rc<RefCell<Renderer>>) -> Result<(), RendererError> {
+        match renderer.borrow().batch.borrow().as_ref() {
+            Some(batch) if !batch.scheduled => {}
+            _ => return Ok(()),
         }
 
-        for child in svg_elem.get_children().iter() {
-            svg_element = svg_element.append(child.clone());
+        Self::request_flush_frame(Rc::downgrade(renderer))?;
+
+        if let Some(batch) = renderer.borrow().batch.borrow_mut().as_mut() {
+            batch.scheduled = true;
         }
 
-        root.append_child(&crate::to_html(&svg_element))
-            .map_err(|_| Dom(UnappendableElement))?;
+        Ok(())
+    }
 
-        Ok(Renderer {
-            dom_root_id: String::from(dom_root_id),
-            figure_defs: BTreeSet::new(),
-            name_defs: HashMap::new(),
-        })
+    /// Requests an animation frame which flushes the renderer,
+    /// which is requested again when the renderer is borrowed mutably as the animation frame occurs
+    fn request_flush_frame(weak_renderer: Weak<RefCell<Renderer>>) -> Result<(), RendererError> {
+        let callback = Closure::once_into_js(move || {
+            if let Some(shared) = weak_renderer.upgrade() {
+                let renderer = match shared.try_borrow() {
+                    Ok(renderer) => renderer,
+                    Err(_) => {
+                        // Errors cannot be returned from an animation frame
+                        let _ = Self::request_flush_frame(Rc::downgrade(&shared));
+                        return;
+                    }
+                };
+
+                if let Some(batch) = renderer.batch.borrow_mut().as_mut() {
+                    batch.scheduled = false;
+                }
+
+                // Errors cannot be returned from an animation frame,
+                // the queued operations are kept so the next flush retries them
+                let _ = renderer.try_flush();
+            }
+        });
+
+        web_sys::window()
+            .ok_or(Dom(NoWindow))?
+            .request_animation_frame(callback.unchecked_ref())
+            .map_err(|_| Dom(UnrequestableAnimationFrame))?;
+
+        Ok(())
     }
+}
 
-    /// Render figure at a location (this will automatically add a definition when needed)
-    ///
-    /// # Arguments
-    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
-    /// * `location` - the location where to add the `figure`
+impl Renderer<HeadlessBackend> {
+    /// Create new renderer object which keeps the SVG in memory instead of in the DOM,
+    /// this works outside of the browser.
     ///
     /// # Examples
-    ///
-    /// ```rust,no_run
+    /// ```rust
     /// use wasm_svg_graphics::prelude::*;
     ///
     /// // Declare renderer (must be mutable)
-    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    /// let mut renderer = SVGRenderer::new_headless()
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render(circle, (20.0, 20.0));
+    /// // Output the whole SVG document
+    /// println!("{}", renderer.to_svg_string());
     /// ```
-    pub fn render(&mut self, figure: SVGElem, location: Point2D) {
-        let figure_id = Self::get_id_of_figure(Self::get_hash(&figure));
+    pub fn new_headless() -> Result<Renderer<HeadlessBackend>, RendererError> {
+        Self::with_backend(HeadlessBackend::new(HEADLESS_ROOT_ID), HEADLESS_ROOT_ID)
+    }
 
-        // If there is already a definition
-        if !self.contains_figure(&figure) {
-            // Add the definition to the dom and hashes
-            self.add_def(figure).expect("Failed to add definition!");
-        }
+    /// Creates renderer object which keeps the SVG in memory from svg element
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the root of the `svg_elem` is not a TagName::Svg
+    ///
+    /// # Arguments
+    /// * `svg_elem` - The svg element to from the renderer from
+    pub fn new_headless_from_svg(
+        svg_elem: SVGElem,
+    ) -> Result<Renderer<HeadlessBackend>, RendererError> {
+        Self::with_backend_from_svg(
+            HeadlessBackend::new(HEADLESS_ROOT_ID),
+            HEADLESS_ROOT_ID,
+            svg_elem,
+        )
+    }
 
-        // Add use of definition
-        self.add_use(&figure_id[..], location)
-            .expect("Failed to add use!");
+    /// Serializes the whole SVG document, including the definitions, to a string
+    ///
+    /// # Panics
+    /// Will panic when [try_to_svg_string](#method.try_to_svg_string) returns an error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// let renderer = SVGRenderer::new_headless()
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// assert_eq!(
+    ///     renderer.to_svg_string(),
+    ///     r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100"><defs/></svg>"#
+    /// );
+    /// ```
+    pub fn to_svg_string(&self) -> String {
+        self.try_to_svg_string()
+            .expect("Failed to retrieve SVG container!")
     }
 
-    /// Render a named figure at a location (this will automatically add a definition when needed)
-    /// One can use named-figures for later adjustments, e.g. updating the location of the figure, hiding/showing the figure
+    /// Fallible version of [to_svg_string](#method.to_svg_string)
+    ///
+    /// # Errors
+    /// Will return an error when the svg cannot be found
+    pub fn try_to_svg_string(&self) -> Result<String, RendererError> {
+        Ok(self.get_svg_root()?.to_svg_string())
+    }
+
+    /// Simulates a pointer event on a named item, which calls the handlers registered with
+    /// [on_named](#method.on_named) in the same way as an event within the browser would
     ///
     /// # Arguments
-    /// * `name` - Name to use for later reference
-    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
-    /// * `location` - the location where to add the `figure`
+    /// * `name` - Name of the item the event occurs on
+    /// * `kind` - The kind of pointer event
+    /// * `position` - The position of the pointer in the coordinates of the viewbox
+    /// * `wheel_delta` - How far the wheel is scrolled, only used for a wheel event
+    ///
+    /// # Panics
+    /// Will panic when [try_dispatch_pointer_event](#method.try_dispatch_pointer_event) returns an error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// let mut renderer = SVGRenderer::new_headless()
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("button", SVGDefault::circle(10), (50.0, 50.0));
+    ///
+    /// let clicks = Rc::new(Cell::new(0));
+    /// let counter = clicks.clone();
+    /// renderer.on_named("button", PointerEventKind::Click, move |_| {
+    ///     counter.set(counter.get() + 1);
+    /// });
+    ///
+    /// renderer.dispatch_pointer_event("button", PointerEventKind::Click, (50.0, 50.0), (0.0, 0.0));
+    /// assert_eq!(clicks.get(), 1);
+    /// ```
+    pub fn dispatch_pointer_event<N: NameRef>(
+        &self,
+        name: N,
+        kind: PointerEventKind,
+        position: Point2D,
+        wheel_delta: Point2D,
+    ) {
+        self.try_dispatch_pointer_event(name, kind, position, wheel_delta)
+            .expect("Failed to dispatch pointer event!");
+    }
+
+    /// Fallible version of [dispatch_pointer_event](#method.dispatch_pointer_event)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and an error when queued operations cannot be applied
+    pub fn try_dispatch_pointer_event<N: NameRef>(
+        &self,
+        name: N,
+        kind: PointerEventKind,
+        position: Point2D,
+        wheel_delta: Point2D,
+    ) -> Result<(), RendererError> {
+        // The event is passed on to the containers of the item, so it has to be in the document
+        self.apply_batch()?;
+
+        let wheel_delta = match kind {
+            PointerEventKind::Wheel => wheel_delta,
+            _ => (0.0, 0.0),
+        };
+
+        self.get_named_element(&name)?.dispatch_event(
+            kind.event_type(),
+            kind.bubbles(),
+            position,
+            wheel_delta,
+        );
+
+        Ok(())
+    }
+}
+
+impl<B: Backend> Renderer<B> {
+    /// sets the viewbox
+    fn set_view_box(
+        &self,
+        element: &B::Element,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<(), RendererError> {
+        let value = &format!("{} {} {} {}", x, y, width, height)[..];
+
+        self.write_attribute(None, element, "viewBox", Some(value))
+    }
+
+    fn to_def(&self, figure_key: u64, figure: &SVGElem) -> Result<B::Element, RendererError> {
+        let elem = to_element(&self.backend, figure)?;
+        self.backend
+            .set_attribute(&elem, "id", &self.get_id_of_figure(figure_key)[..])?;
+        Ok(elem)
+    }
+
+    /// Will return the parent of the svg
+    fn find_root(backend: &B, dom_root_id: &str) -> Result<B::Element, RendererError> {
+        backend
+            .get_element_by_id(dom_root_id)
+            .ok_or_else(|| Dom(UnfindableId(String::from(dom_root_id))))
+    }
+
+    /// Will look up the svg root within the document
+    fn find_svg_root(backend: &B, dom_root_id: &str) -> Result<B::Element, RendererError> {
+        let root = backend
+            .first_element_child(&Self::find_root(backend, dom_root_id)?)
+            .ok_or(Dom(EmptyContainer))?;
+
+        if backend.tag_name(&root) != "svg" {
+            return Err(Dom(UnfindableTag(String::from("svg"))));
+        }
+
+        Ok(root)
+    }
+
+    /// Will look up the defs element within the svg root
+    fn find_defs_root(backend: &B, svg_root: &B::Element) -> Result<B::Element, RendererError> {
+        let defs = backend.first_element_child(svg_root);
+
+        match defs {
+            None => Err(Dom(EmptyContainer)),
+            Some(root) => {
+                if backend.tag_name(&root) != "defs" {
+                    return Err(Dom(UnfindableTag(String::from("defs"))));
+                }
+
+                Ok(root)
+            }
+        }
+    }
+
+    /// Will return the svg root
+    fn get_svg_root(&self) -> Result<B::Element, RendererError> {
+        Ok(self.svg_root.clone())
+    }
+
+    /// Will return the defs element
+    fn get_defs_root(&self) -> Result<B::Element, RendererError> {
+        Ok(self.defs_root.clone())
+    }
+
+    /// Will return the key of the definition of a figure with a hash
+    ///
+    /// # Note
+    /// A definition with the same hash is only used when its figure is actually the same.
+    fn find_figure(&self, hash: u64, figure: &CanonicalFigure) -> Option<u64> {
+        self.figure_keys
+            .get(&hash)?
+            .iter()
+            .copied()
+            .find(|figure_key| {
+                self.figure_defs.get(figure_key).is_some_and(|definition| {
+                    definition.key.is_none() && definition.figure == *figure
+                })
+            })
+    }
+
+    /// Will return the first free figure key starting at a hash
+    fn get_free_key(&self, hash: u64) -> u64 {
+        let mut figure_key = hash;
+
+        while self.figure_defs.contains_key(&figure_key) {
+            figure_key = figure_key.wrapping_add(1);
+        }
+
+        figure_key
+    }
+
+    /// Will return the hash of the key of a definition added with `define_as`
+    fn get_key_hash(key: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Registers a definition under its figure key
+    fn insert_definition(&mut self, figure_key: u64, definition: Definition) {
+        self.figure_keys
+            .entry(definition.hash)
+            .or_default()
+            .push(figure_key);
+        self.figure_defs.insert(figure_key, definition);
+    }
+
+    /// Returns whether the renderer already has a definition for the shape
+    fn contains_id(&self, figure_id: u64) -> bool {
+        self.figure_defs.contains_key(&figure_id)
+    }
+
+    /// Will verify that a figure id belongs to a definition of this renderer and return its key
+    fn check_figure_id(&self, figure_id: FigureId) -> Result<u64, RendererError> {
+        if figure_id.renderer != self.instance_id {
+            return Err(ForeignHandle);
+        }
+
+        if !self.contains_id(figure_id.hash) {
+            return Err(UnfindableDefinition(figure_id.hash));
+        }
+
+        Ok(figure_id.hash)
+    }
+
+    /// Adds a def to the binary tree at the first free figure key starting at its hash
+    /// and will return the figure key
+    fn add_def(
+        &mut self,
+        hash: u64,
+        figure: &SVGElem,
+        canonical: CanonicalFigure,
+        key: Option<String>,
+    ) -> Result<u64, RendererError> {
+        let figure_key = self.get_free_key(hash);
+
+        let def = self.to_def(figure_key, figure)?;
+        if let Some(key) = key.as_ref() {
+            self.backend
+                .set_attribute(&def, DEFINITION_KEY_ATTRIBUTE, &key[..])?;
+        }
+
+        self.backend.append_child(&self.get_defs_root()?, &def)?;
+
+        self.insert_definition(
+            figure_key,
+            Definition {
+                figure: canonical,
+                uses: 0,
+                key,
+                hash,
+            },
+        );
+
+        Ok(figure_key)
+    }
+
+    /// Replaces the figure of a def, all uses referencing the def are updated by the document
+    fn replace_def(
+        &mut self,
+        figure_key: u64,
+        figure: &SVGElem,
+        canonical: CanonicalFigure,
+    ) -> Result<(), RendererError> {
+        let def_id = self.get_id_of_figure(figure_key);
+        let old_def = self
+            .backend
+            .get_element_by_id(&def_id[..])
+            .ok_or(Dom(UnfindableId(def_id)))?;
+
+        let def = self.to_def(figure_key, figure)?;
+        if let Some(key) = self
+            .figure_defs
+            .get(&figure_key)
+            .and_then(|definition| definition.key.as_ref())
+        {
+            self.backend
+                .set_attribute(&def, DEFINITION_KEY_ATTRIBUTE, &key[..])?;
+        }
+
+        self.backend
+            .replace_child(&self.get_defs_root()?, &def, &old_def)?;
+
+        if let Some(definition) = self.figure_defs.get_mut(&figure_key) {
+            definition.figure = canonical;
+        }
+
+        let uses: Vec<u64> = self
+            .named_items
+            .iter()
+            .filter(|(_, item)| item.figure == Some(figure_key))
+            .map(|(id_hash, _)| *id_hash)
+            .collect();
+        for id_hash in uses {
+            self.update_spatial_index(id_hash);
+        }
+
+        Ok(())
+    }
+
+    /// Removes a def from the dom and the binary tree
+    fn remove_def(&mut self, figure_key: u64) -> Result<(), RendererError> {
+        let def_id = self.get_id_of_figure(figure_key);
+
+        if let Some(def) = self.backend.get_element_by_id(&def_id[..]) {
+            self.remove_element(&def)?;
+        }
+
+        if let Some(definition) = self.figure_defs.remove(&figure_key) {
+            if let Some(figure_keys) = self.figure_keys.get_mut(&definition.hash) {
+                figure_keys.retain(|key| *key != figure_key);
+
+                if figure_keys.is_empty() {
+                    self.figure_keys.remove(&definition.hash);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Increments the amount of uses referencing a definition
+    fn add_reference(&mut self, figure_key: u64) {
+        if let Some(definition) = self.figure_defs.get_mut(&figure_key) {
+            definition.uses += 1;
+        }
+    }
+
+    /// Decrements the amount of uses referencing a definition,
+    /// when automatic garbage collection is enabled an unused definition is removed
+    fn remove_reference(&mut self, figure_key: u64) -> Result<(), RendererError> {
+        if let Some(definition) = self.figure_defs.get_mut(&figure_key) {
+            definition.uses = definition.uses.saturating_sub(1);
+
+            if definition.uses == 0 && definition.key.is_none() && self.auto_collect_garbage {
+                self.remove_def(figure_key)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Will return the figure keys referenced by all uses within an element, including the element itself
+    fn get_references_within(&self, element: &B::Element) -> Vec<u64> {
+        let mut references = Vec::new();
+
+        if self.backend.tag_name(element) == "use" {
+            if let Some(figure_key) = self
+                .backend
+                .get_attribute(element, "href")
+                .and_then(|href| self.get_figure_of_href(&href[..]))
+            {
+                references.push(figure_key);
+            }
+        }
+
+        for child in self.backend.children(element).iter() {
+            references.append(&mut self.get_references_within(child));
+        }
+
+        references
+    }
+
+    /// Removes a list of references, see [remove_reference](#method.remove_reference)
+    fn remove_references(&mut self, references: Vec<u64>) -> Result<(), RendererError> {
+        for figure_key in references {
+            self.remove_reference(figure_key)?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a use element from a figure key and transform,
+    /// a translation is set as location and any other transform as transform attribute
+    fn create_use(
+        &self,
+        figure_key: u64,
+        transform: &Transform,
+        style: &Style,
+    ) -> Result<B::Element, RendererError> {
+        let use_element = self.backend.create_element("use")?;
+
+        match transform.as_translation() {
+            Some(location) => {
+                let (x, y) = format_location(location);
+                self.backend.set_attribute(&use_element, "x", &x[..])?;
+                self.backend.set_attribute(&use_element, "y", &y[..])?;
+            }
+            None => {
+                self.backend.set_attribute(
+                    &use_element,
+                    "transform",
+                    &transform.to_string()[..],
+                )?;
+            }
+        }
+        self.backend.set_attribute(
+            &use_element,
+            "href",
+            &self.get_href_of_figure(figure_key)[..],
+        )?;
+
+        let mut variables = None;
+        for (name, value) in style.attributes().iter() {
+            if let Some(value) = value {
+                self.backend.set_attribute(&use_element, name, value)?;
+
+                if VARIABLE_ATTRIBUTES.contains(name) {
+                    variables = set_style_property(
+                        variables.as_deref(),
+                        &format!("--{}", name)[..],
+                        Some(value),
+                    );
+                }
+            }
+        }
+        if let Some(variables) = variables {
+            self.backend
+                .set_attribute(&use_element, "style", &variables[..])?;
+        }
+
+        Ok(use_element)
+    }
+
+    /// Creates a new handle from name, the name is only in use after it has been added with `add_name`
+    fn create_handle(&self, name: &str) -> Result<NodeHandle, RendererError> {
+        if name == ROOT_NAME || self.name_defs.contains_key(name) {
+            return Err(NameAlreadyExists(String::from(name)));
+        }
+
+        let mut s = DefaultHasher::new();
+        name.hash(&mut s);
+        let id_hash = s.finish();
+
+        let id_string = self.get_id_of_named(&id_hash);
+
+        let element = self.backend.get_element_by_id(&id_string[..]);
+        match element {
+            Some(_) => Err(Dom(IdAlreadyExists(id_string))),
+            None => Ok(NodeHandle {
+                renderer: self.instance_id,
+                id_hash,
+                name: String::from(name),
+                generation: self.next_generation(),
+            }),
+        }
+    }
+
+    /// Will return the generation for a new handle, every handle gets a later generation than the ones before
+    fn next_generation(&self) -> u64 {
+        let generation = self.next_generation.get();
+        self.next_generation.set(generation + 1);
+
+        generation
+    }
+
+    /// Sets the id of a named element, the name itself is also stored so it can be recovered by a resync
+    fn set_named_id(&self, element: &B::Element, handle: &NodeHandle) -> Result<(), RendererError> {
+        self.backend
+            .set_attribute(element, "id", &self.get_id_of_named(&handle.id_hash)[..])?;
+        self.backend
+            .set_attribute(element, NAME_ATTRIBUTE, handle.name())
+    }
+
+    /// Adds a name to the internal entries, as child of the named container with key `parent`
+    fn add_name(
+        &mut self,
+        handle: &NodeHandle,
+        parent: ElementKey,
+        mut item: NamedItem<B::Element>,
+    ) {
+        let id_hash = handle.id_hash;
+        item.generation = handle.generation;
+
+        if let Some(parent_item) = parent.and_then(|parent| self.named_items.get_mut(&parent)) {
+            parent_item.children.push(id_hash);
+        }
+
+        item.parent = parent;
+        self.name_defs.insert(item.name.clone(), id_hash);
+        self.named_items.insert(id_hash, item);
+        self.update_spatial_index(id_hash);
+    }
+
+    /// Will return the key of a named item within a batch
+    fn get_item_key(&self, name: &dyn NameRef) -> Result<ElementKey, RendererError> {
+        if name.name() == ROOT_NAME {
+            return Ok(None);
+        }
+
+        Ok(Some(self.get_id_hash(name)?))
+    }
+
+    /// Sets or removes an attribute of a named item, this is queued while batching
+    fn write_attribute(
+        &self,
+        key: ElementKey,
+        element: &B::Element,
+        name: &str,
+        value: Option<&str>,
+    ) -> Result<(), RendererError> {
+        if let Some(batch) = self.batch.borrow_mut().as_mut() {
+            batch.set_attribute(key, element, name, value);
+            return Ok(());
+        }
+
+        match value {
+            Some(value) => self.backend.set_attribute(element, name, value),
+            None => self.backend.remove_attribute(element, name),
+        }
+    }
+
+    /// Will return the value of an attribute of a named item, including changes which are still queued
+    fn read_attribute(&self, key: ElementKey, element: &B::Element, name: &str) -> Option<String> {
+        let pending = self
+            .batch
+            .borrow()
+            .as_ref()
+            .and_then(|batch| batch.get_attribute(key, name));
+
+        match pending {
+            Some(value) => value,
+            None => self.backend.get_attribute(element, name),
+        }
+    }
+
+    /// Sets or removes a single property in the style attribute of a named item,
+    /// while keeping all other properties
+    fn write_style_property(
+        &self,
+        key: ElementKey,
+        element: &B::Element,
+        property: &str,
+        value: Option<&str>,
+    ) -> Result<(), RendererError> {
+        let style = self.read_attribute(key, element, "style");
+        let style = set_style_property(style.as_deref(), property, value);
+
+        self.write_attribute(key, element, "style", style.as_deref())
+    }
+
+    /// Will return the style attribute of an element, including changes which are still queued for named items
+    fn read_style(&self, key: Option<ElementKey>, element: &B::Element) -> Option<String> {
+        match key {
+            Some(key) => self.read_attribute(key, element, "style"),
+            None => self.backend.get_attribute(element, "style"),
+        }
+    }
+
+    /// Will return whether an element is hidden by its own style attribute,
+    /// the key is `None` for elements which are not named and thus never have queued changes
+    fn is_hidden(&self, key: Option<ElementKey>, element: &B::Element) -> bool {
+        let style = self.read_style(key, element);
+
+        HideMode::ALL.iter().any(|mode| {
+            let (property, _) = mode.property();
+
+            get_style_property(style.as_deref(), property)
+                .is_some_and(|value| mode.is_hidden_by(&value[..]))
+        })
+    }
+
+    /// Removes every property of the style attribute which hides a named item
+    fn show_element(&self, key: ElementKey, element: &B::Element) -> Result<(), RendererError> {
+        let old_style = self.read_attribute(key, element, "style");
+
+        let style = HideMode::ALL.iter().fold(old_style.clone(), |style, mode| {
+            let (property, _) = mode.property();
+
+            match get_style_property(style.as_deref(), property) {
+                Some(ref value) if mode.is_hidden_by(&value[..]) => {
+                    set_style_property(style.as_deref(), property, None)
+                }
+                _ => style,
+            }
+        });
+
+        if style == old_style {
+            return Ok(());
+        }
+
+        self.write_attribute(key, element, "style", style.as_deref())
+    }
+
+    /// Appends a child to a named container, this is queued while batching
+    fn insert_child(
+        &self,
+        key: ElementKey,
+        parent: &B::Element,
+        child: B::Element,
+    ) -> Result<(), RendererError> {
+        if let Some(batch) = self.batch.borrow_mut().as_mut() {
+            batch.append_child(key, parent, child);
+            return Ok(());
+        }
+
+        self.backend.append_child(parent, &child)
+    }
+
+    /// Applies all queued operations, this is done before operations which cannot be queued.
+    /// When an operation fails, all operations stay queued, so they are retried by the next flush.
+    fn apply_batch(&self) -> Result<(), RendererError> {
+        let mut batch = match self.batch.borrow_mut().as_mut() {
+            Some(batch) => batch.take(),
+            None => return Ok(()),
+        };
+
+        // On an error the batch only holds the operations which were not applied
+        let result = batch.apply(&self.backend);
+        if result.is_err() {
+            if let Some(queued) = self.batch.borrow_mut().as_mut() {
+                queued.restore(batch);
+            }
+        }
+
+        result
+    }
+
+    /// Will convert a id hash into a HTML id Attribute
+    fn get_id_of_named(&self, id_hash: &u64) -> String {
+        format!("{}-{}-{:x}", NAME_ID_PREFIX, self.namespace, id_hash)
+    }
+
+    fn get_id_of_figure(&self, id_hash: u64) -> String {
+        format!("{}-{}-{:x}", SHAPE_ID_PREFIX, self.namespace, id_hash)
+    }
+
+    /// Will convert a figure key into the href of a use element
+    fn get_href_of_figure(&self, id_hash: u64) -> String {
+        format!("#{}", self.get_id_of_figure(id_hash))
+    }
+
+    /// Will convert the href of a use element back into the figure key
+    fn get_figure_of_href(&self, href: &str) -> Option<u64> {
+        self.get_figure_of_id(href.strip_prefix('#')?)
+    }
+
+    /// Will convert the id of a definition back into the figure key
+    fn get_figure_of_id(&self, id: &str) -> Option<u64> {
+        Self::parse_id(id, SHAPE_ID_PREFIX, &self.namespace[..])
+    }
+
+    /// Will convert the id of a named element back into the id hash
+    fn get_named_of_id(&self, id: &str) -> Option<u64> {
+        Self::parse_id(id, NAME_ID_PREFIX, &self.namespace[..])
+    }
+
+    /// Parses the hash of an id in the form `<prefix>-<namespace>-<hash>`
+    fn parse_id(id: &str, prefix: &str, namespace: &str) -> Option<u64> {
+        let hex = id
+            .strip_prefix(prefix)?
+            .strip_prefix('-')?
+            .strip_prefix(namespace)?
+            .strip_prefix('-')?;
+
+        u64::from_str_radix(hex, 16).ok()
+    }
+
+    /// Will look up the id hash of a name or handle
+    fn get_id_hash(&self, name: &dyn NameRef) -> Result<u64, RendererError> {
+        match name.handle() {
+            Some(handle) => {
+                if handle.renderer != self.instance_id {
+                    return Err(ForeignHandle);
+                }
+
+                // A handle of a deleted item does not refer to a new item with the same name
+                if self
+                    .named_items
+                    .get(&handle.id_hash)
+                    .is_none_or(|item| item.generation != handle.generation)
+                {
+                    return Err(UnfindableName(String::from(handle.name())));
+                }
+
+                Ok(handle.id_hash)
+            }
+            None => self
+                .name_defs
+                .get(name.name())
+                .cloned()
+                .ok_or_else(|| UnfindableName(String::from(name.name()))),
+        }
+    }
+
+    /// Removes a name and the names of all items within it from the internal entries.
+    /// Will return the removed names, parents before their children.
+    fn remove_name(&mut self, id_hash: u64) -> Vec<String> {
+        let item = match self.named_items.remove(&id_hash) {
+            Some(item) => item,
+            None => return Vec::new(),
+        };
+
+        self.name_defs.remove(&item.name);
+        self.event_listeners.remove(&id_hash);
+        self.spatial_index.remove(id_hash);
+        if let Some(parent_item) = item
+            .parent
+            .and_then(|parent| self.named_items.get_mut(&parent))
+        {
+            parent_item.children.retain(|child| *child != id_hash);
+        }
+
+        let mut removed = vec![item.name];
+        for child in item.children {
+            removed.append(&mut self.remove_name(child));
+        }
+
+        removed
+    }
+
+    /// Removes the names of all items within a named container, or the root, from the internal entries.
+    /// Will return the removed names.
+    fn remove_names_within(&mut self, parent: ElementKey) -> Vec<String> {
+        let children: Vec<u64> = match parent {
+            Some(parent) => self
+                .named_items
+                .get(&parent)
+                .map(|item| item.children.clone())
+                .unwrap_or_default(),
+            None => self
+                .named_items
+                .iter()
+                .filter(|(_, item)| item.parent.is_none())
+                .map(|(id_hash, _)| *id_hash)
+                .collect(),
+        };
+
+        children
+            .into_iter()
+            .flat_map(|child| self.remove_name(child))
+            .collect()
+    }
+
+    /// Will return the handles of named items by their id hashes, sorted by name
+    fn get_handles_of(&self, id_hashes: Vec<u64>) -> Vec<NodeHandle> {
+        let mut handles: Vec<NodeHandle> = id_hashes
+            .into_iter()
+            .filter_map(|id_hash| self.get_handle_of(id_hash))
+            .collect();
+        handles.sort_by(|left, right| left.name().cmp(right.name()));
+
+        handles
+    }
+
+    /// Will return the handle of a named item by its id hash
+    fn get_handle_of(&self, id_hash: u64) -> Option<NodeHandle> {
+        let item = self.named_items.get(&id_hash)?;
+
+        Some(NodeHandle {
+            renderer: self.instance_id,
+            id_hash,
+            name: item.name.clone(),
+            generation: item.generation,
+        })
+    }
+
+    /// Will return the id hashes of the named items directly within a named container or the root,
+    /// in the order in which they are drawn
+    fn get_named_children(&self, key: ElementKey) -> Result<Vec<u64>, RendererError> {
+        // The order is read from the document, so queued items have to be added first
+        self.apply_batch()?;
+
+        let element = match key.and_then(|key| self.named_items.get(&key)) {
+            Some(item) => item.element.clone(),
+            None => self.get_svg_root()?,
+        };
+
+        let mut children = Vec::new();
+        self.collect_named_children(&element, &mut children);
+
+        Ok(children
+            .into_iter()
+            .filter(|id_hash| self.named_items[id_hash].parent == key)
+            .collect())
+    }
+
+    /// Adds the id hashes of the closest named items within an element, in document order
+    fn collect_named_children(&self, element: &B::Element, children: &mut Vec<u64>) {
+        for child in self.backend.children(element).iter() {
+            if self.backend.tag_name(child) == "defs" {
+                continue;
+            }
+
+            match self
+                .backend
+                .get_attribute(child, "id")
+                .and_then(|id| self.get_named_of_id(&id[..]))
+                .filter(|id_hash| self.named_items.contains_key(id_hash))
+            {
+                Some(id_hash) => children.push(id_hash),
+                None => self.collect_named_children(child, children),
+            }
+        }
+    }
+
+    /// Adds a named item and all items within it to the tree, depth first
+    fn collect_tree(
+        &self,
+        id_hash: u64,
+        depth: usize,
+        tree: &mut Vec<TreeItem>,
+    ) -> Result<(), RendererError> {
+        let item = &self.named_items[&id_hash];
+        let is_container = item.is_container;
+
+        tree.push(TreeItem {
+            handle: NodeHandle {
+                renderer: self.instance_id,
+                id_hash,
+                name: item.name.clone(),
+                generation: item.generation,
+            },
+            depth,
+            is_container,
+        });
+
+        if is_container {
+            for child in self.get_named_children(Some(id_hash))? {
+                self.collect_tree(child, depth + 1, tree)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Will return the id hash of a named item and of all items within it, parents before their children
+    fn get_named_subtree(&self, id_hash: u64) -> Vec<u64> {
+        let mut subtree = vec![id_hash];
+
+        if let Some(item) = self.named_items.get(&id_hash) {
+            for child in item.children.iter() {
+                subtree.append(&mut self.get_named_subtree(*child));
+            }
+        }
+
+        subtree
+    }
+
+    /// Copies an element and all its children. Named items within the copy get the name
+    /// of the original prefixed with `prefix`, these names must not be in use.
+    /// The copy is not yet added to the document and nothing is registered yet,
+    /// the named items and references of the copy are collected in `copied` instead.
+    fn copy_element(
+        &self,
+        element: &B::Element,
+        parent: ElementKey,
+        prefix: &str,
+        copied: &mut Copied<B::Element>,
+    ) -> Result<B::Element, RendererError> {
+        let copy = self
+            .backend
+            .create_element(&self.backend.tag_name(element)[..])?;
+
+        let original = self
+            .backend
+            .get_attribute(element, "id")
+            .and_then(|id| self.get_named_of_id(&id[..]))
+            .filter(|id_hash| self.named_items.contains_key(id_hash));
+
+        for (name, value) in self.backend.attributes(element) {
+            // Ids have to be unique, the copy of a named item gets its own id below
+            if name == "id" || name == NAME_ATTRIBUTE {
+                continue;
+            }
+
+            self.backend.set_attribute(&copy, &name[..], &value[..])?;
+        }
+        if let Some(text) = self.backend.text(element) {
+            self.backend.set_text(&copy, &text[..])?;
+        }
+
+        if let Some(figure_key) = self
+            .backend
+            .get_attribute(element, "href")
+            .and_then(|href| self.get_figure_of_href(&href[..]))
+        {
+            copied.references.push(figure_key);
+        }
+
+        let mut children_parent = parent;
+        if let Some(original) = original {
+            let item = &self.named_items[&original];
+            let name = format!("{}{}", prefix, item.name);
+            let item_copy = NamedItem {
+                name: name.clone(),
+                element: copy.clone(),
+                is_container: item.is_container,
+                figure: item.figure,
+                transform: item.transform.clone(),
+                style: item.style.clone(),
+                z_index: item.z_index,
+                parent: None,
+                children: Vec::new(),
+                generation: 0,
+                world_transform: Transform::identity(),
+                world_presentation: Presentation::default(),
+            };
+
+            let handle = self.create_handle(&name[..])?;
+            self.set_named_id(&copy, &handle)?;
+            copied.names.push((handle.clone(), parent, item_copy));
+            children_parent = Some(handle.id_hash);
+        }
+
+        for child in self.backend.children(element).iter() {
+            let child_copy = self.copy_element(child, children_parent, prefix, copied)?;
+            self.backend.append_child(&copy, &child_copy)?;
+        }
+
+        Ok(copy)
+    }
+
+    /// Will retrieve the named item
+    fn get_named(&self, name: &dyn NameRef) -> Result<&NamedItem<B::Element>, RendererError> {
+        let id_hash = self.get_id_hash(name)?;
+
+        self.named_items
+            .get(&id_hash)
+            .ok_or_else(|| UnfindableName(String::from(name.name())))
+    }
+
+    /// Will return the transform from the coordinates of the viewbox to client pixels,
+    /// headless the width and height attributes of the svg are used as its size on the screen
+    fn get_viewport_transform(&self) -> Result<Transform, RendererError> {
+        let svg_root = self.get_svg_root()?;
+        let view_box = self.read_attribute(None, &svg_root, "viewBox");
+        let preserve_aspect_ratio = self.read_attribute(None, &svg_root, "preserveAspectRatio");
+
+        let viewport = match self.backend.client_rect(&svg_root) {
+            Some(rect) => rect,
+            None => {
+                let size = |name| {
+                    self.read_attribute(None, &svg_root, name)
+                        .and_then(|value| value.trim().trim_end_matches("px").parse::<f32>().ok())
+                };
+
+                match (size("width"), size("height")) {
+                    (Some(width), Some(height)) => (0.0, 0.0, width, height),
+                    // Without a size the viewbox is shown as is
+                    _ => return Ok(Transform::identity()),
+                }
+            }
+        };
+
+        Ok(Transform::viewport(
+            view_box.as_deref(),
+            preserve_aspect_ratio.as_deref(),
+            viewport,
+        ))
+    }
+
+    /// Adds the named items hit by a point to the hits, in the order in which they are drawn.
+    /// A hit on an element which is not named counts for the named container it is in.
+    fn collect_hits(
+        &self,
+        element: &B::Element,
+        transform: &Transform,
+        presentation: &Presentation,
+        owner: Option<u64>,
+        point: Point2D,
+        hits_within: &mut Vec<u64>,
+    ) {
+        for child in self.backend.children(element) {
+            let tag_name = self.backend.tag_name(&child);
+
+            // Display and visibility are inherited through the presentation, so a descendant of a container
+            // with a hidden visibility can still be hit when it is visible itself
+            let style = self.backend.get_attribute(&child, "style");
+            let is_transparent = get_style_property(style.as_deref(), "opacity")
+                .is_some_and(|opacity| HideMode::Opacity.is_hidden_by(&opacity[..]));
+
+            if tag_name == "defs" || is_transparent {
+                continue;
+            }
+
+            let owner = self
+                .backend
+                .get_attribute(&child, "id")
+                .and_then(|id| self.get_named_of_id(&id[..]))
+                .filter(|id_hash| self.named_items.contains_key(id_hash))
+                .or(owner);
+            let node = DocumentNode {
+                backend: &self.backend,
+                element: child.clone(),
+            };
+
+            let is_hit = match &tag_name[..] {
+                "use" => {
+                    let presentation = match presentation.of(&node) {
+                        Some(presentation) => presentation,
+                        None => continue,
+                    };
+                    let number = |name| {
+                        node.attribute(name)
+                            .and_then(|value| value.parse::<f32>().ok())
+                            .unwrap_or(0.0)
+                    };
+
+                    // The location of a use is applied before its transform
+                    let mut placement = Transform::translate(number("x"), number("y"));
+                    if let Some(local) = node
+                        .attribute("transform")
+                        .and_then(|value| Transform::parse(&value[..]))
+                    {
+                        placement = placement.then(local);
+                    }
+                    let placement = placement.then(transform.clone());
+
+                    let figure = node
+                        .attribute("href")
+                        .and_then(|href| {
+                            self.backend
+                                .get_element_by_id(href.strip_prefix('#').unwrap_or(&href[..]))
+                        })
+                        .map(|element| DocumentNode {
+                            backend: &self.backend,
+                            element,
+                        });
+
+                    figure.is_some_and(|figure| hits(&figure, &placement, &presentation, point))
+                }
+                "g" | "svg" | "a" | "switch" => {
+                    let presentation = match presentation.of(&node) {
+                        Some(presentation) => presentation,
+                        None => continue,
+                    };
+                    let transform = match node
+                        .attribute("transform")
+                        .and_then(|value| Transform::parse(&value[..]))
+                    {
+                        Some(local) => local.then(transform.clone()),
+                        None => transform.clone(),
+                    };
+
+                    self.collect_hits(&child, &transform, &presentation, owner, point, hits_within);
+                    false
+                }
+                _ => hits(&node, transform, presentation, point),
+            };
+
+            if let (true, Some(owner)) = (is_hit, owner) {
+                hits_within.push(owner);
+            }
+        }
+    }
+
+    /// Will return the transform from the coordinates within a named item to the coordinates of the viewbox,
+    /// which includes the transforms of all containers the item is in
+    fn get_local_transform(&self, name: &dyn NameRef) -> Result<Transform, RendererError> {
+        if name.name() == ROOT_NAME {
+            return Ok(Transform::identity());
+        }
+
+        self.get_named(name)?;
+        Ok(self.get_transform_to_root(self.get_id_hash(name)?))
+    }
+
+    /// Will return the transform from the coordinates within a named item to the coordinates of the viewbox
+    /// by its id hash
+    fn get_transform_to_root(&self, id_hash: u64) -> Transform {
+        let mut transform = Transform::identity();
+        let mut current = Some(id_hash);
+
+        while let Some(item) = current.and_then(|key| self.named_items.get(&key)) {
+            transform = transform.then(item.transform.clone());
+            current = item.parent;
+        }
+
+        transform
+    }
+
+    /// Updates the bounding boxes of a named item and all items within it in the spatial index,
+    /// named containers are not in the index. Figures which are not named are not in the index either.
+    fn update_spatial_index(&mut self, id_hash: u64) {
+        let parent = self
+            .named_items
+            .get(&id_hash)
+            .and_then(|item| item.parent)
+            .and_then(|parent| self.named_items.get(&parent));
+
+        let (transform, presentation) = match parent {
+            Some(parent) => (
+                parent.world_transform.clone(),
+                parent.world_presentation.clone(),
+            ),
+            None => (Transform::identity(), Presentation::default()),
+        };
+
+        self.update_spatial_subtree(id_hash, &transform, &presentation);
+    }
+
+    /// Updates the world transform and presentation of a named item from those of its parent,
+    /// together with its bounding box and those of all items within it
+    fn update_spatial_subtree(
+        &mut self,
+        id_hash: u64,
+        parent_transform: &Transform,
+        parent_presentation: &Presentation,
+    ) {
+        let item = match self.named_items.get_mut(&id_hash) {
+            Some(item) => item,
+            None => return,
+        };
+
+        item.world_transform = item.transform.clone().then(parent_transform.clone());
+        item.world_presentation = parent_presentation.with_style(&item.style);
+
+        let transform = item.world_transform.clone();
+        let presentation = item.world_presentation.clone();
+        let children = item.children.clone();
+
+        let bounds = item
+            .figure
+            .and_then(|figure_key| self.figure_defs.get(&figure_key))
+            .and_then(|definition| bounds_of(&definition.figure, &transform, &presentation));
+
+        match bounds {
+            Some(bounds) => self.spatial_index.insert(id_hash, bounds),
+            None => self.spatial_index.remove(id_hash),
+        }
+
+        for child in children {
+            self.update_spatial_subtree(child, &transform, &presentation);
+        }
+    }
+
+    /// Will retrieve the element of a named item
+    fn get_named_element(&self, name: &dyn NameRef) -> Result<B::Element, RendererError> {
+        Ok(self.get_named(name)?.element.clone())
+    }
+
+    /// Will retrieve the element of a named container
+    fn get_named_container(&self, name: &dyn NameRef) -> Result<B::Element, RendererError> {
+        if name.name() == ROOT_NAME {
+            return self.get_svg_root();
+        }
+
+        let item = self.get_named(name)?;
+
+        if !item.is_container {
+            return Err(NamedNotContainer(String::from(name.name())));
+        }
+
+        Ok(item.element.clone())
+    }
+
+    /// Will retrieve the element of a named item
+    fn get_named_item(&self, name: &dyn NameRef) -> Result<B::Element, RendererError> {
+        if name.name() == ROOT_NAME {
+            return self.get_svg_root();
+        }
+
+        self.get_named_element(name)
+    }
+
+    /// Will retrieve the element of a named use
+    fn get_named_use(&self, name: &dyn NameRef) -> Result<B::Element, RendererError> {
+        if name.name() == ROOT_NAME {
+            return Err(NamedNotUse(String::from(ROOT_NAME)));
+        }
+
+        let item = self.get_named(name)?;
+
+        if item.is_container {
+            return Err(NamedNotUse(String::from(name.name())));
+        }
+
+        Ok(item.element.clone())
+    }
+
+    /// Will add a use element to the root svg
+    fn add_use(
+        &mut self,
+        figure_key: u64,
+        transform: Transform,
+        style: Style,
+    ) -> Result<(), RendererError> {
+        let root = self.get_svg_root()?;
+        let use_element = self.create_use(figure_key, &transform, &style)?;
+
+        self.insert_child(None, &root, use_element)?;
+        self.add_reference(figure_key);
+
+        Ok(())
+    }
+
+    /// Will add a use element with a name to a named container or the root svg
+    fn add_named_use(
+        &mut self,
+        parent: &dyn NameRef,
+        name: &str,
+        figure_key: u64,
+        transform: Transform,
+        style: Style,
+    ) -> Result<NodeHandle, RendererError> {
+        let parent_key = self.get_item_key(parent)?;
+        let container = self.get_named_container(parent)?;
+
+        let handle = self.create_handle(name)?;
+
+        let use_element = self.create_use(figure_key, &transform, &style)?;
+        self.set_named_id(&use_element, &handle)?;
+
+        self.insert_child(parent_key, &container, use_element.clone())?;
+        self.add_reference(figure_key);
+        self.add_name(
+            &handle,
+            parent_key,
+            NamedItem::new_use(name, use_element, figure_key, transform, style),
+        );
+
+        Ok(handle)
+    }
+
+    /// Will add a use element to a named container
+    fn add_use_to(
+        &mut self,
+        name: &dyn NameRef,
+        figure_key: u64,
+        transform: Transform,
+    ) -> Result<(), RendererError> {
+        let container = self.get_named_container(name)?;
+        let use_element = self.create_use(figure_key, &transform, &Style::default())?;
+
+        self.insert_child(self.get_item_key(name)?, &container, use_element)?;
+        self.add_reference(figure_key);
+
+        Ok(())
+    }
+
+    /// Sets the transform of a named item,
+    /// a named use which is only translated gets a location instead of a transform attribute
+    fn set_named_transform(
+        &mut self,
+        name: &dyn NameRef,
+        transform: Transform,
+    ) -> Result<(), RendererError> {
+        let id_hash = self.get_id_hash(name)?;
+        let item = self.get_named(name)?;
+        let element = item.element.clone();
+        let key = Some(id_hash);
+
+        match (transform.as_translation(), item.is_container) {
+            (_, true) => {
+                let value = transform.to_string();
+                let value = if transform.is_identity() {
+                    None
+                } else {
+                    Some(&value[..])
+                };
+
+                self.write_attribute(key, &element, "transform", value)?;
+            }
+            (Some(location), false) => {
+                if item.transform.as_translation().is_none() {
+                    self.write_attribute(key, &element, "transform", None)?;
+                }
+
+                let (x, y) = format_location(location);
+                self.write_attribute(key, &element, "x", Some(&x[..]))?;
+                self.write_attribute(key, &element, "y", Some(&y[..]))?;
+            }
+            (None, false) => {
+                self.write_attribute(key, &element, "x", None)?;
+                self.write_attribute(key, &element, "y", None)?;
+                self.write_attribute(key, &element, "transform", Some(&transform.to_string()[..]))?;
+            }
+        }
+
+        if let Some(item) = self.named_items.get_mut(&id_hash) {
+            item.transform = transform;
+        }
+        self.update_spatial_index(id_hash);
+
+        Ok(())
+    }
+
+    /// Sets the presentation overrides of a named item, overrides which are not in `style` are removed
+    fn set_named_style(&mut self, name: &dyn NameRef, style: Style) -> Result<(), RendererError> {
+        let key = self.get_item_key(name)?;
+        let item = self.get_named(name)?;
+        let element = item.element.clone();
+
+        let old_attributes = item.style.attributes();
+        for ((attribute, value), (_, old_value)) in
+            style.attributes().iter().zip(old_attributes.iter())
+        {
+            if value == old_value {
+                continue;
+            }
+
+            self.write_attribute(key, &element, attribute, value.as_deref())?;
+
+            if VARIABLE_ATTRIBUTES.contains(attribute) {
+                self.write_style_property(
+                    key,
+                    &element,
+                    &format!("--{}", attribute)[..],
+                    value.as_deref(),
+                )?;
+            }
+        }
+
+        if let Some(id_hash) = key {
+            if let Some(item) = self.named_items.get_mut(&id_hash) {
+                item.style = style;
+            }
+            self.update_spatial_index(id_hash);
+        }
+
+        Ok(())
+    }
+
+    /// Adjust a named use to another figure
+    fn adjust_use_to(
+        &mut self,
+        name: &dyn NameRef,
+        figure_key: u64,
+        transform: Transform,
+    ) -> Result<(), RendererError> {
+        let use_element = self.get_named_use(name)?;
+        let id_hash = self.get_id_hash(name)?;
+
+        self.set_named_transform(name, transform)?;
+        self.write_attribute(
+            Some(id_hash),
+            &use_element,
+            "href",
+            Some(&self.get_href_of_figure(figure_key)[..]),
+        )?;
+
+        // Only count the reference once the use actually points to the figure
+        self.add_reference(figure_key);
+        let old_figure = self
+            .named_items
+            .get_mut(&id_hash)
+            .and_then(|item| item.figure.replace(figure_key));
+        self.update_spatial_index(id_hash);
+
+        match old_figure {
+            Some(old_figure) => self.remove_reference(old_figure),
+            None => Ok(()),
+        }
+    }
+
+    /// Will return the position of a named item within its parent, the defs are never a sibling
+    fn get_siblings(&self, name: &dyn NameRef) -> Result<Siblings<B::Element>, RendererError> {
+        // Items can only be reordered once they are in the document
+        self.apply_batch()?;
+
+        let element = self.get_named_element(name)?;
+        let id = self.backend.get_attribute(&element, "id");
+        let parent = self.backend.parent_element(&element).ok_or(Dom(NoParent))?;
+
+        let mut others: Vec<B::Element> = self
+            .backend
+            .children(&parent)
+            .into_iter()
+            .filter(|sibling| self.backend.tag_name(sibling) != "defs")
+            .collect();
+        let index = others
+            .iter()
+            .position(|sibling| self.backend.get_attribute(sibling, "id") == id)
+            .ok_or(Dom(NoParent))?;
+        others.remove(index);
+
+        Ok(Siblings {
+            parent,
+            element,
+            others,
+            index,
+        })
+    }
+
+    /// Moves a named item to an index within its siblings, an index past the last sibling moves it to the front
+    fn place_at_index(&self, name: &dyn NameRef, index: usize) -> Result<(), RendererError> {
+        let siblings = self.get_siblings(name)?;
+        let last_index = siblings.others.len();
+
+        if index == siblings.index || (index >= last_index && siblings.index == last_index) {
+            return Ok(());
+        }
+
+        self.check_layer_order(name, &siblings, index)?;
+
+        match siblings.others.get(index) {
+            Some(reference) => {
+                self.backend
+                    .insert_before(&siblings.parent, &siblings.element, reference)
+            }
+            None => self
+                .backend
+                .append_child(&siblings.parent, &siblings.element),
+        }
+    }
+
+    /// Will check whether moving a named item to an index within its siblings keeps the layers ordered by their z-index,
+    /// a layer can only be moved past items which are not layers and past layers with the same z-index
+    fn check_layer_order(
+        &self,
+        name: &dyn NameRef,
+        siblings: &Siblings<B::Element>,
+        index: usize,
+    ) -> Result<(), RendererError> {
+        let z_index = match self.get_named(name)?.z_index {
+            Some(z_index) => z_index,
+            None => return Ok(()),
+        };
+
+        let (below, above) = siblings.others.split_at(index.min(siblings.others.len()));
+        let is_ordered = below
+            .iter()
+            .filter_map(|other| self.get_z_index_of(other))
+            .all(|other_z_index| other_z_index <= z_index)
+            && above
+                .iter()
+                .filter_map(|other| self.get_z_index_of(other))
+                .all(|other_z_index| other_z_index >= z_index);
+
+        if !is_ordered {
+            return Err(LayerOutOfOrder(String::from(name.name())));
+        }
+
+        Ok(())
+    }
+
+    /// Will return the z-index of an element, `None` when it is not a layer
+    fn get_z_index_of(&self, element: &B::Element) -> Option<i32> {
+        self.backend
+            .get_attribute(element, "id")
+            .and_then(|id| self.get_named_of_id(&id[..]))
+            .and_then(|id_hash| self.named_items.get(&id_hash))
+            .and_then(|item| item.z_index)
+    }
+
+    /// Will check whether a name is used for a layer
+    fn check_layer(&self, name: &dyn NameRef) -> Result<(), RendererError> {
+        match self.get_named(name)?.z_index {
+            Some(_) => Ok(()),
+            None => Err(NamedNotLayer(String::from(name.name()))),
+        }
+    }
+
+    /// Places a layer in the root svg, directly above the last other layer with a lower or equal z-index.
+    /// When there is no such layer it is placed below all other layers.
+    fn place_layer(&self, layer: &B::Element, z_index: i32) -> Result<(), RendererError> {
+        // Layers can only be placed once they are in the document
+        self.apply_batch()?;
+
+        let root = self.get_svg_root()?;
+        let layer_id = self.backend.get_attribute(layer, "id");
+
+        let children: Vec<(B::Element, Option<i32>)> = self
+            .backend
+            .children(&root)
+            .into_iter()
+            .filter_map(|child| {
+                let id = self.backend.get_attribute(&child, "id");
+                if id.is_some() && id == layer_id {
+                    return None;
+                }
+
+                let other_z_index = self.get_z_index_of(&child);
+                Some((child, other_z_index))
+            })
+            .collect();
+
+        let below = children.iter().rposition(|(_, other_z_index)| {
+            other_z_index.is_some_and(|other_z_index| other_z_index <= z_index)
+        });
+        let above = match below {
+            Some(below) => children.get(below + 1),
+            None => children
+                .iter()
+                .find(|(_, other_z_index)| other_z_index.is_some()),
+        };
+
+        match above {
+            Some((above, _)) => self.backend.insert_before(&root, layer, above),
+            None => self.backend.append_child(&root, layer),
+        }
+    }
+
+    /// Removes an element from its parent
+    fn remove_element(&self, element: &B::Element) -> Result<(), RendererError> {
+        let parent = self.backend.parent_element(element).ok_or(Dom(NoParent))?;
+
+        self.backend.remove_child(&parent, element)
+    }
+
+    /// Removes a named item from its parent, together with its name, the names within it
+    /// and the references of all uses within it. Will return the removed names.
+    fn delete_named_item(&mut self, id_hash: u64) -> Result<Vec<String>, RendererError> {
+        self.apply_batch()?;
+
+        let element = match self.named_items.get(&id_hash) {
+            Some(item) => item.element.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let references = self.get_references_within(&element);
+
+        self.remove_element(&element)?;
+        let removed = self.remove_name(id_hash);
+
+        self.remove_references(references)?;
+
+        Ok(removed)
+    }
+
+    /// Removes all children of a named container, together with the names and references of all uses within them.
+    /// Will return the removed names.
+    fn delete_children(
+        &mut self,
+        key: ElementKey,
+        element: &B::Element,
+    ) -> Result<Vec<String>, RendererError> {
+        self.apply_batch()?;
+
+        let children = self.backend.children(element);
+        let references = children
+            .iter()
+            .flat_map(|child| self.get_references_within(child))
+            .collect();
+
+        self.backend.remove_children(element)?;
+        let removed = self.remove_names_within(key);
+
+        self.remove_references(references)?;
+
+        Ok(removed)
+    }
+
+    /// Create new renderer object with a certain backend
+    ///
+    /// # Arguments
+    /// * `backend` - The [Backend](../backend/trait.Backend.html) used to interact with the document
+    /// * `dom_root_id` - The HTMl Attribute ID of the parent element of to be created SVG
+    pub fn with_backend(backend: B, dom_root_id: &str) -> Result<Renderer<B>, RendererError> {
+        let svg_element = SVGElem::new(Tag::Svg)
+            .set(
+                Attr::ViewBox,
+                format!(
+                    "{} {} {} {}",
+                    DEFAULT_VIEWBOX[0], DEFAULT_VIEWBOX[1], DEFAULT_VIEWBOX[2], DEFAULT_VIEWBOX[3],
+                ),
+            )
+            .append(SVGElem::new(Tag::Defs));
+
+        Self::attach(backend, dom_root_id, svg_element)
+    }
+
+    /// Creates renderer object from svg element with a certain backend
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the root of the `svg_elem` is not a TagName::Svg
+    ///
+    /// # Arguments
+    /// * `backend` - The [Backend](../backend/trait.Backend.html) used to interact with the document
+    /// * `dom_root_id` - The HTMl Attribute ID of the parent element of to be created SVG
+    /// * `svg_elem` - The svg element to from the renderer from
+    pub fn with_backend_from_svg(
+        backend: B,
+        dom_root_id: &str,
+        svg_elem: SVGElem,
+    ) -> Result<Renderer<B>, RendererError> {
+        if *svg_elem.get_tag_name() != Tag::Svg {
+            return Err(Dom(UnfindableTag(String::from("svg"))));
+        }
+
+        let mut svg_element = SVGElem::new(Tag::Svg).append(SVGElem::new(Tag::Defs));
+        for (attr, value) in svg_elem.get_attributes().iter() {
+            svg_element = svg_element.set(attr.clone(), value);
+        }
+
+        for child in svg_elem.get_children().iter() {
+            svg_element = svg_element.append(child.clone());
+        }
+
+        Self::attach(backend, dom_root_id, svg_element)
+    }
+
+    /// Appends the svg element to the parent and creates the renderer object
+    fn attach(
+        backend: B,
+        dom_root_id: &str,
+        svg_element: SVGElem,
+    ) -> Result<Renderer<B>, RendererError> {
+        let root = Self::find_root(&backend, dom_root_id)?;
+
+        let svg_root = to_element(&backend, &svg_element)?;
+        backend.append_child(&root, &svg_root)?;
+
+        let defs_root = Self::find_defs_root(&backend, &svg_root)?;
+
+        Ok(Self::from_parts(backend, dom_root_id, svg_root, defs_root))
+    }
+
+    /// Creates renderer object from a svg which is already in the document with a certain backend.
+    /// The definitions and names are rebuilt from the existing svg, see [resync](#method.resync).
+    ///
+    /// # Note
+    /// The [namespace](#method.namespace) is taken from the ids within the svg,
+    /// so the svg can be adopted under another parent than the one it was created in.
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the parent does not contain a svg
+    /// with a defs element as its first child,
+    /// or `RendererError::AmbiguousNamespace` when the svg contains ids of multiple namespaces
+    ///
+    /// # Arguments
+    /// * `backend` - The [Backend](../backend/trait.Backend.html) used to interact with the document
+    /// * `dom_root_id` - The HTMl Attribute ID of the parent element of the existing SVG
+    pub fn with_backend_adopt(backend: B, dom_root_id: &str) -> Result<Renderer<B>, RendererError> {
+        let svg_root = Self::find_svg_root(&backend, dom_root_id)?;
+        let defs_root = Self::find_defs_root(&backend, &svg_root)?;
+
+        let namespaces = Self::find_namespaces(&backend, &svg_root, &defs_root);
+        let namespace = match &namespaces[..] {
+            [] => String::from(dom_root_id),
+            [namespace] => namespace.clone(),
+            [first, second, ..] => {
+                return Err(AmbiguousNamespace(first.clone(), second.clone()));
+            }
+        };
+
+        Self::adopt_parts(backend, dom_root_id, svg_root, defs_root, namespace)
+    }
+
+    /// Creates renderer object from a svg which is already in the document with a certain backend,
+    /// only adopting the definitions and names within a certain namespace.
+    /// See [with_backend_adopt](#method.with_backend_adopt).
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the parent does not contain a svg
+    /// with a defs element as its first child,
+    /// or `RendererError::UnusableNamespace` when the namespace cannot be used
+    /// or the svg contains ids of other namespaces only
+    ///
+    /// # Arguments
+    /// * `backend` - The [Backend](../backend/trait.Backend.html) used to interact with the document
+    /// * `dom_root_id` - The HTMl Attribute ID of the parent element of the existing SVG
+    /// * `namespace` - The namespace of the definitions and names to adopt
+    pub fn with_backend_adopt_with_namespace(
+        backend: B,
+        dom_root_id: &str,
+        namespace: &str,
+    ) -> Result<Renderer<B>, RendererError> {
+        let svg_root = Self::find_svg_root(&backend, dom_root_id)?;
+        let defs_root = Self::find_defs_root(&backend, &svg_root)?;
+
+        let namespaces = Self::find_namespaces(&backend, &svg_root, &defs_root);
+        if !is_usable_namespace(namespace)
+            || (!namespaces.is_empty() && !namespaces.iter().any(|other| other == namespace))
+        {
+            return Err(UnusableNamespace(String::from(namespace)));
+        }
+
+        Self::adopt_parts(
+            backend,
+            dom_root_id,
+            svg_root,
+            defs_root,
+            String::from(namespace),
+        )
+    }
+
+    /// Creates the renderer object within a namespace and rebuilds its definitions and names
+    fn adopt_parts(
+        backend: B,
+        dom_root_id: &str,
+        svg_root: B::Element,
+        defs_root: B::Element,
+        namespace: String,
+    ) -> Result<Renderer<B>, RendererError> {
+        let mut renderer = Self::from_parts(backend, dom_root_id, svg_root, defs_root);
+        renderer.namespace = namespace;
+
+        renderer.try_resync()?;
+
+        Ok(renderer)
+    }
+
+    /// Will return the namespaces of the definitions and named items within an existing svg,
+    /// in the order they are found
+    fn find_namespaces(backend: &B, svg_root: &B::Element, defs_root: &B::Element) -> Vec<String> {
+        let mut namespaces: Vec<String> = Vec::new();
+        let mut add = |id: Option<String>, prefix: &str| {
+            if let Some(namespace) = id.and_then(|id| parse_namespace(&id[..], prefix)) {
+                if !namespaces.contains(&namespace) {
+                    namespaces.push(namespace);
+                }
+            }
+        };
+
+        for def in backend.children(defs_root).iter() {
+            add(backend.get_attribute(def, "id"), SHAPE_ID_PREFIX);
+        }
+
+        // The first child is the defs element
+        let mut elements: Vec<B::Element> =
+            backend.children(svg_root).into_iter().skip(1).collect();
+        elements.reverse();
+        while let Some(element) = elements.pop() {
+            if backend.get_attribute(&element, NAME_ATTRIBUTE).is_some() {
+                add(backend.get_attribute(&element, "id"), NAME_ID_PREFIX);
+            }
+
+            elements.extend(backend.children(&element).into_iter().rev());
+        }
+
+        namespaces
+    }
+
+    /// Creates the renderer object without any definitions and names
+    fn from_parts(
+        backend: B,
+        dom_root_id: &str,
+        svg_root: B::Element,
+        defs_root: B::Element,
+    ) -> Renderer<B> {
+        Renderer {
+            backend,
+            instance_id: NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed),
+            dom_root_id: String::from(dom_root_id),
+            svg_root,
+            defs_root,
+            namespace: String::from(dom_root_id),
+            figure_defs: BTreeMap::new(),
+            figure_keys: HashMap::new(),
+            keyed_defs: HashMap::new(),
+            auto_collect_garbage: false,
+            name_defs: HashMap::new(),
+            named_items: HashMap::new(),
+            next_generation: Cell::new(0),
+            event_listeners: HashMap::new(),
+            spatial_index: SpatialIndex::new(),
+            batch: RefCell::new(None),
+        }
+    }
+
+    /// Adds the names and references of an element and all its children,
+    /// `parent` is the key of the closest named container around the element
+    fn resync_element(&mut self, parent: ElementKey, element: &B::Element) {
+        if self.backend.tag_name(element) == "use" {
+            if let Some(figure_key) = self
+                .backend
+                .get_attribute(element, "href")
+                .and_then(|href| self.get_figure_of_href(&href[..]))
+            {
+                self.add_reference(figure_key);
+            }
+        }
+
+        let id_hash = self
+            .backend
+            .get_attribute(element, "id")
+            .and_then(|id| self.get_named_of_id(&id[..]));
+        let name = self.backend.get_attribute(element, NAME_ATTRIBUTE);
+
+        let mut children_parent = parent;
+        if let (Some(id_hash), Some(name)) = (id_hash, name) {
+            let is_container = self.backend.tag_name(element) == "g";
+            let figure = self
+                .backend
+                .get_attribute(element, "href")
+                .and_then(|href| self.get_figure_of_href(&href[..]));
+            let transform = self.read_transform(element, is_container);
+            let style = Style::from_attributes(|name| self.backend.get_attribute(element, name));
+            let z_index = self
+                .backend
+                .get_attribute(element, Z_INDEX_ATTRIBUTE)
+                .and_then(|z_index| z_index.parse().ok());
+
+            let handle = NodeHandle {
+                renderer: self.instance_id,
+                id_hash,
+                name: name.clone(),
+                generation: self.next_generation(),
+            };
+            self.add_name(
+                &handle,
+                parent,
+                NamedItem {
+                    name,
+                    element: element.clone(),
+                    is_container,
+                    figure,
+                    transform,
+                    style,
+                    z_index,
+                    parent: None,
+                    children: Vec::new(),
+                    generation: 0,
+                    world_transform: Transform::identity(),
+                    world_presentation: Presentation::default(),
+                },
+            );
+            children_parent = Some(id_hash);
+        }
+
+        for child in self.backend.children(element).iter() {
+            self.resync_element(children_parent, child);
+        }
+    }
+
+    /// Reads the transform of an existing element, from its transform attribute and its location
+    fn read_transform(&self, element: &B::Element, is_container: bool) -> Transform {
+        let transform = self
+            .backend
+            .get_attribute(element, "transform")
+            .and_then(|value| Transform::parse(&value[..]))
+            .unwrap_or_default();
+
+        if is_container {
+            return transform;
+        }
+
+        let read = |name| {
+            self.backend
+                .get_attribute(element, name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0.0)
+        };
+
+        // The location of a use is applied before its transform attribute
+        Transform::translate(read("x"), read("y")).then(transform)
+    }
+
+    /// Will return the backend used by the renderer
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Will return the namespace of the renderer
+    ///
+    /// The namespace is part of the id of every definition and named item,
+    /// so multiple renderers within the same document do not interfere.
+    /// By default this is the id of the parent element of the svg.
+    pub fn namespace(&self) -> &str {
+        &self.namespace[..]
+    }
+
+    /// Sets the namespace used in the id of every definition and named item
+    ///
+    /// # Arguments
+    /// * `namespace` - The new namespace, which cannot be empty or contain whitespace or a `#`
+    ///
+    /// # Note
+    /// The namespace can only be changed as long as the renderer has no definitions and names,
+    /// so preferably directly after creating the renderer.
+    ///
+    /// # Panics
+    /// Will panic when [try_set_namespace](#method.try_set_namespace) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Both renderers are part of the same document
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    /// let mut other_renderer = SVGRenderer::new("other_svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.set_namespace("diagram");
+    /// other_renderer.set_namespace("legend");
+    ///
+    /// // The same name can be used by both renderers
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    /// other_renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    /// ```
+    pub fn set_namespace(&mut self, namespace: &str) {
+        self.try_set_namespace(namespace)
+            .expect("Failed to set namespace!");
+    }
+
+    /// Fallible version of [set_namespace](#method.set_namespace)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnusableNamespace` when the namespace is empty,
+    /// contains whitespace or a `#`, or when the renderer already has definitions or names
+    pub fn try_set_namespace(&mut self, namespace: &str) -> Result<(), RendererError> {
+        if !is_usable_namespace(namespace)
+            || !self.figure_defs.is_empty()
+            || !self.name_defs.is_empty()
+        {
+            return Err(UnusableNamespace(String::from(namespace)));
+        }
+
+        self.namespace = String::from(namespace);
+
+        Ok(())
+    }
+
+    /// Render figure at a location (this will automatically add a definition when needed)
+    ///
+    /// # Arguments
+    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render](#method.try_render) returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render(circle, (20.0, 20.0));
+    /// ```
+    pub fn render<L: Into<Transform>>(&mut self, figure: SVGElem, location: L) {
+        self.try_render(figure, location)
+            .expect("Failed to render figure!");
+    }
+
+    /// Fallible version of [render](#method.render)
+    ///
+    /// # Errors
+    /// Will return an error when the definition or the use cannot be added to the document
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    /// # use wasm_svg_graphics::errors::RendererError;
+    ///
+    /// # fn main() -> Result<(), RendererError> {
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")?;
+    ///
+    /// // Render circle
+    /// renderer.try_render(SVGDefault::circle(10), (20.0, 20.0))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_render<L: Into<Transform>>(
+        &mut self,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<(), RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_render_id(figure_id, location)
+        })
+    }
+
+    /// Render a named figure at a location (this will automatically add a definition when needed)
+    /// One can use named-figures for later adjustments, e.g. updating the location of the figure, hiding/showing the figure
+    ///
+    /// # Arguments
+    /// * `name` - Name to use for later reference
+    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Cannot create duplicate names or declare a name more than once.
+    /// When an attempt is made to declare a name more than once, a panic will occur.
+    /// One is able to check if a name already exists with the [does_name_exist](#method.does_name_exist) method.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Updates the named figure's location to (20,20)
+    /// renderer.move_named("named_circle", (20.0, 20.0));
+    /// ```
+    pub fn render_named<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+    ) -> NodeHandle {
+        self.try_render_named(name, figure, location)
+            .expect("Failed to render named figure!")
+    }
+
+    /// Fallible version of [render_named](#method.render_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the definition or the use cannot be added to the document
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    /// # use wasm_svg_graphics::errors::RendererError;
+    ///
+    /// # fn main() -> Result<(), RendererError> {
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")?;
+    ///
+    /// renderer.try_render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0))?;
+    ///
+    /// // Declaring the same name twice will return an error instead of panicking
+    /// assert!(renderer
+    ///     .try_render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0))
+    ///     .is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_render_named<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<NodeHandle, RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_render_named_id(name, figure_id, location)
+        })
+    }
+
+    /// Render figure from a previously added definition at a location (this will automatically add a definition when needed)
+    ///
+    /// # Arguments
+    /// * `figure_id` - id of the figure definition, returned by [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_id](#method.try_render_id) returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// let circle_id = renderer.define_render(circle);
+    ///
+    /// // Render circle
+    /// renderer.render_id(circle_id, (20.0, 20.0));
+    /// ```
+    pub fn render_id<L: Into<Transform>>(&mut self, figure_id: FigureId, location: L) {
+        self.try_render_id(figure_id, location)
+            .expect("Failed to render figure from id!");
+    }
+
+    /// Fallible version of [render_id](#method.render_id)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableDefinition` when no definition exists for `figure_id`
+    /// and an error when the use cannot be added to the document
+    pub fn try_render_id<L: Into<Transform>>(
+        &mut self,
+        figure_id: FigureId,
+        location: L,
+    ) -> Result<(), RendererError> {
+        self.try_render_id_styled(figure_id, location, Style::default())
+    }
+
+    /// Render named figure from a previously added definition at a location (this will automatically add a definition when needed)
+    ///
+    /// # Arguments
+    /// * `name` - Name to use for later reference
+    /// * `figure_id` - id of the figure definition, returned by [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_id](#method.try_render_named_id) returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// let circle_id = renderer.define_render(circle);
+    ///
+    /// // Render circle
+    /// renderer.render_named_id("named_circle", circle_id, (20.0, 20.0));
+    ///
+    /// // --snip
+    ///
+    /// // Updates the Circle's location
+    /// renderer.move_named("named_circle", (25.0, 25.0));
+    /// ```
+    pub fn render_named_id<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+    ) -> NodeHandle {
+        self.try_render_named_id(name, figure_id, location)
+            .expect("Failed to render named figure from id!")
+    }
+
+    /// Fallible version of [render_named_id](#method.render_named_id)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableDefinition` when no definition exists for `figure_id`,
+    /// `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the use cannot be added to the document
+    pub fn try_render_named_id<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+    ) -> Result<NodeHandle, RendererError> {
+        self.try_render_named_id_styled(name, figure_id, location, Style::default())
+    }
+
+    /// Render figure at a location with presentation overrides,
+    /// see [Style](../style/struct.Style.html) for how a figure can inherit these overrides
+    ///
+    /// # Arguments
+    /// * `figure` - The figure to render
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    /// * `style` - The presentation overrides of this use of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_styled](#method.try_render_styled) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// let circle = SVGDefault::inherit_style(SVGDefault::circle(10));
+    ///
+    /// // Render a red and a blue circle, which share one definition
+    /// renderer.render_styled(circle.clone(), (20.0, 20.0), Style::new().stroke("#ff0000"));
+    /// renderer.render_styled(circle, (50.0, 20.0), Style::new().stroke("#0000ff"));
+    /// ```
+    pub fn render_styled<L: Into<Transform>>(
+        &mut self,
+        figure: SVGElem,
+        location: L,
+        style: Style,
+    ) {
+        self.try_render_styled(figure, location, style)
+            .expect("Failed to render styled figure!");
+    }
+
+    /// Fallible version of [render_styled](#method.render_styled)
+    ///
+    /// # Errors
+    /// Will return an error when the definition or the use cannot be added to the document
+    pub fn try_render_styled<L: Into<Transform>>(
+        &mut self,
+        figure: SVGElem,
+        location: L,
+        style: Style,
+    ) -> Result<(), RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_render_id_styled(figure_id, location, style)
+        })
+    }
+
+    /// Render a named figure at a location with presentation overrides,
+    /// the overrides can later be changed with [style_named](#method.style_named)
+    ///
+    /// # Arguments
+    /// * `name` - The name of the figure
+    /// * `figure` - The figure to render
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    /// * `style` - The presentation overrides of this use of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_styled](#method.try_render_named_styled) returns an error.
+    pub fn render_named_styled<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+        style: Style,
+    ) -> NodeHandle {
+        self.try_render_named_styled(name, figure, location, style)
+            .expect("Failed to render named styled figure!")
+    }
+
+    /// Fallible version of [render_named_styled](#method.render_named_styled)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_render_named_styled<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+        style: Style,
+    ) -> Result<NodeHandle, RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_render_named_id_styled(name, figure_id, location, style)
+        })
+    }
+
+    /// Render figure from a previously added definition at a location with presentation overrides
+    ///
+    /// # Arguments
+    /// * `figure_id` - The id of the figure, as returned by [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    /// * `style` - The presentation overrides of this use of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_id_styled](#method.try_render_id_styled) returns an error.
+    pub fn render_id_styled<L: Into<Transform>>(
+        &mut self,
+        figure_id: FigureId,
+        location: L,
+        style: Style,
+    ) {
+        self.try_render_id_styled(figure_id, location, style)
+            .expect("Failed to render styled figure from id!");
+    }
+
+    /// Fallible version of [render_id_styled](#method.render_id_styled)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableDefinition` when no definition exists for `figure_id`
+    pub fn try_render_id_styled<L: Into<Transform>>(
+        &mut self,
+        figure_id: FigureId,
+        location: L,
+        style: Style,
+    ) -> Result<(), RendererError> {
+        let figure_key = self.check_figure_id(figure_id)?;
+
+        // Add use of definition
+        self.add_use(figure_key, location.into(), style)
+    }
+
+    /// Render named figure from a previously added definition at a location with presentation overrides
+    ///
+    /// # Arguments
+    /// * `name` - The name of the figure
+    /// * `figure_id` - The id of the figure, as returned by [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    /// * `style` - The presentation overrides of this use of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_id_styled](#method.try_render_named_id_styled) returns an error.
+    pub fn render_named_id_styled<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+        style: Style,
+    ) -> NodeHandle {
+        self.try_render_named_id_styled(name, figure_id, location, style)
+            .expect("Failed to render named styled figure from id!")
+    }
+
+    /// Fallible version of [render_named_id_styled](#method.render_named_id_styled)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use,
+    /// `RendererError::UnfindableDefinition` when no definition exists for `figure_id`
+    /// and an error when the use cannot be added to the document
+    pub fn try_render_named_id_styled<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+        style: Style,
+    ) -> Result<NodeHandle, RendererError> {
+        let figure_key = self.check_figure_id(figure_id)?;
+
+        // Add named use of definition
+        self.add_named_use(&ROOT_NAME, name, figure_key, location.into(), style)
+    }
+
+    /// Define a figure and return its id, this id can later be used for rendering
+    ///
+    /// # Arguments
+    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
+    ///
+    /// # Panics
+    /// Will panic when [try_define_render](#method.try_define_render) returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Define the render
+    /// let circle_id = renderer.define_render(circle);
+    ///
+    /// // Render circle
+    /// renderer.render_id(circle_id, (20.0, 20.0));
+    /// ```
+    pub fn define_render(&mut self, figure: SVGElem) -> FigureId {
+        self.try_define_render(figure)
+            .expect("Failed to add definition!")
+    }
+
+    /// Fallible version of [define_render](#method.define_render)
+    ///
+    /// # Errors
+    /// Will return an error when the definition cannot be added to the document
+    pub fn try_define_render(&mut self, figure: SVGElem) -> Result<FigureId, RendererError> {
+        let (figure_id, _) = self.define_figure(&figure)?;
+
+        Ok(figure_id)
+    }
+
+    /// Finds or adds the definition of a figure, returns the id of the definition and whether it was added
+    fn define_figure(&mut self, figure: &SVGElem) -> Result<(FigureId, bool), RendererError> {
+        let canonical = CanonicalFigure::from_figure(figure);
+
+        let hash = canonical.get_hash();
+        let (figure_key, is_added) = match self.find_figure(hash, &canonical) {
+            // There is already a definition
+            Some(figure_key) => (figure_key, false),
+            // Add the definition to the dom and hashes
+            None => (self.add_def(hash, figure, canonical, None)?, true),
+        };
+
+        Ok((
+            FigureId {
+                renderer: self.instance_id,
+                hash: figure_key,
+            },
+            is_added,
+        ))
+    }
+
+    /// Defines a figure and runs an operation with the id of its definition,
+    /// when the operation fails a definition which was added for it and is still unused is removed again
+    fn with_definition<T, F>(&mut self, figure: SVGElem, operation: F) -> Result<T, RendererError>
+    where
+        F: FnOnce(&mut Self, FigureId) -> Result<T, RendererError>,
+    {
+        let (figure_id, is_added) = self.define_figure(&figure)?;
+        let figure_key = figure_id.hash;
+
+        let result = operation(self, figure_id);
+
+        if result.is_err() && is_added {
+            let is_unused = self
+                .figure_defs
+                .get(&figure_key)
+                .is_some_and(|definition| definition.uses == 0 && definition.key.is_none());
+
+            if is_unused {
+                // The error of the operation is more telling than one of the cleanup
+                let _ = self.remove_def(figure_key);
+            }
+        }
+
+        result
+    }
+
+    /// Define a figure under a key and return its id. When the key is already defined,
+    /// the figure of the definition is replaced, which changes every figure rendered with it at once.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the definition, which stays the same when the figure is replaced
+    /// * `figure` - The figure of the definition
+    ///
+    /// # Note
+    /// A definition with a key is never shared with figures rendered by their content,
+    /// even when they are equal, and is never removed by [collect_garbage](#method.collect_garbage).
+    ///
+    /// # Panics
+    /// Will panic when [try_define_as](#method.try_define_as) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// let player = renderer.define_as("player", SVGDefault::circle(5));
+    /// renderer.render_named_id("alice", player, (10.0, 10.0));
+    /// renderer.render_named_id("bob", player, (30.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Both players now have a different skin
+    /// renderer.define_as("player", SVGDefault::rect(10, 10));
+    /// ```
+    pub fn define_as(&mut self, key: &str, figure: SVGElem) -> FigureId {
+        self.try_define_as(key, figure)
+            .expect("Failed to add definition!")
+    }
+
+    /// Fallible version of [define_as](#method.define_as)
+    ///
+    /// # Errors
+    /// Will return an error when the definition cannot be added to or replaced within the document
+    pub fn try_define_as(&mut self, key: &str, figure: SVGElem) -> Result<FigureId, RendererError> {
+        let canonical = CanonicalFigure::from_figure(&figure);
+
+        let figure_key = match self.keyed_defs.get(key) {
+            Some(figure_key) => {
+                let figure_key = *figure_key;
+                self.replace_def(figure_key, &figure, canonical)?;
+                figure_key
+            }
+            None => {
+                let figure_key = self.add_def(
+                    Self::get_key_hash(key),
+                    &figure,
+                    canonical,
+                    Some(String::from(key)),
+                )?;
+                self.keyed_defs.insert(String::from(key), figure_key);
+                figure_key
+            }
+        };
+
+        Ok(FigureId {
+            renderer: self.instance_id,
+            hash: figure_key,
+        })
+    }
+
+    /// Will return the id of a definition added with [define_as](#method.define_as)
+    ///
+    /// # Arguments
+    /// * `key` - The key of the definition
+    pub fn get_definition(&self, key: &str) -> Option<FigureId> {
+        let figure_key = *self.keyed_defs.get(key)?;
+
+        Some(FigureId {
+            renderer: self.instance_id,
+            hash: figure_key,
+        })
+    }
+
+    /// Clears all elements within the SVG element and clears all internal definitions.
+    /// Basically reinits the renderer.
+    ///
+    /// # Panics
+    /// Will panic when [try_clear](#method.try_clear) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Reinit the Renderer object
+    /// renderer.clear();
+    ///
+    /// // Renders the circle with "named_circle" name again.
+    /// // This would normally panic, since a name is redeclared,
+    /// // but since the renderer is cleared, it will not. :)
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    /// ```
+    pub fn clear(&mut self) {
+        self.try_clear().expect("Failed to clear renderer!");
+    }
+
+    /// Fallible version of [clear](#method.clear)
+    ///
+    /// # Errors
+    /// Will return an error when the svg cannot be found or cleared
+    pub fn try_clear(&mut self) -> Result<(), RendererError> {
+        let svg_root = self.get_svg_root()?;
+        let defs_root = to_element(&self.backend, &SVGElem::new(Tag::Defs))?;
+
+        // Queued operations are cleared as well
+        if let Some(batch) = self.batch.borrow_mut().as_mut() {
+            batch.take();
+        }
+
+        self.backend.remove_children(&svg_root)?;
+        self.backend.append_child(&svg_root, &defs_root)?;
+
+        self.defs_root = defs_root;
+        self.figure_defs = BTreeMap::new();
+        self.figure_keys = HashMap::new();
+        self.keyed_defs = HashMap::new();
+        self.name_defs = HashMap::new();
+        self.named_items = HashMap::new();
+        self.spatial_index.clear();
+        self.event_listeners = HashMap::new();
+
+        Ok(())
+    }
+
+    /// Rebuilds the definitions and names from the svg within the document,
+    /// which is needed after the svg has been edited without the renderer.
+    ///
+    /// # Note
+    /// Only definitions and named items with an id within the [namespace](#method.namespace) of the renderer are recovered.
+    /// Named items also need the `data-name` attribute, which the renderer adds to every named item.
+    /// Handles and figure ids stay valid as long as their item or definition still exists.
+    /// Event handlers are dropped for the named items of which the element was replaced,
+    /// see [same_element](../backend/trait.Backend.html#method.same_element).
+    /// The renderer keeps the elements of the svg and the named items,
+    /// so a resync is also needed after any of these elements are replaced.
+    ///
+    /// # Panics
+    /// Will panic when [try_resync](#method.try_resync) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // --snip, the named circle is removed from the DOM by some other script
+    ///
+    /// renderer.resync();
+    ///
+    /// // The name can be declared again
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    /// ```
+    pub fn resync(&mut self) {
+        self.try_resync().expect("Failed to resync renderer!");
+    }
+
+    /// Fallible version of [resync](#method.resync)
+    ///
+    /// # Errors
+    /// Will return `DomError::UnfindableTag` if the parent does not contain a svg
+    /// with a defs element as its first child
+    pub fn try_resync(&mut self) -> Result<(), RendererError> {
+        self.apply_batch()?;
+
+        let svg_root = Self::find_svg_root(&self.backend, &self.dom_root_id[..])?;
+        let defs = Self::find_defs_root(&self.backend, &svg_root)?;
+
+        self.svg_root = svg_root.clone();
+        self.defs_root = defs.clone();
+        self.figure_defs = BTreeMap::new();
+        self.figure_keys = HashMap::new();
+        self.keyed_defs = HashMap::new();
+        self.name_defs = HashMap::new();
+        self.spatial_index.clear();
+
+        let old_items: HashMap<u64, NamedItem<B::Element>> = self.named_items.drain().collect();
+
+        for def in self.backend.children(&defs).iter() {
+            if let Some(figure_key) = self
+                .backend
+                .get_attribute(def, "id")
+                .and_then(|id| self.get_figure_of_id(&id[..]))
+            {
+                let figure = CanonicalFigure::from_element(&self.backend, def, true);
+                let key = self.backend.get_attribute(def, DEFINITION_KEY_ATTRIBUTE);
+                if let Some(key) = key.as_ref() {
+                    self.keyed_defs.insert(key.clone(), figure_key);
+                }
+
+                let hash = match key.as_ref() {
+                    Some(key) => Self::get_key_hash(&key[..]),
+                    None => figure.get_hash(),
+                };
+                self.insert_definition(
+                    figure_key,
+                    Definition {
+                        figure,
+                        uses: 0,
+                        key,
+                        hash,
+                    },
+                );
+            }
+        }
+
+        // The first child is the defs element
+        for child in self.backend.children(&svg_root).iter().skip(1) {
+            self.resync_element(None, child);
+        }
+
+        // Handles stay valid for the named items which still exist
+        for (id_hash, item) in self.named_items.iter_mut() {
+            if let Some(old_item) = old_items.get(id_hash) {
+                item.generation = old_item.generation;
+            }
+        }
+
+        // Event listeners are kept for the named items which still exist with the same element,
+        // as the listeners of a replaced element are not called anymore
+        let (backend, named_items) = (&self.backend, &self.named_items);
+        self.event_listeners.retain(|id_hash, _| {
+            match (old_items.get(id_hash), named_items.get(id_hash)) {
+                (Some(old_item), Some(item)) => {
+                    backend.same_element(&old_item.element, &item.element)
+                }
+                _ => false,
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Sets whether operations are queued until they are flushed, instead of being applied directly.
+    ///
+    /// While batching, changes to the attributes of named items are coalesced,
+    /// so only the last location of a named figure moved multiple times is written to the document.
+    /// New figures and containers are appended together, per parent.
+    /// Operations which remove elements, e.g. [delete_named](#method.delete_named),
+    /// first flush the queued operations and are then applied directly.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to batch operations, disabling flushes all queued operations
+    ///
+    /// # Panics
+    /// Will panic when [try_set_batching](#method.try_set_batching) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// renderer.set_batching(true);
+    ///
+    /// // Only the last location is written to the DOM
+    /// for i in 0..10 {
+    ///     renderer.move_named("named_circle", (i as f32, i as f32));
+    /// }
+    ///
+    /// renderer.flush();
+    /// ```
+    pub fn set_batching(&mut self, enabled: bool) {
+        self.try_set_batching(enabled)
+            .expect("Failed to set batching!");
+    }
+
+    /// Fallible version of [set_batching](#method.set_batching)
+    ///
+    /// # Errors
+    /// Will return an error when the queued operations cannot be flushed
+    pub fn try_set_batching(&mut self, enabled: bool) -> Result<(), RendererError> {
+        if enabled {
+            let mut batch = self.batch.borrow_mut();
+
+            if batch.is_none() {
+                *batch = Some(Batch::new());
+            }
+
+            return Ok(());
+        }
+
+        self.apply_batch()?;
+        *self.batch.borrow_mut() = None;
+
+        Ok(())
+    }
+
+    /// Will return whether operations are queued until they are flushed, see [set_batching](#method.set_batching)
+    pub fn is_batching(&self) -> bool {
+        self.batch.borrow().is_some()
+    }
+
+    /// Applies all operations queued while batching to the document, see [set_batching](#method.set_batching)
+    ///
+    /// # Panics
+    /// Will panic when [try_flush](#method.try_flush) returns an error.
+    pub fn flush(&self) {
+        self.try_flush()
+            .expect("Failed to flush queued operations!");
+    }
+
+    /// Fallible version of [flush](#method.flush)
+    ///
+    /// # Errors
+    /// Will return an error when a queued operation cannot be applied to the document
+    pub fn try_flush(&self) -> Result<(), RendererError> {
+        self.apply_batch()
+    }
+
+    /// Removes all definitions which are no longer used by any figure and returns how many were removed.
+    ///
+    /// # Note
+    /// Definitions added with [define_render](#method.define_render) which have not been rendered yet
+    /// are also removed, their figure ids will no longer be valid afterwards.
+    /// Unused definitions can also be removed automatically,
+    /// see [set_auto_garbage_collection](#method.set_auto_garbage_collection).
+    ///
+    /// # Panics
+    /// Will panic when [try_collect_garbage](#method.try_collect_garbage) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // The definition of the circle is no longer used by any figure
+    /// renderer.update_named("named_circle", SVGDefault::rect(10, 10), (10.0, 10.0));
+    ///
+    /// // Removes the definition of the circle
+    /// assert_eq!(renderer.collect_garbage(), 1);
+    /// ```
+    pub fn collect_garbage(&mut self) -> usize {
+        self.try_collect_garbage()
+            .expect("Failed to collect garbage!")
+    }
+
+    /// Fallible version of [collect_garbage](#method.collect_garbage)
+    ///
+    /// # Errors
+    /// Will return an error when a definition cannot be removed from the document
+    pub fn try_collect_garbage(&mut self) -> Result<usize, RendererError> {
+        let unused: Vec<u64> = self
+            .figure_defs
+            .iter()
+            .filter(|(_, definition)| definition.uses == 0 && definition.key.is_none())
+            .map(|(figure_key, _)| *figure_key)
+            .collect();
+
+        for figure_key in unused.iter() {
+            self.remove_def(*figure_key)?;
+        }
+
+        Ok(unused.len())
+    }
+
+    /// Sets whether definitions are removed as soon as they are no longer used by any figure
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to automatically remove unused definitions
+    ///
+    /// # Note
+    /// This is disabled by default, since it means a definition has to be added again
+    /// when the same figure is rendered later on, and figure ids of removed definitions are no longer valid.
+    /// Enabling it does not remove the currently unused definitions,
+    /// use [collect_garbage](#method.collect_garbage) for that.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.set_auto_garbage_collection(true);
+    ///
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Also removes the definition of the circle
+    /// renderer.delete_named("named_circle");
+    /// ```
+    pub fn set_auto_garbage_collection(&mut self, enabled: bool) {
+        self.auto_collect_garbage = enabled;
+    }
+
+    /// Clears all figures/containers within a named container, but does not clear up definitions
+    /// unless automatic garbage collection is enabled.
+    ///
+    /// # Arguments
+    /// * `container_name` - The name of the container to clear
+    ///
+    ///
+    /// # Panics
+    /// Will panic when a name passed in is in use by a pure figure.
+    /// For pure figures use [delete_named](#method.delete_named) or [hide_named](#method.hide_named) instead.
+    ///
+    /// # Note
+    /// Most of the time the [update_named](#method.update_named) method is a better alternative.
+    /// Also have a look at [hide_named](#method.hide_named) and [move_named](#method.move_named)
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.create_named_container("named_container", "root");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // Now the container contains the circle figure
+    ///
+    /// // --snip
+    ///
+    /// // Clear the named container
+    /// renderer.clear_named_container("named_container");
+    ///
+    /// // Now the container is empty again
+    ///
+    /// // Render circle in the named_container.
+    /// // Since definitions were not cleared, it will use a previous definition.
+    /// // This saves some processing time in this case.
+    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (20.0, 20.0));
+    ///
+    /// // Now the container contains the circle at a different position
+    /// ```
+    pub fn clear_named_container<N: NameRef>(&mut self, container_name: N) {
+        self.try_clear_named_container(container_name)
+            .expect("Failed to clear named container!");
+    }
+
+    /// Fallible version of [clear_named_container](#method.clear_named_container)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::NamedNotContainer` when the name is used for a pure figure
+    pub fn try_clear_named_container<N: NameRef>(
+        &mut self,
+        container_name: N,
+    ) -> Result<(), RendererError> {
+        self.try_clear_named_container_with_report(container_name)?;
+
+        Ok(())
+    }
+
+    /// Clears a named container, see [clear_named_container](#method.clear_named_container),
+    /// and will return the names of all items which were within it
+    ///
+    /// # Arguments
+    /// * `container_name` - The name of the named container
+    ///
+    /// # Panics
+    /// Will panic when [try_clear_named_container_with_report](#method.try_clear_named_container_with_report)
+    /// returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_named_container("enemies", "root");
+    /// renderer.render_named_in("enemies", "enemy_1", SVGDefault::circle(5), (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// for name in renderer.clear_named_container_with_report("enemies") {
+    ///     // Forget the state kept for the enemy
+    /// }
+    /// ```
+    pub fn clear_named_container_with_report<N: NameRef>(
+        &mut self,
+        container_name: N,
+    ) -> Vec<String> {
+        self.try_clear_named_container_with_report(container_name)
+            .expect("Failed to clear named container!")
+    }
+
+    /// Fallible version of [clear_named_container_with_report](#method.clear_named_container_with_report)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::NamedNotContainer` when the name is used for a pure figure
+    pub fn try_clear_named_container_with_report<N: NameRef>(
+        &mut self,
+        container_name: N,
+    ) -> Result<Vec<String>, RendererError> {
+        let key = self.get_item_key(&container_name)?;
+        let container = self.get_named_container(&container_name)?;
+
+        self.delete_children(key, &container)
+    }
+
+    /// Updates a named container or figure to either contain the passed figure or become the passed figure, respectively.
+    ///
+    /// # Arguments
+    /// * `name` - The name of either a named container or a named figure
+    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_update_named](#method.try_update_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.create_named_container("named_container", "root");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // Now the container contains the circle figure
+    ///
+    /// // --snip
+    ///
+    /// // Update the contents of the named container
+    /// renderer.update_named("named_container", SVGDefault::circle(10), (20.0, 20.0));
+    ///
+    /// // Now the container contains the circle at a different position
+    /// ```
+    pub fn update_named<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure: SVGElem,
+        location: L,
+    ) {
+        self.try_update_named(name, figure, location)
+            .expect("Failed to update named item!");
+    }
+
+    /// Fallible version of [update_named](#method.update_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_update_named<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<(), RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_update_named_with_id(name, figure_id, location)
+        })
+    }
+
+    /// Updates a named container or figure to either contain the passed figure
+    /// or become the passed figure from the id, respectively.
+    ///
+    /// # Arguments
+    /// * `name` - The name of either a named container or a named figure
+    /// * `figure_id` - id of Figure definition used when adding to the dom,
+    ///   defined using [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_update_named_with_id](#method.try_update_named_with_id) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.create_named_container("named_container", "root");
+    ///
+    /// let circle_id = renderer.define_render(SVGDefault::circle(10));
+    ///
+    /// // Render circle
+    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // Now the container contains the circle figure
+    ///
+    /// // --snip
+    ///
+    /// // Update the contents of the named container
+    /// renderer.update_named_with_id("named_container", circle_id, (20.0, 20.0));
+    ///
+    /// // Now the container contains the circle at a different position
+    /// ```
+    pub fn update_named_with_id<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure_id: FigureId,
+        location: L,
+    ) {
+        self.try_update_named_with_id(name, figure_id, location)
+            .expect("Failed to update named item from id!");
+    }
+
+    /// Fallible version of [update_named_with_id](#method.update_named_with_id)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableDefinition` when no definition exists for `figure_id`,
+    /// `RendererError::UnfindableName` when the name is not in use
+    /// and an error when the use cannot be added to the document
+    pub fn try_update_named_with_id<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure_id: FigureId,
+        location: L,
+    ) -> Result<(), RendererError> {
+        let figure_key = self.check_figure_id(figure_id)?;
+
+        match self.get_named_container(&name) {
+            Ok(container) => {
+                // Delete all current elements in de container,
+                // the references are only removed after the new use has been added
+                self.apply_batch()?;
+                let old_references = self.get_references_within(&container);
+                self.backend.remove_children(&container)?;
+                self.remove_names_within(self.get_item_key(&name)?);
+
+                // Add element to container
+                self.add_use_to(&name, figure_key, location.into())?;
+
+                self.remove_references(old_references)
+            }
+            // Adjust use element
+            Err(NamedNotContainer(_)) => self.adjust_use_to(&name, figure_key, location.into()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Hides a named item in the DOM, this can be undone by the [show_named](#method.show_named) method.
+    ///
+    /// # Arguments
+    /// * `name` - Name of item to hide
+    ///
+    /// # Panics
+    /// Will panic when [try_hide_named](#method.try_hide_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Hides the named figure
+    /// renderer.hide_named("named_circle");
+    /// ```
+    pub fn hide_named<N: NameRef>(&self, name: N) {
+        self.try_hide_named(name)
+            .expect("Failed to hide named item!");
+    }
+
+    /// Fallible version of [hide_named](#method.hide_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_hide_named<N: NameRef>(&self, name: N) -> Result<(), RendererError> {
+        self.try_hide_named_with(name, HideMode::Display)
+    }
+
+    /// Hides a named item in the DOM in a certain way, this can be undone by the [show_named](#method.show_named) method.
+    /// Only a single property of the style attribute is set, all other properties are kept.
+    ///
+    /// # Arguments
+    /// * `name` - Name of item to hide
+    /// * `mode` - The way in which the item is hidden, see [HideMode](../style/enum.HideMode.html)
+    ///
+    /// # Panics
+    /// Will panic when [try_hide_named_with](#method.try_hide_named_with) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // Fades out the named figure, when a CSS transition on the opacity is set
+    /// renderer.hide_named_with("named_circle", HideMode::Opacity);
+    /// ```
+    pub fn hide_named_with<N: NameRef>(&self, name: N, mode: HideMode) {
+        self.try_hide_named_with(name, mode)
+            .expect("Failed to hide named item!");
+    }
+
+    /// Fallible version of [hide_named_with](#method.hide_named_with)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_hide_named_with<N: NameRef>(
+        &self,
+        name: N,
+        mode: HideMode,
+    ) -> Result<(), RendererError> {
+        let (property, value) = mode.property();
+
+        self.write_style_property(
+            self.get_item_key(&name)?,
+            &self.get_named_item(&name)?,
+            property,
+            Some(value),
+        )
+    }
+
+    /// Shows a named item in the DOM, this undoes the [hide_named](#method.hide_named) method.
+    ///
+    /// # Arguments
+    /// * `name` - Name of item to show
+    ///
+    /// # Panics
+    /// Will panic when [try_show_named](#method.try_show_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
+    ///
+    /// // Hides the named figure
+    /// renderer.hide_named("named_circle");
+    ///
+    /// // --snip
+    ///
+    /// // Show the named figure again
+    /// renderer.show_named("named_circle");
+    /// ```
+    pub fn show_named<N: NameRef>(&self, name: N) {
+        self.try_show_named(name)
+            .expect("Failed to show named item!");
+    }
+
+    /// Fallible version of [show_named](#method.show_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_show_named<N: NameRef>(&self, name: N) -> Result<(), RendererError> {
+        self.show_element(self.get_item_key(&name)?, &self.get_named_item(&name)?)
+    }
+
+    /// Will return whether a named item is visible, this is not the case when the item
+    /// or one of the containers it is in has been hidden.
+    /// Like in a browser, an item which sets `visibility: visible;` itself is visible
+    /// within a container hidden with [HideMode::Visibility](../style/enum.HideMode.html).
+    ///
+    /// # Arguments
+    /// * `name` - Name of the item
+    ///
+    /// # Panics
+    /// Will panic when [try_is_visible](#method.try_is_visible) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_named_container("container", "root");
+    /// renderer.hide_named("container");
+    ///
+    /// assert!(!renderer.is_visible("container"));
+    /// ```
+    pub fn is_visible<N: NameRef>(&self, name: N) -> bool {
+        self.try_is_visible(name)
+            .expect("Failed to check visibility of named item!")
+    }
+
+    /// Fallible version of [is_visible](#method.is_visible)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_is_visible<N: NameRef>(&self, name: N) -> Result<bool, RendererError> {
+        let mut key = Some(self.get_item_key(&name)?);
+        let mut element = self.get_named_item(&name)?;
+
+        // The innermost visibility applies, while any display or opacity which hides an element hides all within it
+        let mut is_visibility_set = false;
+        loop {
+            let style = self.read_style(key, &element);
+            let property = |mode: HideMode| get_style_property(style.as_deref(), mode.property().0);
+
+            match property(HideMode::Visibility).as_deref() {
+                Some("visible") => is_visibility_set = true,
+                Some(value) if !is_visibility_set && HideMode::Visibility.is_hidden_by(value) => {
+                    return Ok(false)
+                }
+                _ => {}
+            }
+
+            let is_hidden = [HideMode::Display, HideMode::Opacity]
+                .iter()
+                .any(|mode| property(*mode).is_some_and(|value| mode.is_hidden_by(&value[..])));
+            if is_hidden {
+                return Ok(false);
+            }
+
+            // The svg root has been reached
+            if key == Some(None) {
+                return Ok(true);
+            }
+
+            element = match self.backend.parent_element(&element) {
+                Some(parent) => parent,
+                None => return Ok(true),
+            };
+
+            key = if self.backend.tag_name(&element) == "svg" {
+                Some(None)
+            } else {
+                self.backend
+                    .get_attribute(&element, "id")
+                    .and_then(|id| self.get_named_of_id(&id[..]))
+                    .filter(|id_hash| self.named_items.contains_key(id_hash))
+                    .map(Some)
+            };
+        }
+    }
+
+    /// Hides a visible named item or shows a hidden named item,
+    /// only the item itself is considered and not the containers it is in
+    ///
+    /// # Arguments
+    /// * `name` - Name of item to toggle
+    ///
+    /// # Returns
+    /// Whether the item is shown after toggling
+    ///
+    /// # Panics
+    /// Will panic when [try_toggle_named](#method.try_toggle_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// assert!(!renderer.toggle_named("named_circle"));
+    /// assert!(renderer.toggle_named("named_circle"));
+    /// ```
+    pub fn toggle_named<N: NameRef>(&self, name: N) -> bool {
+        self.try_toggle_named(name)
+            .expect("Failed to toggle named item!")
+    }
+
+    /// Fallible version of [toggle_named](#method.toggle_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_toggle_named<N: NameRef>(&self, name: N) -> Result<bool, RendererError> {
+        let key = self.get_item_key(&name)?;
+        let element = self.get_named_item(&name)?;
+
+        if self.is_hidden(Some(key), &element) {
+            self.show_element(key, &element)?;
+
+            Ok(true)
+        } else {
+            let (property, value) = HideMode::default().property();
+            self.write_style_property(key, &element, property, Some(value))?;
+
+            Ok(false)
+        }
+    }
+
+    /// Appends a figure to a named container
+    ///
+    /// # Arguments
+    /// * `name` - The name of either a named container
+    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when a name passed in is in use by a pure figure.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.create_named_container("named_container", "root");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.append_to_container("named_container", circle, (10.0, 10.0));
+    ///
+    /// // Now the container contains the circle figure
+    /// ```
+    pub fn append_to_container<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure: SVGElem,
+        location: L,
+    ) {
+        self.try_append_to_container(name, figure, location)
+            .expect("Failed to add figure to container!");
+    }
+
+    /// Fallible version of [append_to_container](#method.append_to_container)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use,
+    /// `RendererError::NamedNotContainer` when the name is used for a pure figure
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_append_to_container<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<(), RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_append_to_container_with_id(name, figure_id, location)
+        })
+    }
+
+    /// Appends a figure from id to a named container
+    ///
+    /// # Arguments
+    /// * `name` - The name of either a named container
+    /// * `figure_id` - id of Figure definition used when adding to the dom,
+    ///   defined using [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when a name passed in is in use by a pure figure.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.create_named_container("named_container", "root");
+    ///
+    /// let circle_id = renderer.define_render(SVGDefault::circle(10));
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.append_to_container_with_id("named_container", circle_id, (10.0, 10.0));
+    ///
+    /// // Now the container contains the circle figure
+    /// ```
+    pub fn append_to_container_with_id<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure_id: FigureId,
+        location: L,
+    ) {
+        self.try_append_to_container_with_id(name, figure_id, location)
+            .expect("Failed to add figure to container!");
+    }
+
+    /// Fallible version of [append_to_container_with_id](#method.append_to_container_with_id)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableDefinition` when no definition exists for `figure_id`,
+    /// `RendererError::UnfindableName` when the name is not in use,
+    /// `RendererError::NamedNotContainer` when the name is used for a pure figure
+    /// and an error when the use cannot be added to the document
+    pub fn try_append_to_container_with_id<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        figure_id: FigureId,
+        location: L,
+    ) -> Result<(), RendererError> {
+        let figure_key = self.check_figure_id(figure_id)?;
+
+        self.add_use_to(&name, figure_key, location.into())
+    }
+
+    /// Render a named figure within a named container, the figure can later be moved on its own.
+    /// Containers can be nested, so every item of a hierarchical scene can be addressed by name.
+    ///
+    /// # Arguments
+    /// * `container` - The name of the named container, or "root" for the svg root
+    /// * `name` - Name to use for later reference
+    /// * `figure` - The figure to render
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`,
+    ///   relative to the container
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_in](#method.try_render_named_in) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_named_container("car", "root");
+    /// renderer.create_named_container("wheels", "car");
+    ///
+    /// renderer.render_named_in("wheels", "front_wheel", SVGDefault::circle(5), (30.0, 10.0));
+    /// renderer.render_named_in("wheels", "back_wheel", SVGDefault::circle(5), (0.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Moves the whole car, and a single wheel within the car
+    /// renderer.transform_named("car", (100.0, 0.0));
+    /// renderer.move_named("front_wheel", (32.0, 10.0));
+    /// ```
+    pub fn render_named_in<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        container: N,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+    ) -> NodeHandle {
+        self.try_render_named_in(container, name, figure, location)
+            .expect("Failed to render named figure in container!")
+    }
+
+    /// Fallible version of [render_named_in](#method.render_named_in)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the container is not in use,
+    /// `RendererError::NamedNotContainer` when the container is used for a pure figure,
+    /// `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_render_named_in<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        container: N,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<NodeHandle, RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_render_named_id_in(container, name, figure_id, location)
+        })
+    }
+
+    /// Render a named figure from a previously added definition within a named container,
+    /// see [render_named_in](#method.render_named_in)
+    ///
+    /// # Arguments
+    /// * `container` - The name of the named container, or "root" for the svg root
+    /// * `name` - Name to use for later reference
+    /// * `figure_id` - id of the figure definition, returned by [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`,
+    ///   relative to the container
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_id_in](#method.try_render_named_id_in) returns an error.
+    pub fn render_named_id_in<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        container: N,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+    ) -> NodeHandle {
+        self.try_render_named_id_in(container, name, figure_id, location)
+            .expect("Failed to render named figure from id in container!")
+    }
+
+    /// Fallible version of [render_named_id_in](#method.render_named_id_in)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableDefinition` when no definition exists for `figure_id`,
+    /// `RendererError::UnfindableName` when the container is not in use,
+    /// `RendererError::NamedNotContainer` when the container is used for a pure figure,
+    /// `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the use cannot be added to the document
+    pub fn try_render_named_id_in<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        container: N,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+    ) -> Result<NodeHandle, RendererError> {
+        let figure_key = self.check_figure_id(figure_id)?;
+
+        self.add_named_use(
+            &container,
+            name,
+            figure_key,
+            location.into(),
+            Style::default(),
+        )
+    }
+
+    /// Deletes a named item from the DOM and from internal entries.
+    /// But will not delete definitions
+    ///
+    /// # Arguments
+    /// * `name` - Name of item to delete
+    ///
+    /// # Panics
+    /// Will panic when [try_delete_named](#method.try_delete_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Delete the named figure
+    /// renderer.delete_named("named_circle");
+    ///
+    /// // Renders the circle with "named_circle" name again.
+    /// // This would normally panic, since a name is redeclared,
+    /// // but since the named figure is deleted, it will not. :)
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    /// ```
+    pub fn delete_named<N: NameRef>(&mut self, name: N) {
+        self.try_delete_named(name)
+            .expect("Failed to delete named item!");
+    }
+
+    /// Fallible version of [delete_named](#method.delete_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and an error when the item cannot be removed from the document
+    pub fn try_delete_named<N: NameRef>(&mut self, name: N) -> Result<(), RendererError> {
+        self.try_delete_named_with_report(name)?;
+
+        Ok(())
+    }
+
+    /// Deletes a named item, see [delete_named](#method.delete_named),
+    /// and will return all names which are no longer in use.
+    /// For a named container these are the name of the container and the names of all items within it.
+    ///
+    /// # Arguments
+    /// * `name` - Name of item to delete
+    ///
+    /// # Panics
+    /// Will panic when [try_delete_named_with_report](#method.try_delete_named_with_report) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_named_container("car", "root");
+    /// renderer.render_named_in("car", "wheel", SVGDefault::circle(5), (0.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// let removed = renderer.delete_named_with_report("car");
+    /// assert_eq!(removed, vec!["car", "wheel"]);
+    /// ```
+    pub fn delete_named_with_report<N: NameRef>(&mut self, name: N) -> Vec<String> {
+        self.try_delete_named_with_report(name)
+            .expect("Failed to delete named item!")
+    }
+
+    /// Fallible version of [delete_named_with_report](#method.delete_named_with_report)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and an error when the item cannot be removed from the document
+    pub fn try_delete_named_with_report<N: NameRef>(
+        &mut self,
+        name: N,
+    ) -> Result<Vec<String>, RendererError> {
+        let id_hash = self.get_id_hash(&name)?;
+
+        self.delete_named_item(id_hash)
+    }
+
+    /// Moves a named item into another named container, the item keeps its transform within its new parent
+    ///
+    /// # Arguments
+    /// * `name` - Name of the item to move
+    /// * `new_parent` - The name of the named container, or "root" for the svg root
+    ///
+    /// # Note
+    /// The item is drawn on top of the other items of its new parent.
+    /// A layer which is moved into a container is no longer a layer.
+    ///
+    /// # Panics
+    /// Will panic when [try_reparent_named](#method.try_reparent_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_named_container("ground", "root");
+    /// renderer.create_named_container("car", "root");
+    /// renderer.render_named_in("ground", "box", SVGDefault::rect(10, 10), (0.0, 0.0));
+    ///
+    /// // --snip
+    ///
+    /// // The box is loaded onto the car and moves with it from now on
+    /// renderer.reparent_named("box", "car");
+    /// renderer.transform_named("car", (100.0, 0.0));
+    /// ```
+    pub fn reparent_named<N: NameRef, P: NameRef>(&mut self, name: N, new_parent: P) {
+        self.try_reparent_named(name, new_parent)
+            .expect("Failed to reparent named item!");
+    }
+
+    /// Fallible version of [reparent_named](#method.reparent_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when either name is not in use,
+    /// `RendererError::NamedNotContainer` when the new parent is used for a pure figure,
+    /// `RendererError::NamedWithinItself` when the new parent is the item itself or an item within it
+    /// and an error when the item cannot be moved within the document
+    pub fn try_reparent_named<N: NameRef, P: NameRef>(
+        &mut self,
+        name: N,
+        new_parent: P,
+    ) -> Result<(), RendererError> {
+        let id_hash = self.get_id_hash(&name)?;
+        let parent_key = self.get_item_key(&new_parent)?;
+        let parent = self.get_named_container(&new_parent)?;
+
+        if let Some(parent_key) = parent_key {
+            if self.get_named_subtree(id_hash).contains(&parent_key) {
+                return Err(NamedWithinItself(
+                    String::from(name.name()),
+                    String::from(new_parent.name()),
+                ));
+            }
+        }
+
+        // The element has to be in the document before it can be moved
+        self.apply_batch()?;
+
+        let item = &self.named_items[&id_hash];
+        let element = item.element.clone();
+        let old_parent_key = item.parent;
+        let was_layer = item.z_index.is_some();
+
+        self.backend.append_child(&parent, &element)?;
+        if was_layer {
+            self.backend.remove_attribute(&element, Z_INDEX_ATTRIBUTE)?;
+        }
+
+        if let Some(old_parent) = old_parent_key.and_then(|key| self.named_items.get_mut(&key)) {
+            old_parent.children.retain(|child| *child != id_hash);
+        }
+        if let Some(new_parent) = parent_key.and_then(|key| self.named_items.get_mut(&key)) {
+            new_parent.children.push(id_hash);
+        }
+        if let Some(item) = self.named_items.get_mut(&id_hash) {
+            item.parent = parent_key;
+            item.z_index = None;
+        }
+        self.update_spatial_index(id_hash);
+
+        Ok(())
+    }
+
+    /// Changes the name of a named item
+    ///
+    /// # Arguments
+    /// * `old_name` - The current name of the item
+    /// * `new_name` - The new name of the item
+    ///
+    /// # Note
+    /// The handles of the old name can no longer be used, use the returned handle instead.
+    /// The handles of the items within a renamed container stay valid.
+    ///
+    /// # Panics
+    /// Will panic when [try_rename_named](#method.try_rename_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("new_player", SVGDefault::circle(5), (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// renderer.rename_named("new_player", "alice");
+    /// renderer.move_named("alice", (20.0, 10.0));
+    /// ```
+    pub fn rename_named<N: NameRef>(&mut self, old_name: N, new_name: &str) -> NodeHandle {
+        self.try_rename_named(old_name, new_name)
+            .expect("Failed to rename named item!")
+    }
+
+    /// Fallible version of [rename_named](#method.rename_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the old name is not in use,
+    /// `RendererError::NameAlreadyExists` when the new name is already in use
+    /// and an error when the id of the item cannot be changed
+    pub fn try_rename_named<N: NameRef>(
+        &mut self,
+        old_name: N,
+        new_name: &str,
+    ) -> Result<NodeHandle, RendererError> {
+        let old_id_hash = self.get_id_hash(&old_name)?;
+        let handle = self.create_handle(new_name)?;
+
+        // Queued changes are stored by the id hash, which changes with the name
+        self.apply_batch()?;
+
+        self.set_named_id(&self.named_items[&old_id_hash].element, &handle)?;
+
+        let mut item = match self.named_items.remove(&old_id_hash) {
+            Some(item) => item,
+            None => return Err(UnfindableName(String::from(old_name.name()))),
+        };
+        self.name_defs.remove(&item.name);
+        self.spatial_index.remove(old_id_hash);
+
+        if let Some(parent) = item.parent.and_then(|key| self.named_items.get_mut(&key)) {
+            for child in parent
+                .children
+                .iter_mut()
+                .filter(|child| **child == old_id_hash)
+            {
+                *child = handle.id_hash;
+            }
+        }
+        for child in item.children.iter() {
+            if let Some(child) = self.named_items.get_mut(child) {
+                child.parent = Some(handle.id_hash);
+            }
+        }
+
+        if let Some(listeners) = self.event_listeners.remove(&old_id_hash) {
+            *listeners.name.borrow_mut() = String::from(new_name);
+            self.event_listeners.insert(handle.id_hash, listeners);
+        }
+
+        item.name = String::from(new_name);
+        item.generation = handle.generation;
+        self.name_defs.insert(item.name.clone(), handle.id_hash);
+        self.named_items.insert(handle.id_hash, item);
+        self.update_spatial_index(handle.id_hash);
+
+        Ok(handle)
+    }
+
+    /// Copies a named item, together with all items within a named container.
+    /// The copy is added to the same parent, on top of the other items,
+    /// and the copy of every named item is named after the original with a prefix.
+    /// The copies use the same definitions as the originals.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the item to copy
+    /// * `new_name_prefix` - The prefix of the names of the copies
+    ///
+    /// # Returns
+    /// The handle of the copy of the item
+    ///
+    /// # Panics
+    /// Will panic when [try_duplicate_named](#method.try_duplicate_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_named_container("car", "root");
+    /// renderer.render_named_in("car", "wheel", SVGDefault::circle(5), (0.0, 10.0));
+    ///
+    /// // Adds 'second_car' with the named figure 'second_wheel'
+    /// renderer.duplicate_named("car", "second_");
+    /// renderer.transform_named("second_car", (0.0, 50.0));
+    /// renderer.move_named("second_wheel", (5.0, 10.0));
+    /// ```
+    pub fn duplicate_named<N: NameRef>(&mut self, name: N, new_name_prefix: &str) -> NodeHandle {
+        self.try_duplicate_named(name, new_name_prefix)
+            .expect("Failed to duplicate named item!")
+    }
+
+    /// Fallible version of [duplicate_named](#method.duplicate_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use,
+    /// `RendererError::NameAlreadyExists` when the name of one of the copies is already in use
+    /// and an error when the copy cannot be added to the document
+    pub fn try_duplicate_named<N: NameRef>(
+        &mut self,
+        name: N,
+        new_name_prefix: &str,
+    ) -> Result<NodeHandle, RendererError> {
+        let id_hash = self.get_id_hash(&name)?;
+
+        // All names are checked before anything is copied
+        for original in self.get_named_subtree(id_hash) {
+            let new_name = format!("{}{}", new_name_prefix, self.named_items[&original].name);
+            self.create_handle(&new_name[..])?;
+        }
+
+        // Queued items have to be in the document before they can be copied
+        self.apply_batch()?;
+
+        let item = &self.named_items[&id_hash];
+        let element = item.element.clone();
+        let parent_key = item.parent;
+        let z_index = item.z_index;
+
+        let mut copied = Copied {
+            names: Vec::new(),
+            references: Vec::new(),
+        };
+        let copy = self.copy_element(&element, parent_key, new_name_prefix, &mut copied)?;
+
+        match z_index {
+            Some(z_index) => self.place_layer(&copy, z_index)?,
+            None => {
+                let parent = match parent_key {
+                    Some(parent_key) => self.named_items[&parent_key].element.clone(),
+                    None => self.get_svg_root()?,
+                };
+                self.backend.append_child(&parent, &copy)?;
+            }
+        }
+
+        // The copy is only registered once it is completely within the document
+        for figure_key in copied.references {
+            self.add_reference(figure_key);
+        }
+        for (handle, parent, item) in copied.names {
+            self.add_name(&handle, parent, item);
+        }
+
+        let new_name = format!("{}{}", new_name_prefix, self.named_items[&id_hash].name);
+        self.get_handle(&new_name[..])
+            .ok_or(UnfindableName(new_name))
+    }
+
+    /// Will return if a certain name exists and therefore cannot be used for a declaration.
+    /// For a handle this will return whether the named item still exists.
+    ///
+    /// # Arguments
+    /// * 'name' - Name to check
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Will be set to false
+    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    ///
+    /// // Will be set to true
+    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    ///
+    /// // Delete the named figure
+    /// renderer.delete_named("named_circle");
+    ///
+    /// // Will be set to false
+    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    ///
+    /// // Renders the circle with "named_circle" name again.
+    /// // This would normally panic, since a name is redeclared,
+    /// // but since the named figure is deleted, it will not. :)
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    ///
+    /// // Will be set to true
+    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    /// ```
+    pub fn does_name_exist<N: NameRef>(&self, name: N) -> bool {
+        self.get_id_hash(&name).is_ok()
+    }
+
+    /// Creates a new named container in the parent
+    ///
+    /// # Arguments
+    /// * `name` - Name of the named container used for later reference
+    /// * `parent` - Name a container, which to use as parent ("root" is used for the SVG root)
+    ///
+    /// # Panics
+    /// Will panic when [try_create_named_container](#method.try_create_named_container) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.create_named_container("named_container", "root");
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.append_to_container("named_container", circle, (10.0, 10.0));
+    /// ```
+    pub fn create_named_container<P: NameRef>(&mut self, name: &str, parent: P) -> NodeHandle {
+        self.try_create_named_container(name, parent)
+            .expect("Failed to create named container!")
+    }
+
+    /// Fallible version of [create_named_container](#method.create_named_container)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use,
+    /// `RendererError::UnfindableName` or `RendererError::NamedNotContainer` when the parent is not a container
+    /// and an error when the container cannot be added to the document
+    pub fn try_create_named_container<P: NameRef>(
+        &mut self,
+        name: &str,
+        parent: P,
+    ) -> Result<NodeHandle, RendererError> {
+        let parent_key = self.get_item_key(&parent)?;
+        let parent = self.get_named_container(&parent)?;
+
+        let handle = self.create_handle(name)?;
+
+        let container = self.backend.create_element("g")?;
+        self.set_named_id(&container, &handle)?;
+
+        self.insert_child(parent_key, &parent, container.clone())?;
+        self.add_name(
+            &handle,
+            parent_key,
+            NamedItem::new_container(name, container),
+        );
+
+        Ok(handle)
+    }
+
+    /// Creates a layer, which is a named container within the root with a z-index.
+    /// A layer is always drawn above the layers with a lower z-index, no matter when it was created.
+    /// Layers with the same z-index are drawn in the order in which they were created.
+    /// Items which are rendered directly into the root are not part of a layer and are not moved.
+    ///
+    /// # Note
+    /// A layer can be used like any other named container,
+    /// e.g. it can be hidden with [hide_named](#method.hide_named)
+    /// and cleared with [clear_named_container](#method.clear_named_container).
+    ///
+    /// # Arguments
+    /// * `name` - The name of the layer
+    /// * `z_index` - The z-index of the layer, a higher z-index is drawn on top
+    ///
+    /// # Panics
+    /// Will panic when [try_create_layer](#method.try_create_layer) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.create_layer("ui", 10);
+    /// renderer.create_layer("background", 0);
+    /// renderer.create_layer("entities", 5);
+    ///
+    /// // The player is drawn above the background and below the ui
+    /// renderer.render_named_to_layer("entities", "player", SVGDefault::circle(5), (50.0, 50.0));
+    /// renderer.render_to_layer("background", SVGDefault::rect(100, 100), (0.0, 0.0));
+    /// ```
+    pub fn create_layer(&mut self, name: &str, z_index: i32) -> NodeHandle {
+        self.try_create_layer(name, z_index)
+            .expect("Failed to create layer!")
+    }
+
+    /// Fallible version of [create_layer](#method.create_layer)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the layer cannot be added to the document
+    pub fn try_create_layer(
+        &mut self,
+        name: &str,
+        z_index: i32,
+    ) -> Result<NodeHandle, RendererError> {
+        let handle = self.create_handle(name)?;
+
+        let layer = self.backend.create_element("g")?;
+        self.set_named_id(&layer, &handle)?;
+        self.backend
+            .set_attribute(&layer, Z_INDEX_ATTRIBUTE, &z_index.to_string()[..])?;
+
+        self.place_layer(&layer, z_index)?;
+
+        let mut item = NamedItem::new_container(name, layer);
+        item.z_index = Some(z_index);
+        self.add_name(&handle, None, item);
+
+        Ok(handle)
+    }
+
+    /// Changes the z-index of a layer, which moves the layer with all its items
+    ///
+    /// # Arguments
+    /// * `name` - The name of the layer
+    /// * `z_index` - The new z-index of the layer
+    ///
+    /// # Panics
+    /// Will panic when [try_set_layer_z_index](#method.try_set_layer_z_index) returns an error.
+    pub fn set_layer_z_index<N: NameRef>(&mut self, name: N, z_index: i32) {
+        self.try_set_layer_z_index(name, z_index)
+            .expect("Failed to set z-index of layer!");
+    }
+
+    /// Fallible version of [set_layer_z_index](#method.set_layer_z_index)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::NamedNotLayer` when the name is not used for a layer
+    pub fn try_set_layer_z_index<N: NameRef>(
+        &mut self,
+        name: N,
+        z_index: i32,
+    ) -> Result<(), RendererError> {
+        self.check_layer(&name)?;
+
+        let id_hash = self.get_id_hash(&name)?;
+        let layer = self.get_named_element(&name)?;
+
+        if let Some(item) = self.named_items.get_mut(&id_hash) {
+            item.z_index = Some(z_index);
+        }
+
+        self.backend
+            .set_attribute(&layer, Z_INDEX_ATTRIBUTE, &z_index.to_string()[..])?;
+        self.place_layer(&layer, z_index)
+    }
+
+    /// Will return whether a name is used for a layer
+    ///
+    /// # Arguments
+    /// * `name` - Name to check
+    pub fn is_layer<N: NameRef>(&self, name: N) -> bool {
+        self.check_layer(&name).is_ok()
+    }
+
+    /// Render figure within a layer
+    ///
+    /// # Arguments
+    /// * `layer` - The name of the layer
+    /// * `figure` - The figure to render
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_to_layer](#method.try_render_to_layer) returns an error.
+    pub fn render_to_layer<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        layer: N,
+        figure: SVGElem,
+        location: L,
+    ) {
+        self.try_render_to_layer(layer, figure, location)
+            .expect("Failed to render figure to layer!");
+    }
+
+    /// Fallible version of [render_to_layer](#method.render_to_layer)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use,
+    /// `RendererError::NamedNotLayer` when the name is not used for a layer
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_render_to_layer<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        layer: N,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<(), RendererError> {
+        self.check_layer(&layer)?;
+
+        self.try_append_to_container(layer, figure, location)
+    }
+
+    /// Render a named figure within a layer
+    ///
+    /// # Arguments
+    /// * `layer` - The name of the layer
+    /// * `name` - The name of the figure
+    /// * `figure` - The figure to render
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_to_layer](#method.try_render_named_to_layer) returns an error.
+    pub fn render_named_to_layer<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        layer: N,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+    ) -> NodeHandle {
+        self.try_render_named_to_layer(layer, name, figure, location)
+            .expect("Failed to render named figure to layer!")
+    }
+
+    /// Fallible version of [render_named_to_layer](#method.render_named_to_layer)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the layer is not in use,
+    /// `RendererError::NamedNotLayer` when the layer is not used for a layer,
+    /// `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_render_named_to_layer<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        layer: N,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+    ) -> Result<NodeHandle, RendererError> {
+        self.check_layer(&layer)?;
+
+        self.try_render_named_in(layer, name, figure, location)
+    }
+
+    /// Moves a named figure to a given location
+    ///
+    /// # Arguments
+    /// * `name` - Name of the named figure to move
+    /// * `loc` - Location or [Transform](../transform/struct.Transform.html) to move the figure to
+    ///
+    /// # Panics
+    /// Will panic when [try_move_named](#method.try_move_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
+    ///
+    /// // Render circle (since it's the first time of rendering this shape,
+    /// // the renderer will add the shape's definition)
+    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
+    ///
+    /// // --snip
+    ///
+    /// // Moves the named figure to a new location
+    /// renderer.move_named("named_circle", (5.0, 5.0));
+    /// ```
+    pub fn move_named<N: NameRef, L: Into<Transform>>(&mut self, name: N, loc: L) {
+        self.try_move_named(name, loc)
+            .expect("Failed to move named figure!");
+    }
+
+    /// Fallible version of [move_named](#method.move_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::NamedNotUse` when the name is used for a container
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    /// use wasm_svg_graphics::errors::RendererError;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// match renderer.try_move_named("not_in_use_name", (5.0, 5.0)) {
+    ///     Err(RendererError::UnfindableName(name)) => println!("No item named '{}'", name),
+    ///     _ => {}
+    /// }
+    /// ```
+    pub fn try_move_named<N: NameRef, L: Into<Transform>>(
+        &mut self,
+        name: N,
+        loc: L,
+    ) -> Result<(), RendererError> {
+        self.get_named_use(&name)?;
+
+        self.set_named_transform(&name, loc.into())
+    }
+
+    /// Sets the transform of a named figure or named container,
+    /// this replaces the location or transform the item was rendered with
+    ///
+    /// # Arguments
+    /// * `name` - Name of the named figure or container to transform
+    /// * `transform` - The new location or [Transform](../transform/struct.Transform.html) of the item
+    ///
+    /// # Panics
+    /// Will panic when [try_transform_named](#method.try_transform_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// renderer.render_named("named_rect", SVGDefault::rect(10, 10), (10.0, 10.0));
+    ///
+    /// // Rotates the rect around its center and moves it to (20, 20)
+    /// renderer.transform_named(
+    ///     "named_rect",
+    ///     Transform::rotate_around(45.0, (5.0, 5.0)).then(Transform::translate(20.0, 20.0)),
+    /// );
+    ///
+    /// // Containers can be transformed as well, which transforms all figures inside of it
+    /// renderer.create_named_container("container", "root");
+    /// renderer.transform_named("container", Transform::scale(2.0, 2.0));
+    /// ```
+    pub fn transform_named<N: NameRef, T: Into<Transform>>(&mut self, name: N, transform: T) {
+        self.try_transform_named(name, transform)
+            .expect("Failed to transform named item!");
+    }
+
+    /// Fallible version of [transform_named](#method.transform_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_transform_named<N: NameRef, T: Into<Transform>>(
+        &mut self,
+        name: N,
+        transform: T,
+    ) -> Result<(), RendererError> {
+        self.set_named_transform(&name, transform.into())
+    }
+
+    /// Sets the presentation overrides of a named figure or named container,
+    /// this replaces the overrides the item was rendered with
+    ///
+    /// # Arguments
+    /// * `name` - Name of the named figure or container to style
+    /// * `style` - The new presentation overrides, an empty [Style](../style/struct.Style.html) removes all overrides
+    ///
+    /// # Panics
+    /// Will panic when [try_style_named](#method.try_style_named) returns an error.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
+    ///
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// let circle = SVGDefault::inherit_style(SVGDefault::circle(10));
+    /// renderer.render_named("named_circle", circle, (20.0, 20.0));
+    ///
+    /// // Recolor only this circle
+    /// renderer.style_named("named_circle", Style::new().fill("#00ff00").opacity(0.5));
+    /// ```
+    pub fn style_named<N: NameRef>(&mut self, name: N, style: Style) {
+        self.try_style_named(name, style)
+            .expect("Failed to style named item!");
+    }
+
+    /// Fallible version of [style_named](#method.style_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_style_named<N: NameRef>(
+        &mut self,
+        name: N,
+        style: Style,
+    ) -> Result<(), RendererError> {
+        self.set_named_style(&name, style)
+    }
+
+    /// Moves a named item one place up, so it is drawn on top of the sibling which was directly above it
+    ///
+    /// # Arguments
+    /// * `name` - Name of the named figure or container to raise
+    ///
+    /// # Note
+    /// Only the order within the parent of the item is changed.
+    /// Layers stay ordered by their z-index, use [set_layer_z_index](#method.set_layer_z_index) to move them past other layers.
+    /// While batching, the queued changes are applied before the item is moved.
     ///
     /// # Panics
-    /// Cannot create duplicate names or declare a name more than once.
-    /// When an attempt is made to declare a name more than once, a panic will occur.
-    /// One is able to check if a name already exists with the [does_name_exist](#method.does_name_exist) method.
+    /// Will panic when [try_raise_named](#method.try_raise_named) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -477,76 +4943,103 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
-    ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
-    ///
-    /// // --snip
+    /// renderer.render_named("bottom", SVGDefault::circle(10), (10.0, 10.0));
+    /// renderer.render_named("top", SVGDefault::rect(10, 10), (10.0, 10.0));
     ///
-    /// // Updates the named figure's location to (20,20)
-    /// renderer.move_named("named_circle", (20.0, 20.0));
+    /// // The circle is now drawn on top of the rect
+    /// renderer.raise_named("bottom");
     /// ```
-    pub fn render_named(&mut self, name: &str, figure: SVGElem, location: Point2D) {
-        let figure_id = Self::get_id_of_figure(Self::get_hash(&figure));
+    pub fn raise_named<N: NameRef>(&mut self, name: N) {
+        self.try_raise_named(name)
+            .expect("Failed to raise named item!");
+    }
 
-        // If there is already a definition
-        if !self.contains_figure(&figure) {
-            // Add the definition to the dom and hashes
-            self.add_def(figure).expect("Failed to add definition!");
-        }
+    /// Fallible version of [raise_named](#method.raise_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::LayerOutOfOrder` when a layer would be moved past a layer with another z-index
+    pub fn try_raise_named<N: NameRef>(&mut self, name: N) -> Result<(), RendererError> {
+        let index = self.get_siblings(&name)?.index;
 
-        // Add named use of definition
-        self.add_named_use(name, &figure_id[..], location)
-            .expect("Failed to add named use!");
+        self.place_at_index(&name, index + 1)
     }
 
-    /// Render figure from a previously added definition at a location (this will automatically add a definition when needed)
+    /// Moves a named item one place down, so it is drawn below the sibling which was directly below it
     ///
     /// # Arguments
-    /// * `figure_id` - 8 byte hash of the figure used when adding to the dom
-    /// * `location` - the location where to add the `figure`
+    /// * `name` - Name of the named figure or container to lower
     ///
-    /// # Examples
+    /// # Panics
+    /// Will panic when [try_lower_named](#method.try_lower_named) returns an error.
+    pub fn lower_named<N: NameRef>(&mut self, name: N) {
+        self.try_lower_named(name)
+            .expect("Failed to lower named item!");
+    }
+
+    /// Fallible version of [lower_named](#method.lower_named)
     ///
-    /// ```rust,no_run
-    /// use wasm_svg_graphics::prelude::*;
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::LayerOutOfOrder` when a layer would be moved past a layer with another z-index
+    pub fn try_lower_named<N: NameRef>(&mut self, name: N) -> Result<(), RendererError> {
+        let index = self.get_siblings(&name)?.index;
+
+        self.place_at_index(&name, index.saturating_sub(1))
+    }
+
+    /// Moves a named item above all its siblings
     ///
-    /// // Declare renderer (must be mutable)
-    /// let mut renderer = SVGRenderer::new("svg_parent_id")
-    ///     .expect("Failed to create renderer!");
+    /// # Arguments
+    /// * `name` - Name of the named figure or container to move to the front
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
+    /// # Panics
+    /// Will panic when [try_bring_to_front](#method.try_bring_to_front) returns an error.
+    pub fn bring_to_front<N: NameRef>(&mut self, name: N) {
+        self.try_bring_to_front(name)
+            .expect("Failed to bring named item to front!");
+    }
+
+    /// Fallible version of [bring_to_front](#method.bring_to_front)
     ///
-    /// let circle_id = renderer.define_render(circle);
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::LayerOutOfOrder` when a layer would be moved past a layer with another z-index
+    pub fn try_bring_to_front<N: NameRef>(&mut self, name: N) -> Result<(), RendererError> {
+        self.place_at_index(&name, usize::MAX)
+    }
+
+    /// Moves a named item below all its siblings
     ///
-    /// // Render circle
-    /// renderer.render_id(circle_id, (20.0, 20.0));
-    /// ```
-    pub fn render_id(&mut self, figure_id: u64, location: Point2D) {
-        // If there is already a definition
-        if !self.contains_id(figure_id) {
-            // Add the definition to the dom and hashes
-            panic!("Definition doesn't exist");
-        }
+    /// # Arguments
+    /// * `name` - Name of the named figure or container to move to the back
+    ///
+    /// # Panics
+    /// Will panic when [try_send_to_back](#method.try_send_to_back) returns an error.
+    pub fn send_to_back<N: NameRef>(&mut self, name: N) {
+        self.try_send_to_back(name)
+            .expect("Failed to send named item to back!");
+    }
 
-        // Add use of definition
-        self.add_use(&Renderer::get_id_of_figure(figure_id)[..], location)
-            .expect("Failed to add use from id!");
+    /// Fallible version of [send_to_back](#method.send_to_back)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::LayerOutOfOrder` when a layer would be moved past a layer with another z-index
+    pub fn try_send_to_back<N: NameRef>(&mut self, name: N) -> Result<(), RendererError> {
+        self.place_at_index(&name, 0)
     }
 
-    /// Render named figure from a previously added definition at a location (this will automatically add a definition when needed)
+    /// Moves a named item directly below another named item with the same parent
     ///
     /// # Arguments
-    /// * `name` - Name to use for later reference
-    /// * `figure_id` - 8 byte hash of the figure used when adding to the dom
-    /// * `location` - the location where to add the `figure`
+    /// * `name` - Name of the named figure or container to move
+    /// * `sibling` - Name of the item which will be drawn directly on top of the moved item
     ///
-    /// # Examples
+    /// # Panics
+    /// Will panic when [try_insert_before](#method.try_insert_before) returns an error.
     ///
+    /// # Examples
     /// ```rust,no_run
     /// use wasm_svg_graphics::prelude::*;
     ///
@@ -554,38 +5047,74 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
-    ///
-    /// let circle_id = renderer.define_render(circle);
-    ///
-    /// // Render circle
-    /// renderer.render_named_id("named_circle", circle_id, (20.0, 20.0));
-    ///
-    /// // --snip
+    /// renderer.render_named("first", SVGDefault::circle(10), (10.0, 10.0));
+    /// renderer.render_named("second", SVGDefault::circle(10), (20.0, 10.0));
+    /// renderer.render_named("third", SVGDefault::circle(10), (30.0, 10.0));
     ///
-    /// // Updates the Circle's location
-    /// renderer.move_named("named_circle", (25.0, 25.0));
+    /// // The order is now first, third, second
+    /// renderer.insert_before("third", "second");
     /// ```
-    pub fn render_named_id(&mut self, name: &str, figure_id: u64, location: Point2D) {
-        // If there is already a definition
-        if !self.contains_id(figure_id) {
-            // Add the definition to the dom and hashes
-            panic!("Definition doesn't exist");
-        }
+    pub fn insert_before<N: NameRef, S: NameRef>(&mut self, name: N, sibling: S) {
+        self.try_insert_before(name, sibling)
+            .expect("Failed to insert named item before sibling!");
+    }
 
-        // Add use of definition
-        self.add_named_use(name, &Renderer::get_id_of_figure(figure_id)[..], location)
-            .expect("Failed to add named use from id!");
+    /// Fallible version of [insert_before](#method.insert_before)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when one of the names is not in use,
+    /// `RendererError::NamedNotSibling` when the items do not have the same parent
+    /// and `RendererError::LayerOutOfOrder` when a layer would be moved past a layer with another z-index
+    pub fn try_insert_before<N: NameRef, S: NameRef>(
+        &mut self,
+        name: N,
+        sibling: S,
+    ) -> Result<(), RendererError> {
+        let item = self.get_siblings(&name)?;
+        let reference = self.get_siblings(&sibling)?;
+
+        let id_hash = self.get_id_hash(&name)?;
+        let sibling_hash = self.get_id_hash(&sibling)?;
+
+        // Items within unnamed groups share the key of their nearest named container,
+        // so the sibling also has to be one of the other children of the same element
+        let parent_of = |id_hash| self.named_items.get(&id_hash).and_then(|item| item.parent);
+        let sibling_id = self.backend.get_attribute(&reference.element, "id");
+        let sibling_index = item
+            .others
+            .iter()
+            .position(|other| self.backend.get_attribute(other, "id") == sibling_id);
+
+        let index = match sibling_index {
+            Some(index) if parent_of(id_hash) == parent_of(sibling_hash) => index,
+            _ if id_hash == sibling_hash => return Ok(()),
+            _ => {
+                return Err(NamedNotSibling(
+                    String::from(name.name()),
+                    String::from(sibling.name()),
+                ))
+            }
+        };
+
+        self.check_layer_order(&name, &item, index)?;
+
+        self.backend
+            .insert_before(&item.parent, &item.element, &reference.element)
     }
 
-    /// Define a figure and return it's hash, this hash can later be used for rendering
+    /// Render named figure from a previously added definition at an index within the root,
+    /// index `0` is drawn below all other items and an index past the last item is drawn on top.
     ///
     /// # Arguments
-    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
+    /// * `name` - The name of the figure
+    /// * `figure_id` - The id of the figure, as returned by [define_render](#method.define_render)
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    /// * `index` - The index within the items of the root
     ///
-    /// # Examples
+    /// # Panics
+    /// Will panic when [try_render_named_id_at_index](#method.try_render_named_id_at_index) returns an error.
     ///
+    /// # Examples
     /// ```rust,no_run
     /// use wasm_svg_graphics::prelude::*;
     ///
@@ -593,29 +5122,100 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
+    /// let circle_id = renderer.define_render(SVGDefault::circle(10));
     ///
-    /// // Define the render
-    /// let circle_id = renderer.define_render(circle);
+    /// renderer.render_named_id("foreground", circle_id, (10.0, 10.0));
     ///
-    /// // Render circle
-    /// renderer.render_id(circle_id, (20.0, 20.0));
+    /// // The background is drawn below the foreground
+    /// renderer.render_named_id_at_index("background", circle_id, (10.0, 10.0), 0);
     /// ```
-    pub fn define_render(&mut self, figure: SVGElem) -> u64 {
-        let figure_hash = Self::get_hash(&figure);
+    pub fn render_named_id_at_index<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+        index: usize,
+    ) -> NodeHandle {
+        self.try_render_named_id_at_index(name, figure_id, location, index)
+            .expect("Failed to render named figure from id at index!")
+    }
 
-        // If there is already a definition
-        if !self.contains_figure(&figure) {
-            // Add the definition to the dom and hashes
-            self.add_def(figure).expect("Failed to add definition!");
-        }
+    /// Fallible version of [render_named_id_at_index](#method.render_named_id_at_index)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use,
+    /// `RendererError::UnfindableDefinition` when no definition exists for `figure_id`
+    /// and an error when the use cannot be added to the document
+    pub fn try_render_named_id_at_index<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure_id: FigureId,
+        location: L,
+        index: usize,
+    ) -> Result<NodeHandle, RendererError> {
+        let handle = self.try_render_named_id(name, figure_id, location)?;
 
-        figure_hash
+        self.place_at_index(&handle, index)?;
+
+        Ok(handle)
     }
 
-    /// Clears all elements within the SVG element and clears all internal definitions.
-    /// Basically reinits the renderer.
+    /// Render a named figure at an index within the root, see [render_named_id_at_index](#method.render_named_id_at_index)
+    ///
+    /// # Arguments
+    /// * `name` - The name of the figure
+    /// * `figure` - The figure to render
+    /// * `location` - the location or [Transform](../transform/struct.Transform.html) of the `figure`
+    /// * `index` - The index within the items of the root
+    ///
+    /// # Panics
+    /// Will panic when [try_render_named_at_index](#method.try_render_named_at_index) returns an error.
+    pub fn render_named_at_index<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+        index: usize,
+    ) -> NodeHandle {
+        self.try_render_named_at_index(name, figure, location, index)
+            .expect("Failed to render named figure at index!")
+    }
+
+    /// Fallible version of [render_named_at_index](#method.render_named_at_index)
+    ///
+    /// # Errors
+    /// Will return `RendererError::NameAlreadyExists` when the name is already in use
+    /// and an error when the definition or the use cannot be added to the document
+    pub fn try_render_named_at_index<L: Into<Transform>>(
+        &mut self,
+        name: &str,
+        figure: SVGElem,
+        location: L,
+        index: usize,
+    ) -> Result<NodeHandle, RendererError> {
+        self.with_definition(figure, |renderer, figure_id| {
+            renderer.try_render_named_id_at_index(name, figure_id, location, index)
+        })
+    }
+
+    /// Registers a handler for a pointer event on a named figure or container.
+    /// The handler receives the name of the item and the position of the pointer in the coordinates of the viewbox.
+    /// Events on the items within a container are passed on to the container,
+    /// except for [PointerEnter](../events/enum.PointerEventKind.html#variant.PointerEnter)
+    /// and [PointerLeave](../events/enum.PointerEventKind.html#variant.PointerLeave).
+    ///
+    /// # Arguments
+    /// * `name` - Name of the item
+    /// * `kind` - The kind of pointer event
+    /// * `handler` - The handler, which is called for every event
+    ///
+    /// # Note
+    /// The renderer owns the handlers, they are dropped when the item is deleted,
+    /// when the renderer is cleared or with [off_named](#method.off_named).
+    /// A copy made with [duplicate_named](#method.duplicate_named) does not have the handlers of the original.
+    ///
+    /// # Panics
+    /// Will panic when [try_on_named](#method.try_on_named) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -625,47 +5225,107 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
-    ///
-    /// // --snip
-    ///
-    /// // Reinit the Renderer object
-    /// renderer.clear();
+    /// renderer.render_named("button", SVGDefault::circle(10), (50.0, 50.0));
     ///
-    /// // Renders the circle with "named_circle" name again.
-    /// // This would normally panic, since a name is redeclared,
-    /// // but since the renderer is cleared, it will not. :)
-    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    /// renderer.on_named("button", PointerEventKind::Click, |event| {
+    ///     let (x, y) = event.position();
+    ///     println!("{} was clicked at ({}, {})", event.name(), x, y);
+    /// });
     /// ```
-    pub fn clear(&mut self) {
-        self.get_svg_root()
-            .expect("Can't find SVG Root")
-            .set_inner_html("");
+    pub fn on_named<N: NameRef, F: FnMut(&PointerEvent) + 'static>(
+        &mut self,
+        name: N,
+        kind: PointerEventKind,
+        handler: F,
+    ) {
+        self.try_on_named(name, kind, handler)
+            .expect("Failed to add handler to named item!");
+    }
 
-        self.get_svg_root()
-            .expect("Can't find SVG Root (2)")
-            .append_child(&crate::to_html(&SVGElem::new(Tag::Defs)))
-            .expect("Failed to append defs!");
+    /// Fallible version of [on_named](#method.on_named)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `DomError::UnlistenableEvent` when the listener cannot be added to the element
+    pub fn try_on_named<N: NameRef, F: FnMut(&PointerEvent) + 'static>(
+        &mut self,
+        name: N,
+        kind: PointerEventKind,
+        mut handler: F,
+    ) -> Result<(), RendererError> {
+        let id_hash = self.get_id_hash(&name)?;
+        let item = self.get_named(&name)?;
+        let element = item.element.clone();
+
+        let event_name = match self.event_listeners.get(&id_hash) {
+            Some(listeners) => listeners.name.clone(),
+            None => Rc::new(RefCell::new(item.name.clone())),
+        };
+        let handler_name = event_name.clone();
+
+        let listener = self.backend.add_listener(
+            &self.get_svg_root()?,
+            &element,
+            kind.event_type(),
+            Box::new(move |position, wheel_delta| {
+                handler(&PointerEvent {
+                    name: handler_name.borrow().clone(),
+                    kind,
+                    position,
+                    wheel_delta,
+                })
+            }),
+        )?;
+
+        self.event_listeners
+            .entry(id_hash)
+            .or_insert_with(|| Listeners {
+                name: event_name,
+                listeners: Vec::new(),
+            })
+            .listeners
+            .push((kind, listener));
 
-        self.figure_defs = BTreeSet::new();
-        self.name_defs = HashMap::new();
+        Ok(())
     }
 
-    /// Clears all figures/containers within a named container, but does not clear up definitions.
+    /// Removes and drops all handlers for a kind of pointer event of a named item
     ///
     /// # Arguments
-    /// * `container_name` - The name of the container to clear
-    ///
+    /// * `name` - Name of the item
+    /// * `kind` - The kind of pointer event
     ///
     /// # Panics
-    /// Will panic when a name passed in is in use by a pure figure.
-    /// For pure figures use [delete_named](#method.delete_named) or [hide_named](#method.hide_named) instead.
+    /// Will panic when [try_off_named](#method.try_off_named) returns an error.
+    pub fn off_named<N: NameRef>(&mut self, name: N, kind: PointerEventKind) {
+        self.try_off_named(name, kind)
+            .expect("Failed to remove handlers from named item!");
+    }
+
+    /// Fallible version of [off_named](#method.off_named)
     ///
-    /// # Note
-    /// Most of the time the [update_named](#method.update_named) method is a better alternative.
-    /// Also have a look at [hide_named](#method.hide_named) and [move_named](#method.move_named)
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_off_named<N: NameRef>(
+        &mut self,
+        name: N,
+        kind: PointerEventKind,
+    ) -> Result<(), RendererError> {
+        let id_hash = self.get_id_hash(&name)?;
+
+        if let Some(listeners) = self.event_listeners.get_mut(&id_hash) {
+            listeners
+                .listeners
+                .retain(|(listener_kind, _)| *listener_kind != kind);
+        }
+
+        Ok(())
+    }
+
+    /// Will return the handle of a named item, which can be used instead of its name
+    ///
+    /// # Arguments
+    /// * `name` - Name of the named item
     ///
     /// # Examples
     /// ```rust,no_run
@@ -675,41 +5335,30 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Adds the named container 'named_container' to the svg root
-    /// renderer.create_named_container("named_container", "root");
-    ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (10.0, 10.0));
-    ///
-    /// // Now the container contains the circle figure
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
     ///
     /// // --snip
     ///
-    /// // Clear the named container
-    /// renderer.clear_named_container("named_container");
-    ///
-    /// // Now the container is empty again
-    ///
-    /// // Render circle in the named_container.
-    /// // Since definitions were not cleared, it will use a previous definition.
-    /// // This saves some processing time in this case.
-    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (20.0, 20.0));
+    /// // Look up the name once
+    /// let circle = renderer.get_handle("named_circle").expect("Name doesn't exist!");
     ///
-    /// // Now the container contains the circle at a different position
+    /// for i in 0..10 {
+    ///     renderer.move_named(&circle, (i as f32, i as f32));
+    /// }
     /// ```
-    pub fn clear_named_container(&self, container_name: &str) {
-        self.get_named_container(container_name)
-            .expect("Failed to fetch named container!")
-            .set_inner_html("");
+    pub fn get_handle(&self, name: &str) -> Option<NodeHandle> {
+        let id_hash = *self.name_defs.get(name)?;
+
+        self.get_handle_of(id_hash)
     }
 
-    /// Updates a named container or figure to either contain the passed figure or become the passed figure, respectively.
+    /// Will return whether a given name is used for a named container, instead of a pure figure
     ///
     /// # Arguments
-    /// * `name` - The name of either a named container or a named figure
-    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
-    /// * `location` - the location where to add the `figure`
+    /// * `name` - Name to check
+    ///
+    /// # Note
+    /// Will output false if the name is not in use
     ///
     /// # Examples
     /// ```rust,no_run
@@ -719,56 +5368,25 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Adds the named container 'named_container' to the svg root
-    /// renderer.create_named_container("named_container", "root");
+    /// // Generate circle
+    /// let circle = SVGDefault::circle(10);
     ///
     /// // Render circle (since it's the first time of rendering this shape,
     /// // the renderer will add the shape's definition)
-    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (10.0, 10.0));
-    ///
-    /// // Now the container contains the circle figure
-    ///
-    /// // --snip
+    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
     ///
-    /// // Update the contents of the named container
-    /// renderer.update_named("named_container", SVGDefault::circle(10), (20.0, 20.0));
+    /// // Create a named container
+    /// renderer.create_named_container("named_container", "root");
     ///
-    /// // Now the container contains the circle at a different position
+    /// println!("{}", renderer.is_container("named_circle")); // false
+    /// println!("{}", renderer.is_container("named_container")); // true
+    /// println!("{}", renderer.is_container("not_in_use_name")); // false
     /// ```
-    pub fn update_named(&mut self, name: &str, figure: SVGElem, location: Point2D) {
-        let figure_id = Self::get_id_of_figure(Self::get_hash(&figure));
-
-        // If there is already a definition
-        if !self.contains_figure(&figure) {
-            // Add the definition to the dom and hashes
-            self.add_def(figure)
-                .expect("Failed to add named definition!");
-        }
-
-        let container = self.get_named_container(name);
-
-        if !container.is_err() {
-            // Delete all current elements in de container
-            self.clear_named_container(name);
-
-            // Add element to container
-            self.add_use_to(name, &figure_id, location)
-                .expect("Failed to add named use!");
-        } else {
-            // Adjust use element
-            self.adjust_use_to(name, &figure_id, location)
-                .expect("Failed to adjust use element!");
-        }
+    pub fn is_container<N: NameRef>(&self, name: N) -> bool {
+        self.get_named_container(&name).is_ok()
     }
 
-    /// Updates a named container or figure to either contain the passed figure
-    /// or become the passed figure from the id, respectively.
-    ///
-    /// # Arguments
-    /// * `name` - The name of either a named container or a named figure
-    /// * `figure_id` - id of Figure definition used when adding to the dom,
-    /// defined using [define_render](#method.define_render)
-    /// * `location` - the location where to add the `figure`
+    /// Will return all names in use, sorted alphabetically
     ///
     /// # Examples
     /// ```rust,no_run
@@ -778,49 +5396,25 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Adds the named container 'named_container' to the svg root
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
     /// renderer.create_named_container("named_container", "root");
     ///
-    /// let circle_id = renderer.define_render(SVGDefault::circle(10));
-    ///
-    /// // Render circle
-    /// renderer.append_to_container("named_container", SVGDefault::circle(10), (10.0, 10.0));
-    ///
-    /// // Now the container contains the circle figure
-    ///
-    /// // --snip
-    ///
-    /// // Update the contents of the named container
-    /// renderer.update_named_with_id("named_container", circle_id, (20.0, 20.0));
-    ///
-    /// // Now the container contains the circle at a different position
+    /// assert_eq!(renderer.names(), vec!["named_circle", "named_container"]);
     /// ```
-    pub fn update_named_with_id(&mut self, name: &str, figure_id: u64, location: Point2D) {
-        // If there is already a definition
-        if !self.contains_id(figure_id) {
-            panic!("No definition found!");
-        }
-
-        let container = self.get_named_container(name);
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.name_defs.keys().cloned().collect();
+        names.sort();
 
-        if !container.is_err() {
-            // Delete all current elements in de container
-            self.clear_named_container(name);
-
-            // Add element to container
-            self.add_use_to(name, &Renderer::get_id_of_figure(figure_id)[..], location)
-                .expect("Failed to add named use!");
-        } else {
-            // Adjust use element
-            self.adjust_use_to(name, &Renderer::get_id_of_figure(figure_id)[..], location)
-                .expect("Failed to adjust use element!");
-        }
+        names
     }
 
-    /// Hides a named item in the DOM, this can be undone by the [show_named](#method.show_named) method.
+    /// Will return the named container a named item is in, or `None` when the item is within the root
     ///
     /// # Arguments
-    /// * `name` - Name of item to hide
+    /// * `name` - Name of the item
+    ///
+    /// # Panics
+    /// Will panic when [try_get_parent](#method.try_get_parent) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -830,29 +5424,35 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
-    ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
-    ///
-    /// // --snip
+    /// renderer.create_named_container("car", "root");
+    /// renderer.render_named_in("car", "wheel", SVGDefault::circle(5), (0.0, 10.0));
     ///
-    /// // Hides the named figure
-    /// renderer.hide_named("named_circle");
+    /// assert_eq!(renderer.get_parent("wheel").unwrap().name(), "car");
+    /// assert_eq!(renderer.get_parent("car"), None);
     /// ```
-    pub fn hide_named(&self, name: &str) {
-        self.get_named_item(name)
-            .expect("Failed to fetch named item!")
-            .set_attribute("style", "display: none;")
-            .expect("Failed to set attribute of container!");
+    pub fn get_parent<N: NameRef>(&self, name: N) -> Option<NodeHandle> {
+        self.try_get_parent(name)
+            .expect("Failed to get parent of named item!")
+    }
+
+    /// Fallible version of [get_parent](#method.get_parent)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_get_parent<N: NameRef>(&self, name: N) -> Result<Option<NodeHandle>, RendererError> {
+        let parent = self.get_named(&name)?.parent;
+
+        Ok(parent.and_then(|parent| self.get_handle_of(parent)))
     }
 
-    /// Shows a named item in the DOM, this undoes the [hide_named](#method.hide_named) method.
+    /// Will return the named items directly within a named container, in the order in which they are drawn.
+    /// Items which are not named, e.g. added with [append_to_container](#method.append_to_container), are skipped.
     ///
     /// # Arguments
-    /// * `name` - Name of item to show
+    /// * `container_name` - The name of the named container, or "root" for the svg root
+    ///
+    /// # Panics
+    /// Will panic when [try_get_children](#method.try_get_children) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -862,37 +5462,66 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
+    /// renderer.create_named_container("car", "root");
+    /// renderer.render_named_in("car", "front_wheel", SVGDefault::circle(5), (30.0, 10.0));
+    /// renderer.render_named_in("car", "back_wheel", SVGDefault::circle(5), (0.0, 10.0));
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
+    /// for wheel in renderer.get_children("car") {
+    ///     renderer.hide_named(&wheel);
+    /// }
+    /// ```
+    pub fn get_children<N: NameRef>(&self, container_name: N) -> Vec<NodeHandle> {
+        self.try_get_children(container_name)
+            .expect("Failed to get children of named container!")
+    }
+
+    /// Fallible version of [get_children](#method.get_children)
     ///
-    /// // Hides the named figure
-    /// renderer.hide_named("named_circle");
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use,
+    /// `RendererError::NamedNotContainer` when the name is used for a pure figure
+    /// and an error when queued operations cannot be applied
+    pub fn try_get_children<N: NameRef>(
+        &self,
+        container_name: N,
+    ) -> Result<Vec<NodeHandle>, RendererError> {
+        self.get_named_container(&container_name)?;
+
+        Ok(self
+            .get_named_children(self.get_item_key(&container_name)?)?
+            .into_iter()
+            .filter_map(|id_hash| self.get_handle_of(id_hash))
+            .collect())
+    }
+
+    /// Will return the transform of a named item, as set by [move_named](#method.move_named),
+    /// [transform_named](#method.transform_named) or when it was rendered
     ///
-    /// // --snip
+    /// # Arguments
+    /// * `name` - Name of the item
     ///
-    /// // Show the named figure again
-    /// renderer.show_named("named_circle");
-    /// ```
-    pub fn show_named(&self, name: &str) {
-        self.get_named_item(name)
-            .expect("Failed to fetch named item!")
-            .remove_attribute("style")
-            .expect("Failed to set attribute of container!");
+    /// # Panics
+    /// Will panic when [try_get_transform](#method.try_get_transform) returns an error.
+    pub fn get_transform<N: NameRef>(&self, name: N) -> Transform {
+        self.try_get_transform(name)
+            .expect("Failed to get transform of named item!")
     }
 
-    /// Appends a figure to a named container
+    /// Fallible version of [get_transform](#method.get_transform)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_get_transform<N: NameRef>(&self, name: N) -> Result<Transform, RendererError> {
+        Ok(self.get_named(&name)?.transform.clone())
+    }
+
+    /// Will return the location of a named item, which is where its transform places the origin of the item
     ///
     /// # Arguments
-    /// * `name` - The name of either a named container
-    /// * `figure` - [Figure](../figures/struct.Figure.html) object, used when adding to the dom
-    /// * `location` - the location where to add the `figure`
+    /// * `name` - Name of the item
     ///
     /// # Panics
-    /// Will panic when a name passed in is in use by a pure figure.
+    /// Will panic when [try_get_location](#method.try_get_location) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -902,42 +5531,30 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
-    ///
-    /// // Adds the named container 'named_container' to the svg root
-    /// renderer.create_named_container("named_container", "root");
-    ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.append_to_container("named_container", circle, (10.0, 10.0));
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 20.0));
     ///
-    /// // Now the container contains the circle figure
+    /// assert_eq!(renderer.get_location("named_circle"), (10.0, 20.0));
     /// ```
-    pub fn append_to_container(&mut self, name: &str, figure: SVGElem, location: Point2D) {
-        let figure_id = Self::get_id_of_figure(Self::get_hash(&figure));
-
-        // If there is already a definition
-        if !self.contains_figure(&figure) {
-            // Add the definition to the dom and hashes
-            self.add_def(figure)
-                .expect("Failed to add named definition!");
-        }
+    pub fn get_location<N: NameRef>(&self, name: N) -> Point2D {
+        self.try_get_location(name)
+            .expect("Failed to get location of named item!")
+    }
 
-        self.add_use_to(name, &figure_id[..], location)
-            .expect("Failed to add figure to container!")
+    /// Fallible version of [get_location](#method.get_location)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_get_location<N: NameRef>(&self, name: N) -> Result<Point2D, RendererError> {
+        Ok(self.get_named(&name)?.transform.apply((0.0, 0.0)))
     }
 
-    /// Appends a figure from id to a named container
+    /// Will return the id of the figure definition used by a named figure
     ///
     /// # Arguments
-    /// * `name` - The name of either a named container
-    /// * `figure_id` - id of Figure definition used when adding to the dom,
-    /// defined using [define_render](#method.define_render)
-    /// * `location` - the location where to add the `figure`
+    /// * `name` - Name of the named figure
     ///
     /// # Panics
-    /// Will panic when a name passed in is in use by a pure figure.
+    /// Will panic when [try_get_figure_id](#method.try_get_figure_id) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -947,32 +5564,37 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Adds the named container 'named_container' to the svg root
-    /// renderer.create_named_container("named_container", "root");
-    ///
     /// let circle_id = renderer.define_render(SVGDefault::circle(10));
+    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 20.0));
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.append_to_container_with_id("named_container", circle_id, (10.0, 10.0));
-    ///
-    /// // Now the container contains the circle figure
+    /// // Equal figures share a definition
+    /// assert_eq!(renderer.get_figure_id("named_circle"), circle_id);
     /// ```
-    pub fn append_to_container_with_id(&mut self, name: &str, figure_id: u64, location: Point2D) {
-        // If there is already a definition
-        if !self.contains_id(figure_id) {
-            panic!("Definition not found!")
-        }
+    pub fn get_figure_id<N: NameRef>(&self, name: N) -> FigureId {
+        self.try_get_figure_id(name)
+            .expect("Failed to get figure id of named figure!")
+    }
 
-        self.add_use_to(name, &Renderer::get_id_of_figure(figure_id)[..], location)
-            .expect("Failed to add figure to container!")
+    /// Fallible version of [get_figure_id](#method.get_figure_id)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::NamedNotUse` when the name is used for a container
+    pub fn try_get_figure_id<N: NameRef>(&self, name: N) -> Result<FigureId, RendererError> {
+        match self.get_named(&name)?.figure {
+            Some(hash) => Ok(FigureId {
+                renderer: self.instance_id,
+                hash,
+            }),
+            None => Err(NamedNotUse(String::from(name.name()))),
+        }
     }
 
-    /// Deletes a named item from the DOM and from internal entries.
-    /// But will not delete definitions
+    /// Will return all named items, depth first in the order in which they are drawn,
+    /// so every named container is directly followed by the items within it
     ///
-    /// # Arguments
-    /// * `name` - Name of item to delete
+    /// # Panics
+    /// Will panic when [try_tree](#method.try_tree) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -982,90 +5604,150 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
-    ///
-    /// // --snip
-    ///
-    /// // Delete the named figure
-    /// renderer.delete_named("named_circle");
+    /// renderer.create_named_container("car", "root");
+    /// renderer.render_named_in("car", "wheel", SVGDefault::circle(5), (0.0, 10.0));
+    /// renderer.render_named("sun", SVGDefault::circle(20), (0.0, 0.0));
     ///
-    /// // Renders the circle with "named_circle" name again.
-    /// // This would normally panic, since a name is redeclared,
-    /// // but since the named figure is deleted, it will not. :)
-    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    /// // Prints an outline of the scene
+    /// for item in renderer.tree() {
+    ///     println!("{}{}", "  ".repeat(item.depth()), item.name());
+    /// }
     /// ```
-    pub fn delete_named(&mut self, name: &str) {
-        let container = self.get_named_container(name);
-
-        if !container.is_err() {
-            let container = container.unwrap();
+    pub fn tree(&self) -> Vec<TreeItem> {
+        self.try_tree().expect("Failed to get tree of named items!")
+    }
 
-            let parent = container
-                .parent_element()
-                .ok_or(NoParent)
-                .expect("No parent was found!");
+    /// Fallible version of [tree](#method.tree)
+    ///
+    /// # Errors
+    /// Will return an error when queued operations cannot be applied
+    pub fn try_tree(&self) -> Result<Vec<TreeItem>, RendererError> {
+        let mut tree = Vec::new();
 
-            parent
-                .remove_child(&container)
-                .map_err(|_| Dom(UnremoveableChild))
-                .expect("Failed to remove child!");
-        } else {
-            // Adjust use element
-            self.delete_use(name)
-                .expect("Failed to delete use element!");
+        for id_hash in self.get_named_children(None)? {
+            self.collect_tree(id_hash, 0, &mut tree)?;
         }
 
-        self.name_defs.remove(name);
+        Ok(tree)
     }
 
-    /// Will return if a certain name exists and therefore cannot be used for a declaration.
+    /// Adjusts the viewbox of the svg
     ///
     /// # Arguments
-    /// * 'name' - Name to check
+    /// * `x` - The top-left x-coordinate of the viewbox
+    /// * `y` - The top-left y-coordinate of the viewbox
+    /// * `width` - The width of the viewbox
+    /// * `height` - The height of the viewbox
+    ///
+    /// # Note
+    /// By default this is set to [DEFAULT_VIEWBOX](constant.DEFAULT_VIEWBOX.html).
+    ///
+    /// # Panics
+    /// Will panic when [try_adjust_viewbox](#method.try_adjust_viewbox) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
     /// use wasm_svg_graphics::prelude::*;
     ///
-    /// // Declare renderer (must be mutable)
-    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    /// // Declare renderer
+    /// let renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Will be set to false
-    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    /// // Adjust the viewbox
+    /// renderer.adjust_viewbox(0, 0, 50, 50);
+    /// ```
+    pub fn adjust_viewbox(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.try_adjust_viewbox(x, y, width, height)
+            .expect("Failed to set viewBox!");
+    }
+
+    /// Fallible version of [adjust_viewbox](#method.adjust_viewbox)
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    /// # Errors
+    /// Will return an error when the svg cannot be found or the attribute cannot be set
+    pub fn try_adjust_viewbox(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<(), RendererError> {
+        self.set_view_box(&self.get_svg_root()?, x, y, width, height)
+    }
+
+    /// Converts a position in client pixels, e.g. the position of a mouse event, into the coordinates of the viewbox.
+    /// This takes the viewbox, the `preserveAspectRatio` attribute and the size of the svg on the screen into account.
     ///
-    /// // Will be set to true
-    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    /// # Arguments
+    /// * `position` - The position relative to the top-left corner of the browser window
     ///
-    /// // Delete the named figure
-    /// renderer.delete_named("named_circle");
+    /// # Note
+    /// Headless the svg is positioned at `(0, 0)` and sized by its width and height attributes,
+    /// without those attributes client pixels and the coordinates of the viewbox are the same.
     ///
-    /// // Will be set to false
-    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    /// # Panics
+    /// Will panic when [try_client_to_viewbox](#method.try_client_to_viewbox) returns an error.
     ///
-    /// // Renders the circle with "named_circle" name again.
-    /// // This would normally panic, since a name is redeclared,
-    /// // but since the named figure is deleted, it will not. :)
-    /// renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    /// # Examples
+    /// ```rust,no_run
+    /// use wasm_svg_graphics::prelude::*;
     ///
-    /// // Will be set to true
-    /// let does_named_circle_exist = renderer.does_name_exist("named_circle");
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
+    ///     .expect("Failed to create renderer!");
+    ///
+    /// // Place a circle where the user clicked, i.e. at clientX 120 and clientY 45
+    /// let location = renderer.client_to_viewbox((120.0, 45.0));
+    /// renderer.render(SVGDefault::circle(5), location);
     /// ```
-    pub fn does_name_exist(&self, name: &str) -> bool {
-        self.name_defs.contains_key(name)
+    pub fn client_to_viewbox(&self, position: Point2D) -> Point2D {
+        self.try_client_to_viewbox(position)
+            .expect("Failed to convert client position!")
     }
 
-    /// Creates a new named container in the parent
+    /// Fallible version of [client_to_viewbox](#method.client_to_viewbox)
+    ///
+    /// # Errors
+    /// Will return `RendererError::SingularTransform` with "root" when the svg has no size on the screen
+    pub fn try_client_to_viewbox(&self, position: Point2D) -> Result<Point2D, RendererError> {
+        self.get_viewport_transform()?
+            .inverse()
+            .map(|transform| transform.apply(position))
+            .ok_or_else(|| SingularTransform(String::from(ROOT_NAME)))
+    }
+
+    /// Converts a position in the coordinates of the viewbox into client pixels,
+    /// the reverse of [client_to_viewbox](#method.client_to_viewbox)
     ///
     /// # Arguments
-    /// * `name` - Name of the named container used for later reference
-    /// * `parent` - Name a container, which to use as parent ("root" is used for the SVG root)
+    /// * `position` - The position in the coordinates of the viewbox
+    ///
+    /// # Panics
+    /// Will panic when [try_viewbox_to_client](#method.try_viewbox_to_client) returns an error.
+    pub fn viewbox_to_client(&self, position: Point2D) -> Point2D {
+        self.try_viewbox_to_client(position)
+            .expect("Failed to convert viewbox position!")
+    }
+
+    /// Fallible version of [viewbox_to_client](#method.viewbox_to_client)
+    ///
+    /// # Errors
+    /// Will return an error when the svg cannot be found
+    pub fn try_viewbox_to_client(&self, position: Point2D) -> Result<Point2D, RendererError> {
+        Ok(self.get_viewport_transform()?.apply(position))
+    }
+
+    /// Converts a position in client pixels into the coordinates within a named item,
+    /// which for a named container are the coordinates its items are placed in.
+    /// Besides everything [client_to_viewbox](#method.client_to_viewbox) takes into account,
+    /// this undoes the transform of the item and of all containers it is in.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the item, or "root" for the coordinates of the viewbox
+    /// * `position` - The position relative to the top-left corner of the browser window
+    ///
+    /// # Panics
+    /// Will panic when [try_client_to_local](#method.try_client_to_local) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -1075,40 +5757,80 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
-    ///
-    /// // Adds the named container 'named_container' to the svg root
-    /// renderer.create_named_container("named_container", "root");
+    /// renderer.create_named_container("map", "root");
+    /// renderer.transform_named("map", Transform::scale(2.0, 2.0));
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.append_to_container("named_container", circle, (10.0, 10.0));
+    /// // Place a marker within the map where the user clicked
+    /// let location = renderer.client_to_local("map", (120.0, 45.0));
+    /// renderer.render_named_in("map", "marker", SVGDefault::circle(5), location);
     /// ```
-    pub fn create_named_container(&mut self, name: &str, parent: &str) {
-        let parent = self
-            .get_named_container(parent)
-            .expect("Failed to fetch named container!");
+    pub fn client_to_local<N: NameRef>(&self, name: N, position: Point2D) -> Point2D {
+        self.try_client_to_local(name, position)
+            .expect("Failed to convert client position!")
+    }
 
-        let id_string = self
-            .create_id_string(name)
-            .expect("Unable to create id string");
+    /// Fallible version of [client_to_local](#method.client_to_local)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    /// and `RendererError::SingularTransform` when the svg has no size on the screen
+    /// or the named item or one of its containers is scaled by zero
+    pub fn try_client_to_local<N: NameRef>(
+        &self,
+        name: N,
+        position: Point2D,
+    ) -> Result<Point2D, RendererError> {
+        let position = self.try_client_to_viewbox(position)?;
+
+        self.get_local_transform(&name)?
+            .inverse()
+            .map(|transform| transform.apply(position))
+            .ok_or_else(|| SingularTransform(String::from(name.name())))
+    }
+
+    /// Converts a position in the coordinates within a named item into client pixels,
+    /// the reverse of [client_to_local](#method.client_to_local)
+    ///
+    /// # Arguments
+    /// * `name` - Name of the item, or "root" for the coordinates of the viewbox
+    /// * `position` - The position in the coordinates within the item
+    ///
+    /// # Panics
+    /// Will panic when [try_local_to_client](#method.try_local_to_client) returns an error.
+    pub fn local_to_client<N: NameRef>(&self, name: N, position: Point2D) -> Point2D {
+        self.try_local_to_client(name, position)
+            .expect("Failed to convert local position!")
+    }
 
-        let container = crate::create_element_ns("http://www.w3.org/2000/svg", "g")
-            .expect("Failed to create new named container!");
-        container.set_id(&id_string[..]);
+    /// Fallible version of [local_to_client](#method.local_to_client)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_local_to_client<N: NameRef>(
+        &self,
+        name: N,
+        position: Point2D,
+    ) -> Result<Point2D, RendererError> {
+        let position = self.get_local_transform(&name)?.apply(position);
 
-        parent
-            .append_child(&container)
-            .map_err(|_| Dom(UnappendableElement))
-            .expect("Failed to append named container to parent!");
+        self.try_viewbox_to_client(position)
     }
 
-    /// Moves a named figure to a given location
+    /// Will return the topmost named item at a position, using the geometry of the figures.
+    /// Hidden items are skipped, as are the fills and strokes which are `none`.
+    /// An item which sets `visibility: visible;` itself is hit within a container hidden with its visibility.
+    /// A figure which is not named, e.g. added with [append_to_container](#method.append_to_container),
+    /// counts as the named container it is in.
     ///
     /// # Arguments
-    /// * `name` - Name of the named figure to move
-    /// * `loc` - Location to move the figure to
+    /// * `position` - The position in the coordinates of the viewbox
+    ///
+    /// # Note
+    /// Like pointer events in a browser, transparent fills and strokes are hit as well.
+    /// Curves are approximated by straight lines and text is never hit.
+    ///
+    /// # Panics
+    /// Will panic when [try_hit_test](#method.try_hit_test) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -1118,47 +5840,79 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
+    /// renderer.render_named("background", SVGDefault::rect(100, 100), (0.0, 0.0));
+    /// renderer.render_named("button", SVGDefault::circle(10), (50.0, 50.0));
     ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
+    /// // Select the item the user clicked on
+    /// let position = renderer.client_to_viewbox((120.0, 45.0));
+    /// if let Some(item) = renderer.hit_test(position) {
+    ///     println!("Selected {}", item.name());
+    /// }
+    /// ```
+    pub fn hit_test(&self, position: Point2D) -> Option<NodeHandle> {
+        self.try_hit_test(position)
+            .expect("Failed to hit test named items!")
+    }
+
+    /// Fallible version of [hit_test](#method.hit_test)
     ///
-    /// // --snip
+    /// # Errors
+    /// Will return an error when queued operations cannot be applied
+    pub fn try_hit_test(&self, position: Point2D) -> Result<Option<NodeHandle>, RendererError> {
+        Ok(self.try_hit_test_all(position)?.into_iter().next())
+    }
+
+    /// Will return all named items at a position, the topmost item first.
+    /// As with [hit_test](#method.hit_test) a hit figure counts as the innermost named item it belongs to,
+    /// the containers of that item can be found with [get_parent](#method.get_parent).
     ///
-    /// // Moves the named figure to a new location
-    /// renderer.move_named("named_circle", (5.0, 5.0));
-    /// ```
-    pub fn move_named(&mut self, name: &str, loc: Point2D) {
-        if !self.does_name_exist(name) {
-            panic!("Failed to move named figure: Name doesn't exist!");
-        }
+    /// # Arguments
+    /// * `position` - The position in the coordinates of the viewbox
+    ///
+    /// # Panics
+    /// Will panic when [try_hit_test_all](#method.try_hit_test_all) returns an error.
+    pub fn hit_test_all(&self, position: Point2D) -> Vec<NodeHandle> {
+        self.try_hit_test_all(position)
+            .expect("Failed to hit test named items!")
+    }
 
-        if self.is_container(name) {
-            panic!("Failed to move named figure: Name is used for a container!")
+    /// Fallible version of [hit_test_all](#method.hit_test_all)
+    ///
+    /// # Errors
+    /// Will return an error when queued operations cannot be applied
+    pub fn try_hit_test_all(&self, position: Point2D) -> Result<Vec<NodeHandle>, RendererError> {
+        // The geometry is read from the document, so queued changes have to be applied first
+        self.apply_batch()?;
+
+        let mut hits_within = Vec::new();
+        self.collect_hits(
+            &self.get_svg_root()?,
+            &Transform::identity(),
+            &Presentation::default(),
+            None,
+            position,
+            &mut hits_within,
+        );
+
+        let mut handles: Vec<NodeHandle> = Vec::new();
+        for id_hash in hits_within.into_iter().rev() {
+            if handles.iter().all(|handle| handle.id_hash != id_hash) {
+                handles.extend(self.get_handle_of(id_hash));
+            }
         }
 
-        let element = super::get_document()
-            .expect("Document failed")
-            .get_element_by_id(&Renderer::get_id_of_named(self.name_defs.get(name).unwrap())[..])
-            .unwrap();
-
-        element
-            .set_attribute("x", &format!("{:.2}", loc.0)[..])
-            .unwrap();
-        element
-            .set_attribute("y", &format!("{:.2}", loc.1)[..])
-            .unwrap();
+        Ok(handles)
     }
 
-    /// Will return whether a given name is used for a named container, instead of a pure figure
+    /// Will return the bounding box of a named figure in the coordinates of the viewbox,
+    /// including its transform and the transforms of the containers it is in.
+    /// Named containers do not have a bounding box.
     ///
     /// # Arguments
-    /// * `name` - Name to check
+    /// * `name` - Name of the item
     ///
-    /// # Note
-    /// Will output false if the name is not in use
+    /// # Panics
+    /// Will panic when [try_get_bounding_box](#method.try_get_bounding_box) returns an error.
     ///
     /// # Examples
     /// ```rust,no_run
@@ -1168,56 +5922,151 @@ impl Renderer {
     /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Generate circle
-    /// let circle = SVGDefault::circle(10);
-    ///
-    /// // Render circle (since it's the first time of rendering this shape,
-    /// // the renderer will add the shape's definition)
-    /// renderer.render_named("named_circle", circle, (10.0, 10.0));
-    ///
-    /// // Create a named container
-    /// renderer.create_named_container("named_container", "root");
+    /// renderer.render_named("named_rect", SVGDefault::rect(10, 20), (5.0, 5.0));
     ///
-    /// println!("{}", renderer.is_container("named_circle")); // false
-    /// println!("{}", renderer.is_container("named_container")); // true
-    /// println!("{}", renderer.is_container("not_in_use_name")); // false
+    /// let bounds = renderer.get_bounding_box("named_rect").unwrap();
+    /// assert_eq!(bounds.min(), (4.5, 4.5));
     /// ```
-    pub fn is_container(&self, name: &str) -> bool {
-        self.get_named_container(name).is_ok()
+    pub fn get_bounding_box<N: NameRef>(&self, name: N) -> Option<BoundingBox> {
+        self.try_get_bounding_box(name)
+            .expect("Failed to get bounding box of named item!")
     }
 
-    /// Adjusts the viewbox of the svg
+    /// Fallible version of [get_bounding_box](#method.get_bounding_box)
+    ///
+    /// # Errors
+    /// Will return `RendererError::UnfindableName` when the name is not in use
+    pub fn try_get_bounding_box<N: NameRef>(
+        &self,
+        name: N,
+    ) -> Result<Option<BoundingBox>, RendererError> {
+        self.get_named(&name)?;
+
+        Ok(self.spatial_index.get(self.get_id_hash(&name)?))
+    }
+
+    /// Will return the named figures with a bounding box which intersects an area, sorted by name.
+    /// The renderer keeps the bounding boxes in a spatial index,
+    /// so only the figures around the area are considered.
     ///
     /// # Arguments
-    /// * `x` - The top-left x-coordinate of the viewbox
-    /// * `y` - The top-left y-coordinate of the viewbox
-    /// * `width` - The width of the viewbox
-    /// * `height` - The height of the viewbox
+    /// * `area` - The area in the coordinates of the viewbox
     ///
     /// # Note
-    /// By default this is set to [DEFAULT_VIEWBOX](constant.DEFAULT_VIEWBOX.html).
+    /// Hidden figures are included, see [is_visible](#method.is_visible).
+    /// Figures which are not named, e.g. added with [append_to_container](#method.append_to_container),
+    /// are not in the index and thus never returned, not even as the container they are in.
     ///
     /// # Examples
     /// ```rust,no_run
     /// use wasm_svg_graphics::prelude::*;
     ///
-    /// // Declare renderer
-    /// let renderer = SVGRenderer::new("svg_parent_id")
+    /// // Declare renderer (must be mutable)
+    /// let mut renderer = SVGRenderer::new("svg_parent_id")
     ///     .expect("Failed to create renderer!");
     ///
-    /// // Adjust the viewbox
-    /// renderer.adjust_viewbox(0, 0, 50, 50);
+    /// for index in 0..10000 {
+    ///     let location = ((index % 100) as f32 * 10.0, (index / 100) as f32 * 10.0);
+    ///     renderer.render_named(&format!("marker_{}", index), SVGDefault::circle(2), location);
+    /// }
+    ///
+    /// // Only show the markers within the viewbox
+    /// let visible = renderer.query_area(BoundingBox::from_rect(0.0, 0.0, 100.0, 100.0));
     /// ```
-    pub fn adjust_viewbox(&self, x: i32, y: i32, width: i32, height: i32) {
-        Renderer::set_view_box(
-            &self
-                .get_svg_root()
-                .expect("Failed to retrieve SVG container!"),
-            x,
-            y,
-            width,
-            height,
-        )
-        .expect("Failed to set viewBox!");
+    pub fn query_area(&self, area: BoundingBox) -> Vec<NodeHandle> {
+        self.get_handles_of(self.spatial_index.query(&area))
+    }
+
+    /// Will return the named figures with a bounding box within a distance of a point, sorted by name.
+    /// See [query_area](#method.query_area).
+    ///
+    /// # Arguments
+    /// * `center` - The point in the coordinates of the viewbox
+    /// * `radius` - The largest distance between the point and the bounding box of a figure
+    pub fn query_radius(&self, center: Point2D, radius: f32) -> Vec<NodeHandle> {
+        let area = BoundingBox::from_point(center).expand(radius, radius);
+
+        let within = self
+            .spatial_index
+            .query(&area)
+            .into_iter()
+            .filter(|id_hash| {
+                self.spatial_index.get(*id_hash).is_some_and(|bounds| {
+                    // The distance to the closest point of the bounding box
+                    let dx = (bounds.x() - center.0)
+                        .max(center.0 - bounds.max().0)
+                        .max(0.0);
+                    let dy = (bounds.y() - center.1)
+                        .max(center.1 - bounds.max().1)
+                        .max(0.0);
+
+                    dx * dx + dy * dy <= radius * radius
+                })
+            })
+            .collect();
+
+        self.get_handles_of(within)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default as SVGDefault;
+
+    #[test]
+    fn colliding_definitions() {
+        let mut renderer = Renderer::<HeadlessBackend>::new_headless().unwrap();
+        let (first, second) = (SVGDefault::circle(5), SVGDefault::rect(5, 5));
+        let canonical = |figure: &SVGElem| CanonicalFigure::from_figure(figure);
+
+        // Both figures are forced onto the same hash
+        let hash = canonical(&first).get_hash();
+        let first_key = renderer
+            .add_def(hash, &first, canonical(&first), None)
+            .unwrap();
+        let second_key = renderer
+            .add_def(hash, &second, canonical(&second), None)
+            .unwrap();
+        assert_eq!(first_key, hash);
+        assert_eq!(second_key, hash.wrapping_add(1));
+
+        assert_eq!(
+            renderer.find_figure(hash, &canonical(&first)),
+            Some(first_key)
+        );
+        assert_eq!(
+            renderer.find_figure(hash, &canonical(&second)),
+            Some(second_key)
+        );
+        assert_eq!(
+            renderer.find_figure(hash, &canonical(&SVGDefault::circle(6))),
+            None
+        );
+
+        // Collecting the first definition keeps the second one findable
+        renderer.add_reference(second_key);
+        assert_eq!(renderer.collect_garbage(), 1);
+        assert_eq!(renderer.find_figure(hash, &canonical(&first)), None);
+        assert_eq!(
+            renderer.find_figure(hash, &canonical(&second)),
+            Some(second_key)
+        );
+
+        // The first definition is added again in the free place at the hash
+        let readded_key = renderer
+            .add_def(hash, &first, canonical(&first), None)
+            .unwrap();
+        assert_eq!(readded_key, hash);
+        assert_eq!(
+            renderer.find_figure(hash, &canonical(&second)),
+            Some(second_key)
+        );
+        assert_eq!(renderer.figure_defs.len(), 2);
+
+        // Removing the last definition of a hash removes the hash
+        renderer.remove_reference(second_key).unwrap();
+        assert_eq!(renderer.collect_garbage(), 2);
+        assert!(renderer.figure_keys.is_empty());
     }
 }
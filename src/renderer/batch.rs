@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use crate::backend::Backend;
+use crate::errors::RendererError;
+
+/// Key of an element within a batch, either the id hash of a named item or `None` for the svg root
+pub(super) type ElementKey = Option<u64>;
+
+/// Pending attribute changes of a single element, where `None` removes the attribute
+type PendingAttributes<E> = (E, Vec<(String, Option<String>)>);
+
+/// Operations which are queued while batching, until they are flushed
+pub(super) struct Batch<E> {
+    /// Pending attribute changes per element
+    attributes: HashMap<ElementKey, PendingAttributes<E>>,
+
+    /// Pending children per parent, in order of insertion
+    inserts: Vec<(ElementKey, E, Vec<E>)>,
+
+    /// Whether a flush has already been scheduled
+    pub(super) scheduled: bool,
+}
+
+impl<E: Clone> Batch<E> {
+    pub(super) fn new() -> Batch<E> {
+        Batch {
+            attributes: HashMap::new(),
+            inserts: Vec::new(),
+            scheduled: false,
+        }
+    }
+
+    /// Queues an attribute change, overwriting an earlier change of the same attribute
+    pub(super) fn set_attribute(
+        &mut self,
+        key: ElementKey,
+        element: &E,
+        name: &str,
+        value: Option<&str>,
+    ) {
+        let (_, attributes) = self
+            .attributes
+            .entry(key)
+            .or_insert_with(|| (element.clone(), Vec::new()));
+
+        let value = value.map(String::from);
+        match attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old_value)) => *old_value = value,
+            None => attributes.push((String::from(name), value)),
+        }
+    }
+
+    /// Will return the queued value of an attribute, `Some(None)` when its removal is queued
+    pub(super) fn get_attribute(&self, key: ElementKey, name: &str) -> Option<Option<String>> {
+        self.attributes.get(&key).and_then(|(_, attributes)| {
+            attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        })
+    }
+
+    /// Takes all queued operations out of the batch, leaving it empty
+    pub(super) fn take(&mut self) -> Batch<E> {
+        Batch {
+            attributes: std::mem::take(&mut self.attributes),
+            inserts: std::mem::take(&mut self.inserts),
+            scheduled: false,
+        }
+    }
+
+    /// Queues the operations of a batch taken earlier again, before the operations queued since
+    pub(super) fn restore(&mut self, earlier: Batch<E>) {
+        let later = self.take();
+        let scheduled = self.scheduled;
+        *self = earlier;
+        self.scheduled = scheduled;
+
+        for (key, (element, attributes)) in later.attributes {
+            for (name, value) in attributes {
+                self.set_attribute(key, &element, &name[..], value.as_deref());
+            }
+        }
+
+        for (key, parent, children) in later.inserts {
+            for child in children {
+                self.append_child(key, &parent, child);
+            }
+        }
+    }
+
+    /// Queues a child to be appended to a parent
+    pub(super) fn append_child(&mut self, key: ElementKey, parent: &E, child: E) {
+        match self
+            .inserts
+            .iter_mut()
+            .find(|(parent_key, _, _)| *parent_key == key)
+        {
+            Some((_, _, children)) => children.push(child),
+            None => self.inserts.push((key, parent.clone(), vec![child])),
+        }
+    }
+
+    /// Applies all queued operations, children are appended before the attributes are changed.
+    /// Applied operations are removed from the batch, so after an error only the unapplied operations are left
+    pub(super) fn apply<B: Backend<Element = E>>(
+        &mut self,
+        backend: &B,
+    ) -> Result<(), RendererError> {
+        let mut applied = 0;
+        let result = self.inserts.iter().try_for_each(|(_, parent, children)| {
+            backend.append_children(parent, &children[..])?;
+            applied += 1;
+            Ok(())
+        });
+        self.inserts.drain(..applied);
+        result?;
+
+        let mut result = Ok(());
+        self.attributes.retain(|_, (element, attributes)| {
+            if result.is_err() {
+                return true;
+            }
+
+            let mut applied = 0;
+            result = attributes.iter().try_for_each(|(name, value)| {
+                match value {
+                    Some(value) => backend.set_attribute(element, name, value)?,
+                    None => backend.remove_attribute(element, name)?,
+                }
+                applied += 1;
+                Ok(())
+            });
+            attributes.drain(..applied);
+
+            !attributes.is_empty()
+        });
+
+        result
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use svg_definitions::Point2D;
+
+use crate::bounds::BoundingBox;
+
+/// The deepest level of the tree, which keeps tiny items from creating long chains of nodes
+const MAX_DEPTH: usize = 16;
+
+/// The size of the root before any item is added, the same as the default viewbox
+const INITIAL_SIZE: f32 = 100.0;
+
+/// A node of the quadtree, which holds the items which fit within its loose bounds but not within a child
+struct Node {
+    items: Vec<(u64, BoundingBox)>,
+    children: Option<Box<[Node; 4]>>,
+}
+
+impl Node {
+    fn new() -> Node {
+        Node {
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.children.is_none()
+    }
+}
+
+/// Loose quadtree over the bounding boxes of named items, by their id hash
+///
+/// Every item is placed in the deepest cell which is at least as large as the item and contains its center.
+/// Since the bounds of a cell are loosened by half its size on every side, such a cell always encloses the item,
+/// so the place of an item only depends on its bounding box.
+/// The root grows when an item is added outside of it.
+pub(super) struct SpatialIndex {
+    /// The top-left corner of the cell of the root
+    origin: Point2D,
+
+    /// The width and height of the cell of the root
+    size: f32,
+    root: Node,
+
+    /// The bounding box of every item, used to find an item when it is moved or removed
+    boxes: HashMap<u64, BoundingBox>,
+}
+
+impl SpatialIndex {
+    pub(super) fn new() -> SpatialIndex {
+        SpatialIndex {
+            origin: (0.0, 0.0),
+            size: INITIAL_SIZE,
+            root: Node::new(),
+            boxes: HashMap::new(),
+        }
+    }
+
+    /// Adds an item or moves it to another bounding box
+    pub(super) fn insert(&mut self, id_hash: u64, bounds: BoundingBox) {
+        if self.boxes.get(&id_hash) == Some(&bounds) {
+            return;
+        }
+
+        self.remove(id_hash);
+
+        let finite = [bounds.x(), bounds.y(), bounds.width(), bounds.height()]
+            .iter()
+            .all(|value| value.is_finite());
+        if !finite {
+            return;
+        }
+
+        while !self.fits_root(&bounds) {
+            self.grow(bounds.center());
+        }
+
+        let (origin, size) = (self.origin, self.size);
+        let depth = depth_of(&bounds, size);
+
+        let mut node = &mut self.root;
+        let mut cell = (origin, size);
+        for _ in 0..depth {
+            let (index, child_cell) = child_containing(cell, bounds.center());
+            node = &mut node.children.get_or_insert_with(|| {
+                Box::new([Node::new(), Node::new(), Node::new(), Node::new()])
+            })[index];
+            cell = child_cell;
+        }
+
+        node.items.push((id_hash, bounds));
+        self.boxes.insert(id_hash, bounds);
+    }
+
+    /// Removes an item, nodes which become empty are removed as well
+    pub(super) fn remove(&mut self, id_hash: u64) {
+        if let Some(bounds) = self.boxes.remove(&id_hash) {
+            remove_from(
+                &mut self.root,
+                (self.origin, self.size),
+                id_hash,
+                bounds.center(),
+            );
+        }
+    }
+
+    /// Will return the bounding box of an item
+    pub(super) fn get(&self, id_hash: u64) -> Option<BoundingBox> {
+        self.boxes.get(&id_hash).cloned()
+    }
+
+    /// Removes all items
+    pub(super) fn clear(&mut self) {
+        *self = SpatialIndex::new();
+    }
+
+    /// Will return the id hashes of the items with a bounding box which intersects an area
+    pub(super) fn query(&self, area: &BoundingBox) -> Vec<u64> {
+        let mut found = Vec::new();
+        query_in(&self.root, (self.origin, self.size), area, &mut found);
+
+        found
+    }
+
+    /// Will return whether the loose bounds of the root enclose a bounding box
+    fn fits_root(&self, bounds: &BoundingBox) -> bool {
+        bounds.width() <= self.size
+            && bounds.height() <= self.size
+            && cell_contains((self.origin, self.size), bounds.center())
+    }
+
+    /// Doubles the size of the root towards a point, the old root becomes one of the quadrants
+    fn grow(&mut self, towards: Point2D) {
+        let left = towards.0 < self.origin.0;
+        let up = towards.1 < self.origin.1;
+
+        let origin = (
+            if left {
+                self.origin.0 - self.size
+            } else {
+                self.origin.0
+            },
+            if up {
+                self.origin.1 - self.size
+            } else {
+                self.origin.1
+            },
+        );
+
+        let old_root = std::mem::replace(&mut self.root, Node::new());
+        if !old_root.is_empty() {
+            let mut children = Box::new([Node::new(), Node::new(), Node::new(), Node::new()]);
+            children[quadrant(left, up)] = old_root;
+            self.root.children = Some(children);
+        }
+
+        self.origin = origin;
+        self.size *= 2.0;
+    }
+}
+
+/// Will return the index of the quadrant in which the old root ends up when growing
+fn quadrant(grown_left: bool, grown_up: bool) -> usize {
+    // The old root is on the right when the root grew to the left
+    (grown_left as usize) + 2 * (grown_up as usize)
+}
+
+/// Will return the depth at which the cells are still at least as large as a bounding box
+fn depth_of(bounds: &BoundingBox, root_size: f32) -> usize {
+    let extent = bounds.width().max(bounds.height());
+    let mut depth = 0;
+    let mut size = root_size / 2.0;
+
+    while depth < MAX_DEPTH && extent <= size {
+        depth += 1;
+        size /= 2.0;
+    }
+
+    depth
+}
+
+/// Will return whether a point is within a cell, where the right and bottom edges belong to the next cell
+fn cell_contains((origin, size): (Point2D, f32), point: Point2D) -> bool {
+    point.0 >= origin.0
+        && point.0 < origin.0 + size
+        && point.1 >= origin.1
+        && point.1 < origin.1 + size
+}
+
+/// Will return the index and cell of the child which contains a point
+fn child_containing((origin, size): (Point2D, f32), point: Point2D) -> (usize, (Point2D, f32)) {
+    let half = size / 2.0;
+    let right = point.0 >= origin.0 + half;
+    let down = point.1 >= origin.1 + half;
+
+    let child_origin = (
+        if right { origin.0 + half } else { origin.0 },
+        if down { origin.1 + half } else { origin.1 },
+    );
+
+    ((right as usize) + 2 * (down as usize), (child_origin, half))
+}
+
+/// Will return the bounds of a cell, loosened by half its size on every side
+fn loose_bounds((origin, size): (Point2D, f32)) -> BoundingBox {
+    BoundingBox::from_rect(origin.0, origin.1, size, size).expand(size / 2.0, size / 2.0)
+}
+
+/// Removes an item from the node on the path towards its center which holds it
+fn remove_from(node: &mut Node, cell: (Point2D, f32), id_hash: u64, center: Point2D) {
+    let count = node.items.len();
+    node.items.retain(|(item, _)| *item != id_hash);
+    if node.items.len() < count {
+        return;
+    }
+
+    let (index, child_cell) = child_containing(cell, center);
+    if let Some(children) = node.children.as_mut() {
+        remove_from(&mut children[index], child_cell, id_hash, center);
+
+        if children.iter().all(Node::is_empty) {
+            node.children = None;
+        }
+    }
+}
+
+fn query_in(node: &Node, cell: (Point2D, f32), area: &BoundingBox, found: &mut Vec<u64>) {
+    if !loose_bounds(cell).intersects(area) {
+        return;
+    }
+
+    found.extend(
+        node.items
+            .iter()
+            .filter(|(_, bounds)| bounds.intersects(area))
+            .map(|(id_hash, _)| *id_hash),
+    );
+
+    if let Some(children) = node.children.as_ref() {
+        let half = cell.1 / 2.0;
+
+        for (index, child) in children.iter().enumerate() {
+            let origin = (
+                cell.0 .0 + half * (index % 2) as f32,
+                cell.0 .1 + half * (index / 2) as f32,
+            );
+
+            query_in(child, (origin, half), area, found);
+        }
+    }
+}
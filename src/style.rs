@@ -0,0 +1,188 @@
+//! Presentation overrides for a single use of a figure
+
+/// Presentation of a single use of a figure, which overrides the presentation of the definition
+///
+/// Since equal figures share one definition, a style is the way to give a single use a different color.
+/// The style is set on the `<use>` element, both as presentation attributes and as the CSS variables
+/// `--fill`, `--stroke` and `--stroke-width`.
+/// A figure inherits the presentation attributes only where it does not set them itself,
+/// figures made with [inherit_style](../default/fn.inherit_style.html) use the CSS variables instead.
+///
+/// # Examples
+/// ```rust,no_run
+/// use wasm_svg_graphics::prelude::*;
+///
+/// // Declare renderer (must be mutable)
+/// let mut renderer = SVGRenderer::new("svg_parent_id")
+///     .expect("Failed to create renderer!");
+///
+/// // Both circles share the same definition
+/// let circle = SVGDefault::inherit_style(SVGDefault::circle(10));
+///
+/// renderer.render_styled(circle.clone(), (20.0, 20.0), Style::new().stroke("#ff0000"));
+/// renderer.render_styled(circle, (50.0, 20.0), Style::new().stroke("#0000ff").stroke_width(2.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Style {
+    fill: Option<String>,
+    stroke: Option<String>,
+    stroke_width: Option<f32>,
+    opacity: Option<f32>,
+    class: Option<String>,
+}
+
+/// The presentation attributes which are also set as CSS variable
+pub(crate) const VARIABLE_ATTRIBUTES: [&str; 3] = ["fill", "stroke", "stroke-width"];
+
+impl Style {
+    /// Creates a style without any overrides
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Sets the fill color
+    pub fn fill(mut self, fill: &str) -> Style {
+        self.fill = Some(String::from(fill));
+        self
+    }
+
+    /// Sets the stroke color
+    pub fn stroke(mut self, stroke: &str) -> Style {
+        self.stroke = Some(String::from(stroke));
+        self
+    }
+
+    /// Sets the width of the stroke
+    pub fn stroke_width(mut self, stroke_width: f32) -> Style {
+        self.stroke_width = Some(stroke_width);
+        self
+    }
+
+    /// Sets the opacity, from `0.0` to `1.0`
+    pub fn opacity(mut self, opacity: f32) -> Style {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Sets the CSS class
+    pub fn class(mut self, class: &str) -> Style {
+        self.class = Some(String::from(class));
+        self
+    }
+
+    /// Will return whether the style does not override anything
+    pub fn is_empty(&self) -> bool {
+        *self == Style::default()
+    }
+
+    /// Will return the value of every attribute of the style, where `None` means it is not overridden
+    pub(crate) fn attributes(&self) -> [(&'static str, Option<String>); 5] {
+        [
+            ("fill", self.fill.clone()),
+            ("stroke", self.stroke.clone()),
+            (
+                "stroke-width",
+                self.stroke_width.map(|width| width.to_string()),
+            ),
+            ("opacity", self.opacity.map(|opacity| opacity.to_string())),
+            ("class", self.class.clone()),
+        ]
+    }
+
+    /// Creates a style from the attributes of an existing element, used when adopting existing svgs
+    pub(crate) fn from_attributes<F: Fn(&str) -> Option<String>>(attribute: F) -> Style {
+        Style {
+            fill: attribute("fill"),
+            stroke: attribute("stroke"),
+            stroke_width: attribute("stroke-width").and_then(|width| width.parse().ok()),
+            opacity: attribute("opacity").and_then(|opacity| opacity.parse().ok()),
+            class: attribute("class"),
+        }
+    }
+}
+
+/// The way a named item is hidden, each mode sets a single property of the style attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HideMode {
+    /// Sets `display: none;`, the item is not rendered at all
+    #[default]
+    Display,
+
+    /// Sets `visibility: hidden;`, children can still be made visible with their own visibility
+    Visibility,
+
+    /// Sets `opacity: 0;`, the item still receives pointer events and can be faded with a CSS transition
+    Opacity,
+}
+
+impl HideMode {
+    /// All modes, in the order in which they are checked
+    pub(crate) const ALL: [HideMode; 3] =
+        [HideMode::Display, HideMode::Visibility, HideMode::Opacity];
+
+    /// Will return the style property and the value which hide an item
+    pub(crate) fn property(self) -> (&'static str, &'static str) {
+        match self {
+            HideMode::Display => ("display", "none"),
+            HideMode::Visibility => ("visibility", "hidden"),
+            HideMode::Opacity => ("opacity", "0"),
+        }
+    }
+
+    /// Will return whether a value of the style property of the mode hides an item,
+    /// any opacity which is clamped to zero hides an item
+    pub(crate) fn is_hidden_by(self, value: &str) -> bool {
+        match self {
+            HideMode::Opacity => value
+                .trim_end_matches('%')
+                .parse::<f32>()
+                .is_ok_and(|opacity| opacity <= 0.0),
+            _ => value == self.property().1,
+        }
+    }
+}
+
+/// Will return the value of a single property within the value of a style attribute
+pub(crate) fn get_style_property(style: Option<&str>, property: &str) -> Option<String> {
+    style
+        .unwrap_or("")
+        .split(';')
+        .filter_map(|declaration| {
+            let mut parts = declaration.splitn(2, ':');
+
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if name.trim() == property => {
+                    Some(String::from(value.trim()))
+                }
+                _ => None,
+            }
+        })
+        .next_back()
+}
+
+/// Sets or removes a single property within the value of a style attribute,
+/// all other declarations are kept. Will return `None` when no declarations are left.
+pub(crate) fn set_style_property(
+    style: Option<&str>,
+    property: &str,
+    value: Option<&str>,
+) -> Option<String> {
+    let mut declarations: Vec<String> = style
+        .unwrap_or("")
+        .split(';')
+        .map(str::trim)
+        .filter(|declaration| !declaration.is_empty())
+        .filter(|declaration| declaration.split(':').next().map(str::trim) != Some(property))
+        .map(|declaration| format!("{};", declaration))
+        .collect();
+
+    if let Some(value) = value {
+        declarations.push(format!("{}: {};", property, value));
+    }
+
+    if declarations.is_empty() {
+        None
+    } else {
+        Some(declarations.join(" "))
+    }
+}
@@ -0,0 +1,345 @@
+//! Placement of figures and containers, from a simple location to any combination of transformations
+
+use std::fmt;
+
+use svg_definitions::Point2D;
+
+/// A single transformation, in the form of a SVG transform function
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Translate(f32, f32),
+
+    /// (Angle in degrees, Pivot)
+    Rotate(f32, Point2D),
+    Scale(f32, f32),
+
+    /// (Angle in degrees)
+    SkewX(f32),
+
+    /// (Angle in degrees)
+    SkewY(f32),
+    Matrix([f32; 6]),
+}
+
+impl Operation {
+    fn to_matrix(self) -> [f32; 6] {
+        match self {
+            Operation::Translate(x, y) => [1.0, 0.0, 0.0, 1.0, x, y],
+            Operation::Rotate(angle, (cx, cy)) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+
+                // Rotation around the pivot, i.e. translate(cx, cy) rotate(angle) translate(-cx, -cy)
+                [
+                    cos,
+                    sin,
+                    -sin,
+                    cos,
+                    cx - cos * cx + sin * cy,
+                    cy - sin * cx - cos * cy,
+                ]
+            }
+            Operation::Scale(x, y) => [x, 0.0, 0.0, y, 0.0, 0.0],
+            Operation::SkewX(angle) => [1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0],
+            Operation::SkewY(angle) => [1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            Operation::Matrix(matrix) => matrix,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operation::Translate(x, y) => write!(f, "translate({} {})", x, y),
+            Operation::Rotate(angle, (cx, cy)) if *cx == 0.0 && *cy == 0.0 => {
+                write!(f, "rotate({})", angle)
+            }
+            Operation::Rotate(angle, (cx, cy)) => write!(f, "rotate({} {} {})", angle, cx, cy),
+            Operation::Scale(x, y) => write!(f, "scale({} {})", x, y),
+            Operation::SkewX(angle) => write!(f, "skewX({})", angle),
+            Operation::SkewY(angle) => write!(f, "skewY({})", angle),
+            Operation::Matrix([a, b, c, d, e, g]) => {
+                write!(f, "matrix({} {} {} {} {} {})", a, b, c, d, e, g)
+            }
+        }
+    }
+}
+
+/// Placement of a figure or container, consisting of any combination of transformations
+///
+/// Every method of the [Renderer](../renderer/struct.Renderer.html) which takes a location also takes a transform,
+/// a location `(x, y)` is the same as `Transform::translate(x, y)`.
+///
+/// # Examples
+/// ```rust
+/// use wasm_svg_graphics::prelude::*;
+///
+/// // Rotate by 45 degrees around (5, 5), then scale by 2 and move to (20, 20)
+/// let transform = Transform::rotate_around(45.0, (5.0, 5.0))
+///     .then(Transform::scale(2.0, 2.0))
+///     .then(Transform::translate(20.0, 20.0));
+///
+/// assert_eq!(
+///     transform.to_string(),
+///     "translate(20 20) scale(2 2) rotate(45 5 5)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Transform {
+    /// The operations in the order of a SVG transform list, so the last operation is applied first
+    operations: Vec<Operation>,
+}
+
+impl Transform {
+    fn from_operation(operation: Operation) -> Transform {
+        Transform {
+            operations: vec![operation],
+        }
+    }
+
+    /// The transform which does not change anything
+    pub fn identity() -> Transform {
+        Transform::default()
+    }
+
+    /// Moves by `x` and `y`
+    pub fn translate(x: f32, y: f32) -> Transform {
+        Transform::from_operation(Operation::Translate(x, y))
+    }
+
+    /// Rotates clockwise by `angle` degrees around the origin
+    pub fn rotate(angle: f32) -> Transform {
+        Transform::from_operation(Operation::Rotate(angle, (0.0, 0.0)))
+    }
+
+    /// Rotates clockwise by `angle` degrees around the `pivot`
+    pub fn rotate_around(angle: f32, pivot: Point2D) -> Transform {
+        Transform::from_operation(Operation::Rotate(angle, pivot))
+    }
+
+    /// Scales by `x` horizontally and `y` vertically
+    pub fn scale(x: f32, y: f32) -> Transform {
+        Transform::from_operation(Operation::Scale(x, y))
+    }
+
+    /// Skews along the x-axis by `angle` degrees
+    pub fn skew_x(angle: f32) -> Transform {
+        Transform::from_operation(Operation::SkewX(angle))
+    }
+
+    /// Skews along the y-axis by `angle` degrees
+    pub fn skew_y(angle: f32) -> Transform {
+        Transform::from_operation(Operation::SkewY(angle))
+    }
+
+    /// Applies an arbitrary matrix `[a, b, c, d, e, f]`, in the same form as the SVG `matrix` function
+    pub fn matrix(matrix: [f32; 6]) -> Transform {
+        Transform::from_operation(Operation::Matrix(matrix))
+    }
+
+    /// Composes two transforms, where `next` is applied after this transform
+    pub fn then(mut self, next: Transform) -> Transform {
+        let mut operations = next.operations;
+        operations.append(&mut self.operations);
+
+        Transform { operations }
+    }
+
+    /// Will return whether the transform does not change anything
+    pub fn is_identity(&self) -> bool {
+        self.to_matrix() == [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+    }
+
+    /// Will return the location, when the transform consists of only a translation
+    pub fn as_translation(&self) -> Option<Point2D> {
+        match self.operations[..] {
+            [] => Some((0.0, 0.0)),
+            [Operation::Translate(x, y)] => Some((x, y)),
+            _ => None,
+        }
+    }
+
+    /// Will return the whole transform as a single matrix `[a, b, c, d, e, f]`,
+    /// in the same form as the SVG `matrix` function
+    pub fn to_matrix(&self) -> [f32; 6] {
+        self.operations
+            .iter()
+            .fold([1.0, 0.0, 0.0, 1.0, 0.0, 0.0], |matrix, operation| {
+                multiply(matrix, operation.to_matrix())
+            })
+    }
+
+    /// Transforms a point
+    pub fn apply(&self, point: Point2D) -> Point2D {
+        let [a, b, c, d, e, f] = self.to_matrix();
+
+        (a * point.0 + c * point.1 + e, b * point.0 + d * point.1 + f)
+    }
+
+    /// Will return the transform which undoes this transform,
+    /// or `None` when the transform cannot be undone, e.g. when scaling by zero
+    pub fn inverse(&self) -> Option<Transform> {
+        let [a, b, c, d, e, f] = self.to_matrix();
+        let determinant = a * d - b * c;
+
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        Some(Transform::matrix([
+            d / determinant,
+            -b / determinant,
+            -c / determinant,
+            a / determinant,
+            (c * f - d * e) / determinant,
+            (b * e - a * f) / determinant,
+        ]))
+    }
+
+    /// Will return the transform from the coordinates of a viewbox to the coordinates of the viewport it is shown in,
+    /// following the rules of the SVG `viewBox` and `preserveAspectRatio` attributes
+    ///
+    /// # Arguments
+    /// * `view_box` - The value of the `viewBox` attribute, if it is set
+    /// * `preserve_aspect_ratio` - The value of the `preserveAspectRatio` attribute, if it is set
+    /// * `viewport` - The viewport as `(x, y, width, height)`
+    pub(crate) fn viewport(
+        view_box: Option<&str>,
+        preserve_aspect_ratio: Option<&str>,
+        (x, y, width, height): (f32, f32, f32, f32),
+    ) -> Transform {
+        let (min_x, min_y, view_width, view_height) = match view_box.and_then(parse_view_box) {
+            Some(view_box) => view_box,
+            None => return Transform::translate(x, y),
+        };
+
+        let scale_x = width / view_width;
+        let scale_y = height / view_height;
+
+        // The default is "xMidYMid meet", "defer" only applies to images
+        let mut parts = preserve_aspect_ratio
+            .unwrap_or("")
+            .split_whitespace()
+            .filter(|part| *part != "defer");
+        let align = parts.next().unwrap_or("xMidYMid");
+        let slice = parts.next() == Some("slice");
+
+        if align == "none" {
+            return Transform::translate(-min_x, -min_y)
+                .then(Transform::scale(scale_x, scale_y))
+                .then(Transform::translate(x, y));
+        }
+
+        let scale = if slice {
+            scale_x.max(scale_y)
+        } else {
+            scale_x.min(scale_y)
+        };
+
+        let align_x = alignment(align.get(0..4).unwrap_or("xMid"));
+        let align_y = alignment(align.get(4..8).unwrap_or("YMid"));
+
+        Transform::translate(-min_x, -min_y)
+            .then(Transform::scale(scale, scale))
+            .then(Transform::translate(
+                x + align_x * (width - view_width * scale),
+                y + align_y * (height - view_height * scale),
+            ))
+    }
+
+    /// Parses the value of a SVG transform attribute, used when adopting existing svgs
+    pub(crate) fn parse(value: &str) -> Option<Transform> {
+        let mut operations = Vec::new();
+
+        for function in value.split(')').map(str::trim).filter(|f| !f.is_empty()) {
+            let mut parts = function.splitn(2, '(');
+            let name = parts
+                .next()?
+                .trim_matches(|c: char| c == ',' || c.is_whitespace());
+            let arguments = parts
+                .next()?
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|argument| !argument.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<f32>, _>>()
+                .ok()?;
+
+            operations.push(match (name, &arguments[..]) {
+                ("translate", [x]) => Operation::Translate(*x, 0.0),
+                ("translate", [x, y]) => Operation::Translate(*x, *y),
+                ("rotate", [angle]) => Operation::Rotate(*angle, (0.0, 0.0)),
+                ("rotate", [angle, cx, cy]) => Operation::Rotate(*angle, (*cx, *cy)),
+                ("scale", [s]) => Operation::Scale(*s, *s),
+                ("scale", [x, y]) => Operation::Scale(*x, *y),
+                ("skewX", [angle]) => Operation::SkewX(*angle),
+                ("skewY", [angle]) => Operation::SkewY(*angle),
+                ("matrix", [a, b, c, d, e, f]) => Operation::Matrix([*a, *b, *c, *d, *e, *f]),
+                _ => return None,
+            });
+        }
+
+        Some(Transform { operations })
+    }
+}
+
+/// Parses the value of a SVG viewBox attribute into `(min-x, min-y, width, height)`
+fn parse_view_box(value: &str) -> Option<(f32, f32, f32, f32)> {
+    let numbers = value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|number| !number.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<f32>, _>>()
+        .ok()?;
+
+    match numbers[..] {
+        [min_x, min_y, width, height] if width > 0.0 && height > 0.0 => {
+            Some((min_x, min_y, width, height))
+        }
+        _ => None,
+    }
+}
+
+/// Will return which part of the remaining space is placed before the viewbox for an alignment,
+/// e.g. `xMid` places half of it to the left
+fn alignment(value: &str) -> f32 {
+    match value.get(1..) {
+        Some("Min") => 0.0,
+        Some("Max") => 1.0,
+        _ => 0.5,
+    }
+}
+
+/// Multiplies two matrices in the form `[a, b, c, d, e, f]`
+fn multiply(left: [f32; 6], right: [f32; 6]) -> [f32; 6] {
+    let [a1, b1, c1, d1, e1, f1] = left;
+    let [a2, b2, c2, d2, e2, f2] = right;
+
+    [
+        a1 * a2 + c1 * b2,
+        b1 * a2 + d1 * b2,
+        a1 * c2 + c1 * d2,
+        b1 * c2 + d1 * d2,
+        a1 * e2 + c1 * f2 + e1,
+        b1 * e2 + d1 * f2 + f1,
+    ]
+}
+
+impl From<Point2D> for Transform {
+    fn from(location: Point2D) -> Transform {
+        Transform::translate(location.0, location.1)
+    }
+}
+
+impl fmt::Display for Transform {
+    /// Formats the transform as the value of a SVG transform attribute
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, operation) in self.operations.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", operation)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,633 @@
+//! Test suite for custom backends, which run natively.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_svg_graphics::backend::Backend;
+use wasm_svg_graphics::errors::DomError::*;
+use wasm_svg_graphics::errors::RendererError;
+use wasm_svg_graphics::prelude::*;
+
+struct Node {
+    tag_name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<usize>,
+    parent: Option<usize>,
+}
+
+/// Minimal backend storing all elements in a single arena, which can be shared between renderers
+#[derive(Clone)]
+struct ArenaBackend {
+    nodes: Rc<RefCell<Vec<Node>>>,
+    lookups: Rc<Cell<usize>>,
+    listeners: Rc<Cell<usize>>,
+
+    /// The amount of changes which succeed before changes start to fail, `None` to never fail
+    failing_after: Rc<Cell<Option<usize>>>,
+}
+
+/// Listener which only counts how many listeners are alive
+struct ArenaListener(Rc<Cell<usize>>);
+
+impl Drop for ArenaListener {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
+    }
+}
+
+impl ArenaBackend {
+    fn new(parent_id: &str) -> ArenaBackend {
+        ArenaBackend {
+            nodes: Rc::new(RefCell::new(vec![Node {
+                tag_name: String::from("div"),
+                attributes: vec![(String::from("id"), String::from(parent_id))],
+                children: Vec::new(),
+                parent: None,
+            }])),
+            lookups: Rc::new(Cell::new(0)),
+            listeners: Rc::new(Cell::new(0)),
+            failing_after: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Will return whether a change fails, counting down the changes which still succeed
+    fn fails(&self) -> bool {
+        match self.failing_after.get() {
+            Some(0) => true,
+            Some(remaining) => {
+                self.failing_after.set(Some(remaining - 1));
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Adds another parent element to the arena, so multiple renderers can be attached
+    fn add_parent(&self, parent_id: &str) {
+        let parent = self.create_element("div").unwrap();
+        self.set_attribute(&parent, "id", parent_id).unwrap();
+        self.append_child(&0, &parent).unwrap();
+    }
+
+    fn attribute(&self, element: usize, name: &str) -> Option<String> {
+        self.nodes.borrow()[element]
+            .attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    fn is_attached(&self, element: usize) -> bool {
+        match self.nodes.borrow()[element].parent {
+            None => element == 0,
+            Some(parent) => self.is_attached(parent),
+        }
+    }
+}
+
+impl Backend for ArenaBackend {
+    type Element = usize;
+    type Listener = ArenaListener;
+
+    fn create_element(&self, tag_name: &str) -> Result<usize, RendererError> {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node {
+            tag_name: String::from(tag_name),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            parent: None,
+        });
+        Ok(nodes.len() - 1)
+    }
+
+    fn get_element_by_id(&self, id: &str) -> Option<usize> {
+        self.lookups.set(self.lookups.get() + 1);
+
+        let len = self.nodes.borrow().len();
+        (0..len).find(|element| {
+            self.attribute(*element, "id").as_deref() == Some(id) && self.is_attached(*element)
+        })
+    }
+
+    fn tag_name(&self, element: &usize) -> String {
+        self.nodes.borrow()[*element].tag_name.clone()
+    }
+
+    fn set_attribute(&self, element: &usize, name: &str, value: &str) -> Result<(), RendererError> {
+        if self.fails() {
+            return Err(RendererError::Dom(UnsetableAttribute(
+                String::from(name),
+                String::from(value),
+            )));
+        }
+
+        let mut nodes = self.nodes.borrow_mut();
+        let attributes = &mut nodes[*element].attributes;
+        attributes.retain(|(key, _)| key != name);
+        attributes.push((String::from(name), String::from(value)));
+        Ok(())
+    }
+
+    fn get_attribute(&self, element: &usize, name: &str) -> Option<String> {
+        self.attribute(*element, name)
+    }
+
+    fn attributes(&self, element: &usize) -> Vec<(String, String)> {
+        self.nodes.borrow()[*element].attributes.clone()
+    }
+
+    fn remove_attribute(&self, element: &usize, name: &str) -> Result<(), RendererError> {
+        self.nodes.borrow_mut()[*element]
+            .attributes
+            .retain(|(key, _)| key != name);
+        Ok(())
+    }
+
+    fn set_text(&self, _element: &usize, _text: &str) -> Result<(), RendererError> {
+        Ok(())
+    }
+
+    fn text(&self, _element: &usize) -> Option<String> {
+        None
+    }
+
+    fn append_child(&self, parent: &usize, child: &usize) -> Result<(), RendererError> {
+        if self.fails() {
+            return Err(RendererError::Dom(UnappendableElement));
+        }
+
+        if let Some(old_parent) = self.parent_element(child) {
+            self.remove_child(&old_parent, child)?;
+        }
+
+        let mut nodes = self.nodes.borrow_mut();
+        nodes[*parent].children.push(*child);
+        nodes[*child].parent = Some(*parent);
+        Ok(())
+    }
+
+    fn insert_before(
+        &self,
+        parent: &usize,
+        child: &usize,
+        reference: &usize,
+    ) -> Result<(), RendererError> {
+        if let Some(old_parent) = self.parent_element(child) {
+            self.remove_child(&old_parent, child)?;
+        }
+
+        let mut nodes = self.nodes.borrow_mut();
+        let index = nodes[*parent]
+            .children
+            .iter()
+            .position(|element| element == reference)
+            .unwrap();
+        nodes[*parent].children.insert(index, *child);
+        nodes[*child].parent = Some(*parent);
+        Ok(())
+    }
+
+    fn replace_child(
+        &self,
+        parent: &usize,
+        new_child: &usize,
+        old_child: &usize,
+    ) -> Result<(), RendererError> {
+        self.insert_before(parent, new_child, old_child)?;
+        self.remove_child(parent, old_child)
+    }
+
+    fn remove_child(&self, parent: &usize, child: &usize) -> Result<(), RendererError> {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes[*parent].children.retain(|element| element != child);
+        nodes[*child].parent = None;
+        Ok(())
+    }
+
+    fn remove_children(&self, element: &usize) -> Result<(), RendererError> {
+        let mut nodes = self.nodes.borrow_mut();
+        for child in std::mem::take(&mut nodes[*element].children) {
+            nodes[child].parent = None;
+        }
+        Ok(())
+    }
+
+    fn parent_element(&self, element: &usize) -> Option<usize> {
+        self.nodes.borrow()[*element].parent
+    }
+
+    fn children(&self, element: &usize) -> Vec<usize> {
+        self.nodes.borrow()[*element].children.clone()
+    }
+
+    fn first_element_child(&self, element: &usize) -> Option<usize> {
+        self.nodes.borrow()[*element].children.first().cloned()
+    }
+
+    fn same_element(&self, first: &usize, second: &usize) -> bool {
+        first == second
+    }
+
+    fn add_listener(
+        &self,
+        _svg: &usize,
+        _element: &usize,
+        _event_type: &str,
+        _handler: Box<dyn FnMut(Point2D, Point2D)>,
+    ) -> Result<ArenaListener, RendererError> {
+        self.listeners.set(self.listeners.get() + 1);
+        Ok(ArenaListener(self.listeners.clone()))
+    }
+}
+
+#[test]
+fn backend_render() {
+    let mut renderer =
+        SVGRenderer::with_backend(ArenaBackend::new("svg_parent_id"), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    renderer.render_id(circle_id, (20.0, 20.0));
+    renderer.render_id(circle_id, (30.0, 30.0));
+
+    let backend = renderer.backend();
+    let nodes = backend.nodes.borrow();
+
+    // svg root containing the defs and two uses
+    let svg_root = nodes[0].children[0];
+    assert_eq!(nodes[svg_root].tag_name, "svg");
+    assert_eq!(nodes[svg_root].children.len(), 3);
+
+    // Only a single definition is added
+    let defs = nodes[svg_root].children[0];
+    assert_eq!(nodes[defs].tag_name, "defs");
+    assert_eq!(nodes[defs].children.len(), 1);
+}
+
+#[test]
+fn backend_named() {
+    let mut renderer =
+        SVGRenderer::with_backend(ArenaBackend::new("svg_parent_id"), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+    renderer.create_named_container("named_container", "root");
+    renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.move_named("named_circle", (25.0, 5.0));
+
+    assert!(renderer.is_container("named_container"));
+    assert!(!renderer.is_container("named_circle"));
+
+    let backend = renderer.backend();
+    let use_element = backend.nodes.borrow()[0].children[0];
+    let use_element = backend.nodes.borrow()[use_element].children[2];
+    assert_eq!(backend.attribute(use_element, "x").as_deref(), Some("25"));
+    assert_eq!(backend.attribute(use_element, "y").as_deref(), Some("5"));
+
+    renderer.delete_named("named_container");
+    renderer.delete_named("named_circle");
+
+    assert!(!renderer.does_name_exist("named_container"));
+    assert!(!renderer.does_name_exist("named_circle"));
+}
+
+#[test]
+fn backend_namespaces() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let backend = ArenaBackend::new("page");
+    backend.add_parent("first_parent_id");
+    backend.add_parent("second_parent_id");
+
+    let mut first = SVGRenderer::with_backend(backend.clone(), "first_parent_id")
+        .expect("Failed to create renderer!");
+    let mut second = SVGRenderer::with_backend(backend.clone(), "second_parent_id")
+        .expect("Failed to create renderer!");
+
+    // By default the namespace is the id of the parent
+    assert_eq!(first.namespace(), "first_parent_id");
+    second.set_namespace("second");
+    assert_eq!(second.namespace(), "second");
+
+    // Both renderers can use the same names and figures within the same document
+    first.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    second.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+
+    // Every use refers to a definition within its own svg
+    for parent in 1..=2 {
+        let svg_root = backend.nodes.borrow()[parent].children[0];
+        let defs = backend.nodes.borrow()[svg_root].children[0];
+        let def = backend.nodes.borrow()[defs].children[0];
+        let use_element = backend.nodes.borrow()[svg_root].children[1];
+
+        assert_eq!(
+            backend.attribute(use_element, "href"),
+            backend.attribute(def, "id").map(|id| format!("#{}", id))
+        );
+    }
+
+    first.delete_named("named_circle");
+    assert!(second.does_name_exist("named_circle"));
+
+    // The namespace can only be changed while the renderer is empty
+    match second.try_set_namespace("other") {
+        Err(UnusableNamespace(namespace)) => assert_eq!(namespace, "other"),
+        _ => panic!("Expected UnusableNamespace"),
+    }
+
+    match first.try_set_namespace("has whitespace") {
+        Err(UnusableNamespace(_)) => {}
+        _ => panic!("Expected UnusableNamespace"),
+    }
+}
+
+#[test]
+fn backend_adopt() {
+    let backend = ArenaBackend::new("svg_parent_id");
+
+    let circle = {
+        let mut renderer = SVGRenderer::with_backend(backend.clone(), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+        let circle_id = renderer.define_render(SVGDefault::circle(10));
+        renderer.create_named_container("named_container", "root");
+        renderer.append_to_container_with_id("named_container", circle_id, (10.0, 10.0));
+        let circle = renderer.render_named_id("named_circle", circle_id, (10.0, 10.0));
+        renderer.define_render(SVGDefault::rect(10, 10));
+
+        circle
+    };
+
+    let mut renderer = SVGRenderer::with_backend_adopt(backend.clone(), "svg_parent_id")
+        .expect("Failed to adopt svg!");
+
+    // The svg is not added again
+    assert_eq!(backend.nodes.borrow()[0].children.len(), 1);
+
+    // Names are recovered
+    assert!(renderer.is_container("named_container"));
+    assert!(renderer.does_name_exist("named_circle"));
+    assert_eq!(
+        renderer.get_handle("named_circle").unwrap().name(),
+        circle.name()
+    );
+
+    // Definitions are recovered and reused
+    let svg_root = backend.nodes.borrow()[0].children[0];
+    let defs = backend.nodes.borrow()[svg_root].children[0];
+    renderer.render(SVGDefault::circle(10), (30.0, 30.0));
+    assert_eq!(backend.nodes.borrow()[defs].children.len(), 2);
+
+    // Uses are counted, so only the unused rect is collected
+    assert_eq!(renderer.collect_garbage(), 1);
+
+    renderer.move_named("named_circle", (20.0, 20.0));
+    renderer.delete_named("named_container");
+    assert!(!renderer.does_name_exist("named_container"));
+}
+
+#[test]
+fn backend_resync() {
+    let mut renderer =
+        SVGRenderer::with_backend(ArenaBackend::new("svg_parent_id"), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+    let circle = renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.render_named("other_circle", SVGDefault::circle(10), (10.0, 10.0));
+
+    // Remove the named circle without the renderer
+    {
+        let backend = renderer.backend();
+        let svg_root = backend.nodes.borrow()[0].children[0];
+        let use_element = backend.nodes.borrow()[svg_root].children[1];
+        backend.remove_child(&svg_root, &use_element).unwrap();
+    }
+
+    renderer.resync();
+
+    assert!(!renderer.does_name_exist(&circle));
+    assert!(renderer.does_name_exist("other_circle"));
+    assert_eq!(renderer.collect_garbage(), 0);
+
+    renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+}
+
+#[test]
+fn backend_cached_elements() {
+    let mut renderer =
+        SVGRenderer::with_backend(ArenaBackend::new("svg_parent_id"), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+    renderer.create_named_container("outer", "root");
+    renderer.create_named_container("inner", "outer");
+    let circle = renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+
+    // Named items and the svg are not looked up in the document again
+    let lookups = renderer.backend().lookups.get();
+    for i in 0..100 {
+        renderer.move_named(&circle, (i as f32, i as f32));
+        renderer.hide_named("named_circle");
+        renderer.show_named("named_circle");
+        renderer.append_to_container("inner", SVGDefault::circle(10), (0.0, 0.0));
+        renderer.render(SVGDefault::circle(10), (0.0, 0.0));
+    }
+    assert_eq!(renderer.backend().lookups.get(), lookups);
+
+    // Names within a deleted container are removed as well
+    renderer.delete_named("outer");
+    assert!(!renderer.does_name_exist("inner"));
+    renderer.create_named_container("inner", "root");
+
+    renderer.clear();
+    assert!(!renderer.does_name_exist("named_circle"));
+    renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+}
+
+#[test]
+fn backend_adopt_transform() {
+    let backend = ArenaBackend::new("svg_parent_id");
+
+    {
+        let mut renderer = SVGRenderer::with_backend(backend.clone(), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+        renderer.render_named(
+            "named_rect",
+            SVGDefault::rect(10, 10),
+            Transform::rotate(45.0),
+        );
+        renderer.create_named_container("named_container", "root");
+        renderer.transform_named("named_container", Transform::scale(2.0, 2.0));
+    }
+
+    let mut renderer = SVGRenderer::with_backend_adopt(backend.clone(), "svg_parent_id")
+        .expect("Failed to adopt svg!");
+
+    let svg_root = backend.nodes.borrow()[0].children[0];
+    let use_element = backend.nodes.borrow()[svg_root].children[1];
+    let container = backend.nodes.borrow()[svg_root].children[2];
+
+    // The recovered transform is replaced by a location
+    renderer.move_named("named_rect", (10.0, 10.0));
+    assert_eq!(backend.attribute(use_element, "transform"), None);
+    assert_eq!(
+        backend.attribute(use_element, "x"),
+        Some(String::from("10"))
+    );
+
+    renderer.transform_named("named_rect", Transform::rotate(90.0));
+    assert_eq!(backend.attribute(use_element, "x"), None);
+    assert_eq!(
+        backend.attribute(use_element, "transform"),
+        Some(String::from("rotate(90)"))
+    );
+
+    assert_eq!(
+        backend.attribute(container, "transform"),
+        Some(String::from("scale(2 2)"))
+    );
+    renderer.transform_named("named_container", Transform::identity());
+    assert_eq!(backend.attribute(container, "transform"), None);
+}
+
+#[test]
+fn backend_adopt_layers() {
+    let backend = ArenaBackend::new("svg_parent_id");
+
+    {
+        let mut renderer = SVGRenderer::with_backend(backend.clone(), "svg_parent_id")
+            .expect("Failed to create renderer!");
+
+        renderer.create_layer("background", 0);
+        renderer.create_layer("ui", 10);
+    }
+
+    let mut renderer = SVGRenderer::with_backend_adopt(backend.clone(), "svg_parent_id")
+        .expect("Failed to adopt svg!");
+
+    // The z-index of the layers is recovered
+    assert!(renderer.is_layer("ui"));
+    renderer.create_layer("entities", 5);
+
+    let svg_root = backend.nodes.borrow()[0].children[0];
+    let names: Vec<Option<String>> = backend.nodes.borrow()[svg_root]
+        .children
+        .iter()
+        .map(|child| backend.attribute(*child, "data-name"))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            None,
+            Some(String::from("background")),
+            Some(String::from("entities")),
+            Some(String::from("ui"))
+        ]
+    );
+}
+
+#[test]
+fn backend_listeners() {
+    let mut renderer =
+        SVGRenderer::with_backend(ArenaBackend::new("svg_parent_id"), "svg_parent_id")
+            .expect("Failed to create renderer!");
+    let listeners = renderer.backend().listeners.clone();
+
+    renderer.create_named_container("container", "root");
+    renderer.render_named_in(
+        "container",
+        "named_circle",
+        SVGDefault::circle(10),
+        (0.0, 0.0),
+    );
+    renderer.render_named("other_circle", SVGDefault::circle(10), (0.0, 0.0));
+    renderer.on_named("container", PointerEventKind::Click, |_| {});
+    renderer.on_named("named_circle", PointerEventKind::PointerDown, |_| {});
+    renderer.on_named("named_circle", PointerEventKind::PointerUp, |_| {});
+    renderer.on_named("other_circle", PointerEventKind::Wheel, |_| {});
+    assert_eq!(listeners.get(), 4);
+
+    // Listeners are dropped with their item and the items within it
+    renderer.off_named("named_circle", PointerEventKind::PointerUp);
+    assert_eq!(listeners.get(), 3);
+    renderer.rename_named("other_circle", "renamed_circle");
+    assert_eq!(listeners.get(), 3);
+    renderer.delete_named("container");
+    assert_eq!(listeners.get(), 1);
+
+    renderer.resync();
+    assert_eq!(listeners.get(), 1);
+    renderer.clear();
+    assert_eq!(listeners.get(), 0);
+}
+
+#[test]
+fn backend_failed_flush() {
+    let backend = ArenaBackend::new("svg_parent_id");
+    let mut renderer = SVGRenderer::with_backend(backend.clone(), "svg_parent_id")
+        .expect("Failed to create renderer!");
+
+    renderer.set_batching(true);
+    renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.flush();
+
+    let svg_root = backend.nodes.borrow()[0].children[0];
+    let use_element = backend.nodes.borrow()[svg_root].children[1];
+    let read = |name| backend.attribute(use_element, name)?.parse::<f32>().ok();
+
+    renderer.move_named("named_circle", (20.0, 20.0));
+
+    // The queued operations are kept when the flush fails
+    backend.failing_after.set(Some(0));
+    assert!(renderer.try_flush().is_err());
+
+    backend.failing_after.set(None);
+    renderer.flush();
+    assert_eq!(read("x"), Some(20.0));
+    assert_eq!(read("y"), Some(20.0));
+
+    // Only the operations which were not applied are queued again
+    renderer.move_named("named_circle", (30.0, 30.0));
+    backend.failing_after.set(Some(1));
+    assert!(renderer.try_flush().is_err());
+
+    backend.failing_after.set(Some(1));
+    renderer.flush();
+    backend.failing_after.set(None);
+    assert_eq!(read("x"), Some(30.0));
+    assert_eq!(read("y"), Some(30.0));
+}
+
+#[test]
+fn backend_failed_duplicate() {
+    let backend = ArenaBackend::new("svg_parent_id");
+    let mut renderer = SVGRenderer::with_backend(backend.clone(), "svg_parent_id")
+        .expect("Failed to create renderer!");
+
+    renderer.create_named_container("car", "root");
+    renderer.render_named_in("car", "front_wheel", SVGDefault::circle(5), (0.0, 10.0));
+    renderer.render_named_in("car", "back_wheel", SVGDefault::circle(5), (20.0, 10.0));
+
+    // Every change of the copy fails in turn, which leaves nothing of the copy behind
+    let mut failing_after = 0;
+    loop {
+        backend.failing_after.set(Some(failing_after));
+        let result = renderer.try_duplicate_named("car", "second_");
+        backend.failing_after.set(None);
+
+        if result.is_ok() {
+            break;
+        }
+
+        for name in ["second_car", "second_front_wheel", "second_back_wheel"].iter() {
+            assert!(!renderer.does_name_exist(*name));
+        }
+        failing_after += 1;
+    }
+    assert!(failing_after > 2);
+    assert!(renderer.does_name_exist("second_back_wheel"));
+
+    // The references of the failed copies were not counted
+    renderer.delete_named("car");
+    renderer.delete_named("second_car");
+    assert_eq!(renderer.collect_garbage(), 1);
+}
@@ -0,0 +1,201 @@
+//! Test suite for the bounding boxes of figures, which runs natively.
+
+use wasm_svg_graphics::prelude::*;
+
+fn assert_bounds(bounds: Option<BoundingBox>, min: Point2D, max: Point2D) {
+    let bounds = bounds.expect("Expected a bounding box");
+    let close = |left: Point2D, right: Point2D| {
+        (left.0 - right.0).abs() < 1e-3 && (left.1 - right.1).abs() < 1e-3
+    };
+
+    assert!(
+        close(bounds.min(), min) && close(bounds.max(), max),
+        "{:?} != {:?} - {:?}",
+        bounds,
+        min,
+        max
+    );
+}
+
+fn path(data: &str) -> SVGElem {
+    SVGElem::new(Tag::Path).set(Attr::D, data)
+}
+
+#[test]
+fn bounds_defaults() {
+    // Half of the stroke is outside of the shape
+    assert_bounds(
+        BoundingBox::of(&SVGDefault::circle(10)),
+        (-10.5, -10.5),
+        (10.5, 10.5),
+    );
+    assert_bounds(
+        BoundingBox::of(&SVGDefault::rect(10, 20)),
+        (-0.5, -0.5),
+        (10.5, 20.5),
+    );
+    assert_bounds(
+        BoundingBox::of(&SVGDefault::polygon(vec![(0, 0), (10, 0), (5, 5)])),
+        (-0.5, -0.5),
+        (10.5, 5.5),
+    );
+
+    // Without a stroke only the curve itself counts, which does not reach its control points
+    assert_bounds(
+        BoundingBox::of(&SVGDefault::curve(0, 0, 10, 0, 0, 10, 10, 10)),
+        (0.0, 0.0),
+        (10.0, 7.5),
+    );
+
+    // The CSS variables fall back to the original values
+    assert_bounds(
+        BoundingBox::of(&SVGDefault::inherit_style(SVGDefault::circle(10))),
+        (-10.5, -10.5),
+        (10.5, 10.5),
+    );
+}
+
+#[test]
+fn bounds_paths() {
+    // Half a circle, sweeping through the top
+    assert_bounds(
+        BoundingBox::of(&path("M 0 0 A 5 5 0 0 1 10 0")),
+        (0.0, -5.0),
+        (10.0, 0.0),
+    );
+    assert_bounds(
+        BoundingBox::of(&path("M 0 0 A 5 5 0 0 0 10 0")),
+        (0.0, 0.0),
+        (10.0, 5.0),
+    );
+
+    // Radii which are too small are scaled up
+    assert_bounds(
+        BoundingBox::of(&path("M 0 0 a 1 1 0 1 1 10 0")),
+        (0.0, -5.0),
+        (10.0, 0.0),
+    );
+
+    assert_bounds(
+        BoundingBox::of(&path("M 0 0 Q 5 10 10 0")),
+        (0.0, 0.0),
+        (10.0, 5.0),
+    );
+    assert_bounds(
+        BoundingBox::of(&path("M0 0 C 0 10 10 10 10 0 S 20 -10 20 0")),
+        (0.0, -7.5),
+        (20.0, 7.5),
+    );
+
+    // Relative commands with implicit repetition
+    assert_bounds(
+        BoundingBox::of(&path("m 1 1 10 0 0 10 z")),
+        (1.0, 1.0),
+        (11.0, 11.0),
+    );
+    assert_bounds(
+        BoundingBox::of(&path("M0,0L1e1-5h-20")),
+        (-10.0, -5.0),
+        (10.0, 0.0),
+    );
+
+    // Everything up to an error is kept
+    assert_bounds(
+        BoundingBox::of(&path("M 0 0 L 10 10 L 20")),
+        (0.0, 0.0),
+        (10.0, 10.0),
+    );
+    assert_eq!(BoundingBox::of(&path("M 5 5")), None);
+}
+
+#[test]
+fn bounds_groups() {
+    let group = SVGElem::new(Tag::G)
+        .set(Attr::Stroke, "#000")
+        .set(Attr::StrokeWidth, 2)
+        .set(Attr::Transform, "translate(10 10)")
+        .append(
+            SVGElem::new(Tag::Rect)
+                .set(Attr::Width, 10)
+                .set(Attr::Height, "10px"),
+        )
+        .append(
+            SVGElem::new(Tag::Circle)
+                .set(Attr::R, 50)
+                .set(Attr::Style, "display: none;"),
+        )
+        .append(SVGElem::new(Tag::Text).set_inner("Not measured"));
+
+    assert_bounds(BoundingBox::of(&group), (9.0, 9.0), (21.0, 21.0));
+
+    // The stroke scales with the transform
+    let scaled = SVGElem::new(Tag::G)
+        .set(Attr::Transform, "scale(2 1)")
+        .append(
+            SVGElem::new(Tag::Line)
+                .set(Attr::X1, 0)
+                .set(Attr::Y1, 0)
+                .set(Attr::X2, 10)
+                .set(Attr::Y2, 0)
+                .set(Attr::Stroke, "red")
+                .set(Attr::StrokeWidth, 2),
+        );
+    assert_bounds(BoundingBox::of(&scaled), (-2.0, -1.0), (22.0, 1.0));
+
+    assert_eq!(BoundingBox::of(&SVGElem::new(Tag::G)), None);
+    assert_eq!(
+        BoundingBox::of(&SVGElem::new(Tag::Circle).set(Attr::R, 0)),
+        None
+    );
+}
+
+#[test]
+fn bounds_transformed() {
+    let circle = SVGElem::new(Tag::Circle).set(Attr::R, 10);
+
+    // A rotated circle keeps the same bounds, while its transformed bounding box grows
+    let rotation = Transform::rotate(45.0);
+    assert_bounds(
+        BoundingBox::of_transformed(&circle, &rotation),
+        (-10.0, -10.0),
+        (10.0, 10.0),
+    );
+
+    let bounds = BoundingBox::of(&circle).unwrap();
+    let diagonal = 10.0 * 2f32.sqrt();
+    assert_bounds(
+        Some(bounds.transform(&rotation)),
+        (-diagonal, -diagonal),
+        (diagonal, diagonal),
+    );
+
+    assert_bounds(
+        BoundingBox::of_transformed(&SVGDefault::rect(10, 10), &Transform::translate(5.0, 0.0)),
+        (4.5, -0.5),
+        (15.5, 10.5),
+    );
+}
+
+#[test]
+fn bounds_operations() {
+    let left = BoundingBox::from_rect(0.0, 0.0, 10.0, 5.0);
+    let right = BoundingBox::new((20.0, 10.0), (10.0, 0.0));
+
+    assert_eq!(left.width(), 10.0);
+    assert_eq!(left.height(), 5.0);
+    assert_eq!(right.min(), (10.0, 0.0));
+    assert_eq!(right.center(), (15.0, 5.0));
+
+    // Touching edges overlap
+    assert!(left.intersects(&right));
+    assert!(!left.intersects(&BoundingBox::from_point((11.0, 0.0))));
+
+    let union = left.union(right);
+    assert_eq!((union.x(), union.y()), (0.0, 0.0));
+    assert_eq!(union.max(), (20.0, 10.0));
+
+    assert!(union.contains((20.0, 10.0)));
+    assert!(!union.contains((20.1, 10.0)));
+    assert_eq!(left.include((-1.0, 7.0)).min(), (-1.0, 0.0));
+    assert_eq!(left.expand(1.0, 2.0).max(), (11.0, 7.0));
+}
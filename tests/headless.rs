@@ -0,0 +1,1742 @@
+//! Test suite for the headless renderer, which runs natively.
+
+use wasm_svg_graphics::backend::HeadlessBackend;
+use wasm_svg_graphics::prelude::*;
+
+#[test]
+fn headless_empty() {
+    let renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    assert_eq!(
+        renderer.to_svg_string(),
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100"><defs/></svg>"#
+    );
+}
+
+#[test]
+fn headless_render() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let rect = SVGDefault::rect(10, 20);
+    let rect_id = renderer.define_render(rect);
+
+    renderer.render_id(rect_id, (20.0, 20.0));
+
+    let output = renderer.to_svg_string();
+
+    assert!(output.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100"><defs><rect "#
+    ));
+    assert!(output.contains(r#"width="10""#));
+    assert!(output.contains(r#"height="20""#));
+    assert_eq!(output.matches("<use ").count(), 1);
+    assert!(output.contains(r##"<use x="20" y="20" href="#figure-"##));
+}
+
+#[test]
+fn headless_render_named() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.move_named("named_circle", (20.0, 30.0));
+
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r##"<use x="20" y="30" href="#figure-"##));
+    assert!(output.contains(r#" id="named-"#));
+
+    renderer.hide_named("named_circle");
+    assert!(renderer
+        .to_svg_string()
+        .contains(r#"style="display: none;""#));
+
+    renderer.show_named("named_circle");
+    assert!(!renderer.to_svg_string().contains("style="));
+
+    renderer.delete_named("named_circle");
+    assert!(!renderer.does_name_exist("named_circle"));
+    assert!(!renderer.to_svg_string().contains("<use"));
+}
+
+#[test]
+fn headless_named_container() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.create_named_container("outer", "root");
+    renderer.create_named_container("inner", "outer");
+    renderer.append_to_container("inner", SVGDefault::circle(10), (10.0, 10.0));
+
+    assert!(renderer.is_container("outer"));
+    assert!(renderer.is_container("inner"));
+
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r#"<g id="named-"#));
+    assert_eq!(output.matches("<g ").count(), 2);
+    assert!(output.contains(r##"<use x="10" y="10" href="#figure-"##));
+
+    renderer.update_named("inner", SVGDefault::circle(5), (20.0, 20.0));
+
+    let output = renderer.to_svg_string();
+    assert_eq!(output.matches("<use ").count(), 1);
+    assert!(output.contains(r##"<use x="20" y="20" href="#figure-"##));
+
+    renderer.clear_named_container("outer");
+    assert!(!renderer.to_svg_string().contains("<use"));
+}
+
+#[test]
+fn headless_update_named() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    let rect_id = renderer.define_render(SVGDefault::rect(10, 10));
+
+    renderer.render_named_id("named", circle_id, (10.0, 10.0));
+    renderer.update_named_with_id("named", rect_id, (15.0, 15.0));
+
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r##"<use x="15" y="15" href="#figure-"##));
+    assert_eq!(output.matches("<use ").count(), 1);
+}
+
+#[test]
+fn headless_clear() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.adjust_viewbox(0, 0, 50, 50);
+
+    renderer.clear();
+
+    assert_eq!(
+        renderer.to_svg_string(),
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 50 50"><defs/></svg>"#
+    );
+
+    renderer.render_named("named_circle", SVGDefault::circle(10), (20.0, 20.0));
+    assert!(renderer.does_name_exist("named_circle"));
+}
+
+#[test]
+fn headless_from_svg() {
+    let renderer = SVGRenderer::new_headless_from_svg(
+        SVGElem::new(Tag::Svg)
+            .set(Attr::ViewBox, "0 0 10 10")
+            .append(SVGElem::new(Tag::Text).set_inner("a & b")),
+    )
+    .expect("Failed to create renderer!");
+
+    assert_eq!(
+        renderer.to_svg_string(),
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><defs/><text>a &amp; b</text></svg>"#
+    );
+}
+
+#[test]
+fn headless_try_errors() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer
+        .try_render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0))
+        .expect("Failed to render named circle!");
+    renderer
+        .try_create_named_container("named_container", "root")
+        .expect("Failed to create named container!");
+
+    match renderer.try_render_named("named_circle", SVGDefault::circle(10), (0.0, 0.0)) {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "named_circle"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+
+    match renderer.try_create_named_container("root", "root") {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "root"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+
+    match renderer.try_move_named("not_in_use_name", (0.0, 0.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "not_in_use_name"),
+        _ => panic!("Expected UnfindableName"),
+    }
+
+    match renderer.try_move_named("named_container", (0.0, 0.0)) {
+        Err(NamedNotUse(name)) => assert_eq!(name, "named_container"),
+        _ => panic!("Expected NamedNotUse"),
+    }
+
+    match renderer.try_append_to_container("named_circle", SVGDefault::circle(10), (0.0, 0.0)) {
+        Err(NamedNotContainer(name)) => assert_eq!(name, "named_circle"),
+        _ => panic!("Expected NamedNotContainer"),
+    }
+
+    match renderer.try_update_named("not_in_use_name", SVGDefault::circle(10), (0.0, 0.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "not_in_use_name"),
+        _ => panic!("Expected UnfindableName"),
+    }
+
+    match renderer.try_delete_named("not_in_use_name") {
+        Err(UnfindableName(name)) => assert_eq!(name, "not_in_use_name"),
+        _ => panic!("Expected UnfindableName"),
+    }
+
+    // The renderer is still intact after all errors
+    renderer
+        .try_delete_named("named_circle")
+        .expect("Failed to delete named circle!");
+    assert!(!renderer.does_name_exist("named_circle"));
+}
+
+#[test]
+fn headless_try_from_svg() {
+    assert!(SVGRenderer::new_headless_from_svg(SVGElem::new(Tag::G)).is_err());
+}
+
+#[test]
+fn headless_handles() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    let mut other_renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    let container = renderer.create_named_container("named_container", "root");
+    let circle = renderer.render_named_id("named_circle", circle_id, (10.0, 10.0));
+
+    assert_eq!(circle.name(), "named_circle");
+    assert_eq!(renderer.get_handle("named_circle"), Some(circle.clone()));
+    assert_eq!(renderer.get_handle("not_in_use_name"), None);
+
+    // Handles can be used everywhere names can be used
+    renderer.move_named(&circle, (20.0, 20.0));
+    renderer.append_to_container_with_id(&container, circle_id, (5.0, 5.0));
+    assert!(renderer.is_container(&container));
+    assert!(renderer.does_name_exist(&circle));
+
+    // Ids and handles are tied to the renderer which issued them
+    match other_renderer.try_render_id(circle_id, (0.0, 0.0)) {
+        Err(ForeignHandle) => {}
+        _ => panic!("Expected ForeignHandle"),
+    }
+
+    match other_renderer.try_move_named(&circle, (0.0, 0.0)) {
+        Err(ForeignHandle) => {}
+        _ => panic!("Expected ForeignHandle"),
+    }
+
+    // Handles of deleted items are no longer valid
+    renderer.delete_named(&circle);
+    assert!(!renderer.does_name_exist(&circle));
+
+    match renderer.try_move_named(&circle, (0.0, 0.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "named_circle"),
+        _ => panic!("Expected UnfindableName"),
+    }
+
+    // Nor do they refer to a new item with the same name
+    let new_circle = renderer.render_named_id("named_circle", circle_id, (30.0, 30.0));
+    assert_ne!(new_circle, circle);
+    assert!(!renderer.does_name_exist(&circle));
+    assert!(renderer.does_name_exist(&new_circle));
+    assert_eq!(
+        renderer.get_handle("named_circle"),
+        Some(new_circle.clone())
+    );
+
+    match renderer.try_move_named(&circle, (0.0, 0.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "named_circle"),
+        _ => panic!("Expected UnfindableName"),
+    }
+    assert_eq!(renderer.get_location(&new_circle), (30.0, 30.0));
+
+    // Handles stay valid when their item survives a resync
+    renderer.resync();
+    assert!(renderer.does_name_exist(&new_circle));
+    assert!(!renderer.does_name_exist(&circle));
+
+    // Figure ids are no longer valid after clearing the renderer
+    renderer.clear();
+
+    match renderer.try_render_id(circle_id, (0.0, 0.0)) {
+        Err(UnfindableDefinition(_)) => {}
+        _ => panic!("Expected UnfindableDefinition"),
+    }
+}
+
+#[test]
+fn headless_collect_garbage() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    renderer.define_render(SVGDefault::rect(10, 10));
+
+    renderer.render_named_id("named", circle_id, (10.0, 10.0));
+    renderer.create_named_container("named_container", "root");
+    renderer.append_to_container_with_id("named_container", circle_id, (10.0, 10.0));
+
+    // The rect is not used by any figure
+    assert_eq!(renderer.collect_garbage(), 1);
+    assert!(!renderer.to_svg_string().contains("<rect "));
+
+    // The circle is still used within the container
+    let rect_id = renderer.define_render(SVGDefault::rect(10, 10));
+    renderer.update_named_with_id("named", rect_id, (10.0, 10.0));
+    assert_eq!(renderer.collect_garbage(), 0);
+    assert!(renderer.to_svg_string().contains("<circle "));
+
+    renderer.clear_named_container("named_container");
+    assert_eq!(renderer.collect_garbage(), 1);
+    assert!(!renderer.to_svg_string().contains("<circle "));
+
+    renderer.delete_named("named");
+    assert_eq!(renderer.collect_garbage(), 1);
+    assert!(renderer.to_svg_string().contains("<defs/>"));
+}
+
+#[test]
+fn headless_auto_garbage_collection() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    renderer.set_auto_garbage_collection(true);
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    let rect_id = renderer.define_render(SVGDefault::rect(10, 10));
+
+    renderer.render_named_id("named", circle_id, (10.0, 10.0));
+    renderer.render_id(circle_id, (20.0, 20.0));
+
+    // The circle is still used by the unnamed figure
+    renderer.update_named_with_id("named", rect_id, (10.0, 10.0));
+    assert!(renderer.to_svg_string().contains("<circle "));
+
+    // Updating to the same definition keeps it
+    renderer.update_named_with_id("named", rect_id, (20.0, 20.0));
+    assert!(renderer.to_svg_string().contains("<rect "));
+
+    renderer.create_named_container("named_container", "root");
+    renderer.append_to_container_with_id("named_container", rect_id, (10.0, 10.0));
+
+    renderer.delete_named("named");
+    assert!(renderer.to_svg_string().contains("<rect "));
+
+    renderer.delete_named("named_container");
+    assert!(!renderer.to_svg_string().contains("<rect "));
+
+    // Figure ids of removed definitions are no longer valid
+    match renderer.try_render_id(rect_id, (0.0, 0.0)) {
+        Err(UnfindableDefinition(_)) => {}
+        _ => panic!("Expected UnfindableDefinition"),
+    }
+}
+
+#[test]
+fn headless_failed_render_definitions() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    renderer.set_auto_garbage_collection(true);
+
+    renderer.render_named("a", SVGDefault::circle(10), (10.0, 10.0));
+    let svg = renderer.to_svg_string();
+
+    // A failed render does not leave the definition of its figure behind
+    match renderer.try_render_named("a", SVGDefault::rect(10, 10), (20.0, 20.0)) {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "a"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+    assert_eq!(renderer.to_svg_string(), svg);
+
+    match renderer.try_append_to_container("a", SVGDefault::rect(10, 10), (20.0, 20.0)) {
+        Err(NamedNotContainer(name)) => assert_eq!(name, "a"),
+        _ => panic!("Expected NamedNotContainer"),
+    }
+    match renderer.try_update_named("b", SVGDefault::rect(10, 10), (20.0, 20.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "b"),
+        _ => panic!("Expected UnfindableName"),
+    }
+    assert_eq!(renderer.to_svg_string(), svg);
+
+    // A definition which was already there is kept
+    match renderer.try_render_named("a", SVGDefault::circle(10), (20.0, 20.0)) {
+        Err(NameAlreadyExists(_)) => {}
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+    assert_eq!(renderer.to_svg_string(), svg);
+}
+
+#[test]
+fn headless_deduplication() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    // The same figure is only defined once, independent of the order of its attributes
+    renderer.render(SVGDefault::circle(10), (10.0, 10.0));
+    renderer.render(SVGDefault::circle(10), (20.0, 20.0));
+    renderer.render(
+        SVGElem::new(Tag::Circle)
+            .set(Attr::Cy, 0)
+            .set(Attr::Cx, 0)
+            .set(Attr::Fill, "transparent")
+            .set(Attr::StrokeWidth, 1)
+            .set(Attr::Stroke, "#000000")
+            .set(Attr::R, 10),
+        (30.0, 30.0),
+    );
+
+    let output = renderer.to_svg_string();
+    assert_eq!(output.matches("<circle ").count(), 1);
+    assert_eq!(output.matches("<use ").count(), 3);
+
+    // Figures which only differ in their text are different definitions
+    let hello_id = renderer.define_render(SVGElem::new(Tag::Text).set_inner("hello"));
+    let world_id = renderer.define_render(SVGElem::new(Tag::Text).set_inner("world"));
+    assert_ne!(hello_id, world_id);
+
+    let output = renderer.to_svg_string();
+    assert!(output.contains(">hello</text>"));
+    assert!(output.contains(">world</text>"));
+}
+
+#[test]
+fn headless_batching() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    let rect_id = renderer.define_render(SVGDefault::rect(10, 10));
+
+    renderer.set_batching(true);
+    assert!(renderer.is_batching());
+
+    renderer.create_named_container("named_container", "root");
+    renderer.append_to_container_with_id("named_container", circle_id, (5.0, 5.0));
+    renderer.render_named_id("named", circle_id, (10.0, 10.0));
+
+    for i in 0..10 {
+        renderer.move_named("named", (i as f32, i as f32));
+    }
+    renderer.update_named_with_id("named", rect_id, (20.0, 20.0));
+    renderer.hide_named("named");
+    renderer.show_named("named");
+
+    // Nothing is applied before flushing
+    assert!(!renderer.to_svg_string().contains("<use"));
+    assert!(!renderer.to_svg_string().contains("<g "));
+
+    renderer.flush();
+
+    let output = renderer.to_svg_string();
+    assert_eq!(output.matches("<use ").count(), 2);
+    assert!(output.contains(r##"<use x="5" y="5" href="#figure-"##));
+    assert!(output.contains(r##"<use x="20" y="20" href="#figure-"##));
+    assert!(!output.contains("style="));
+
+    // The circle is only used within the container
+    assert_eq!(renderer.collect_garbage(), 0);
+
+    // Removing elements flushes the queue first
+    renderer.move_named("named", (30.0, 30.0));
+    renderer.clear_named_container("named_container");
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"<use x="30" y="30" href="#figure-"##));
+    assert_eq!(renderer.collect_garbage(), 1);
+
+    // Disabling batching flushes as well
+    renderer.move_named("named", (40.0, 40.0));
+    renderer.set_batching(false);
+    assert!(!renderer.is_batching());
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"<use x="40" y="40" href="#figure-"##));
+}
+
+#[test]
+fn headless_transform() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let rect_id = renderer.define_render(SVGDefault::rect(10, 10));
+
+    renderer.render_id(rect_id, Transform::rotate_around(45.0, (5.0, 5.0)));
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"<use transform="rotate(45 5 5)" href="#figure-"##));
+
+    // Translations are set as location
+    renderer.render_named_id("named_rect", rect_id, Transform::translate(10.0, 10.0));
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"<use x="10" y="10" href="#figure-"##));
+
+    renderer.transform_named(
+        "named_rect",
+        Transform::scale(2.0, 2.0).then(Transform::translate(20.0, 20.0)),
+    );
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r#"transform="translate(20 20) scale(2 2)""#));
+    assert!(!output.contains(r#"x="10""#));
+
+    renderer.move_named("named_rect", (30.0, 30.0));
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r#"x="30" y="30""#));
+    assert_eq!(output.matches("transform=").count(), 1);
+
+    renderer.update_named_with_id("named_rect", rect_id, Transform::skew_x(10.0));
+    assert!(renderer
+        .to_svg_string()
+        .contains(r#"transform="skewX(10)""#));
+
+    // Containers are transformed as a whole
+    renderer.create_named_container("named_container", "root");
+    renderer.append_to_container_with_id("named_container", rect_id, (5.0, 5.0));
+    renderer.transform_named("named_container", Transform::rotate(90.0));
+    assert!(renderer
+        .to_svg_string()
+        .contains(r#" transform="rotate(90)"><use "#));
+
+    renderer.transform_named("named_container", Transform::identity());
+    assert!(!renderer.to_svg_string().contains("rotate(90)"));
+
+    // Only named uses can be moved
+    assert!(renderer
+        .try_move_named("named_container", (1.0, 1.0))
+        .is_err());
+
+    // Locations are written in full precision, so they are read back the same after a resync
+    renderer.render_named_id("precise_rect", rect_id, (0.125, 0.0));
+    let rendered = renderer.to_svg_string();
+    renderer.move_named("precise_rect", (0.125, 0.0));
+    assert_eq!(renderer.to_svg_string(), rendered);
+    assert!(rendered.contains(r#"x="0.125" y="0""#));
+
+    renderer.resync();
+    assert_eq!(renderer.get_location("precise_rect"), (0.125, 0.0));
+}
+
+#[test]
+fn headless_style() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let circle = SVGDefault::inherit_style(SVGDefault::circle(10));
+    assert_eq!(
+        circle.get_attributes().get(&Attr::Style).map(|s| &s[..]),
+        Some("fill: var(--fill, transparent); stroke-width: var(--stroke-width, 1); stroke: var(--stroke, #000000);")
+    );
+
+    renderer.render_styled(circle.clone(), (10.0, 10.0), Style::new().stroke("#ff0000"));
+    renderer.render_named_styled(
+        "named_circle",
+        circle.clone(),
+        (20.0, 20.0),
+        Style::new().fill("#00ff00").opacity(0.5).class("player"),
+    );
+
+    // Styled uses still share a single definition
+    let output = renderer.to_svg_string();
+    assert_eq!(output.matches("<circle ").count(), 1);
+    assert!(output.contains(r##"stroke="#ff0000" style="--stroke: #ff0000;""##));
+    assert!(output
+        .contains(r##"fill="#00ff00" opacity="0.5" class="player" style="--fill: #00ff00;""##));
+
+    // Hiding and showing keeps the overrides
+    renderer.hide_named("named_circle");
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"style="--fill: #00ff00; display: none;""##));
+    renderer.show_named("named_circle");
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"style="--fill: #00ff00;""##));
+
+    // Overrides which are left out are removed
+    renderer.style_named("named_circle", Style::new().stroke_width(3.0));
+    let output = renderer.to_svg_string();
+    assert!(!output.contains("#00ff00"));
+    assert!(!output.contains("opacity="));
+    assert!(!output.contains("class="));
+    assert!(output.contains(r#"stroke-width="3" style="--stroke-width: 3;""#));
+
+    renderer.style_named("named_circle", Style::new());
+    assert_eq!(renderer.to_svg_string().matches("style=").count(), 2);
+
+    // Queued style changes are combined
+    renderer.set_batching(true);
+    renderer.hide_named("named_circle");
+    renderer.style_named("named_circle", Style::new().stroke("#ff00ff"));
+    renderer.flush();
+    assert!(renderer
+        .to_svg_string()
+        .contains(r#"style="display: none; --stroke: #ff00ff;""#));
+    renderer.set_batching(false);
+
+    // Containers pass their style on to their children
+    renderer.create_named_container("named_container", "root");
+    renderer.style_named("named_container", Style::new().stroke("#0000ff"));
+    assert!(renderer
+        .to_svg_string()
+        .contains(r##"stroke="#0000ff" style="--stroke: #0000ff;""##));
+}
+
+#[test]
+fn headless_visibility() {
+    use wasm_svg_graphics::backend::Backend;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.create_named_container("named_container", "root");
+    renderer.render_named_styled(
+        "named_circle",
+        SVGDefault::circle(10),
+        (10.0, 10.0),
+        Style::new().stroke("#ff0000").opacity(0.5),
+    );
+    assert!(renderer.is_visible("named_circle"));
+
+    // Every mode sets a single property and keeps the others
+    for (mode, property) in [
+        (HideMode::Display, "display: none;"),
+        (HideMode::Visibility, "visibility: hidden;"),
+        (HideMode::Opacity, "opacity: 0;"),
+    ]
+    .iter()
+    {
+        renderer.hide_named_with("named_circle", *mode);
+        assert!(!renderer.is_visible("named_circle"));
+        assert!(renderer
+            .to_svg_string()
+            .contains(&format!(r#"style="--stroke: #ff0000; {}""#, property)[..]));
+
+        renderer.show_named("named_circle");
+        assert!(renderer.is_visible("named_circle"));
+        let output = renderer.to_svg_string();
+        assert!(output.contains(r##"opacity="0.5" style="--stroke: #ff0000;""##));
+    }
+
+    // Toggling uses the display property
+    assert!(!renderer.toggle_named("named_circle"));
+    assert!(!renderer.is_visible("named_circle"));
+    assert!(renderer.toggle_named("named_circle"));
+    assert!(renderer.is_visible("named_circle"));
+
+    // Any opacity of zero hides an item and is removed when the item is shown
+    let element = renderer.backend().root().children()[0].children()[2].clone();
+    assert_eq!(element.tag_name(), "use");
+    for opacity in ["0.0", "0%", "-1"].iter() {
+        let style = format!("--stroke: #ff0000; opacity: {};", opacity);
+        renderer
+            .backend()
+            .set_attribute(&element, "style", &style[..])
+            .expect("Failed to set style!");
+        assert!(!renderer.is_visible("named_circle"));
+
+        renderer.show_named("named_circle");
+        assert!(renderer.is_visible("named_circle"));
+        let output = renderer.to_svg_string();
+        assert!(output.contains(r##"opacity="0.5" style="--stroke: #ff0000;""##));
+    }
+
+    // Hidden containers hide their children
+    renderer.hide_named_with("named_container", HideMode::Visibility);
+    renderer.show_named("named_container");
+    assert!(!renderer.to_svg_string().contains("visibility"));
+    renderer.hide_named("named_container");
+    assert!(renderer.is_visible("named_circle"));
+    renderer.set_batching(true);
+    renderer.create_named_container("inner_container", "named_container");
+    renderer.flush();
+    assert!(!renderer.is_visible("inner_container"));
+
+    // Pending changes are taken into account
+    renderer.show_named("named_container");
+    assert!(renderer.is_visible("inner_container"));
+    renderer.hide_named("root");
+    assert!(!renderer.is_visible("inner_container"));
+    renderer.set_batching(false);
+    assert!(renderer.to_svg_string().starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100" style="display: none;">"#
+    ));
+}
+
+/// Will return the names of the named items in the root, from bottom to top
+fn root_order(renderer: &SVGRenderer<HeadlessBackend>) -> Vec<String> {
+    renderer.backend().root().children()[0]
+        .children()
+        .iter()
+        .filter_map(|child| child.get_attribute("data-name"))
+        .collect()
+}
+
+#[test]
+fn headless_z_order() {
+    use wasm_svg_graphics::backend::Backend;
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let circle_id = renderer.define_render(SVGDefault::circle(10));
+    for name in ["a", "b", "c"].iter() {
+        renderer.render_named_id(name, circle_id, (10.0, 10.0));
+    }
+    assert_eq!(root_order(&renderer), ["a", "b", "c"]);
+
+    renderer.raise_named("a");
+    assert_eq!(root_order(&renderer), ["b", "a", "c"]);
+    renderer.raise_named("c");
+    assert_eq!(root_order(&renderer), ["b", "a", "c"]);
+    renderer.lower_named("c");
+    assert_eq!(root_order(&renderer), ["b", "c", "a"]);
+    renderer.lower_named("b");
+    assert_eq!(root_order(&renderer), ["b", "c", "a"]);
+
+    renderer.bring_to_front("b");
+    assert_eq!(root_order(&renderer), ["c", "a", "b"]);
+    renderer.send_to_back("a");
+    assert_eq!(root_order(&renderer), ["a", "c", "b"]);
+
+    renderer.insert_before("b", "c");
+    assert_eq!(root_order(&renderer), ["a", "b", "c"]);
+    renderer.insert_before("a", "a");
+    assert_eq!(root_order(&renderer), ["a", "b", "c"]);
+
+    // The definitions always stay first
+    assert!(renderer
+        .to_svg_string()
+        .contains("viewBox=\"0 0 100 100\"><defs>"));
+
+    renderer.render_named_id_at_index("d", circle_id, (10.0, 10.0), 1);
+    assert_eq!(root_order(&renderer), ["a", "d", "b", "c"]);
+    renderer.render_named_at_index("e", SVGDefault::circle(10), (10.0, 10.0), 10);
+    assert_eq!(root_order(&renderer), ["a", "d", "b", "c", "e"]);
+
+    // Queued items are added before they are reordered
+    renderer.set_batching(true);
+    renderer.render_named_id("f", circle_id, (10.0, 10.0));
+    renderer.send_to_back("f");
+    assert_eq!(root_order(&renderer), ["f", "a", "d", "b", "c", "e"]);
+
+    // Only siblings can be reordered relative to each other
+    renderer.create_named_container("container", "root");
+    renderer.create_named_container("inner", "container");
+    match renderer.try_insert_before("inner", "a") {
+        Err(NamedNotSibling(name, sibling)) => {
+            assert_eq!((&name[..], &sibling[..]), ("inner", "a"))
+        }
+        _ => panic!("Expected NamedNotSibling"),
+    }
+    match renderer.try_raise_named("root") {
+        Err(UnfindableName(name)) => assert_eq!(name, "root"),
+        _ => panic!("Expected UnfindableName"),
+    }
+
+    // Items within different unnamed groups are not siblings, even though they are both in the root
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    renderer.render_named("first", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.render_named("second", SVGDefault::circle(10), (10.0, 10.0));
+    {
+        let backend = renderer.backend();
+        let svg = backend.root().children()[0].clone();
+        let second = svg.children()[2].clone();
+        let group = backend
+            .create_element("g")
+            .expect("Failed to create group!");
+        backend
+            .append_child(&svg, &group)
+            .expect("Failed to append group!");
+        backend
+            .append_child(&group, &second)
+            .expect("Failed to move item!");
+    }
+    renderer.resync();
+    match renderer.try_insert_before("first", "second") {
+        Err(NamedNotSibling(name, sibling)) => {
+            assert_eq!((&name[..], &sibling[..]), ("first", "second"))
+        }
+        _ => panic!("Expected NamedNotSibling"),
+    }
+}
+
+#[test]
+fn headless_layers() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.create_layer("ui", 10);
+    renderer.create_layer("background", 0);
+    renderer.render_named("free", SVGDefault::circle(10), (10.0, 10.0));
+    renderer.create_layer("entities", 5);
+    renderer.create_layer("overlay", 10);
+    assert_eq!(
+        root_order(&renderer),
+        ["background", "entities", "ui", "overlay", "free"]
+    );
+    assert!(renderer.is_layer("ui"));
+    assert!(!renderer.is_layer("free"));
+    assert!(renderer
+        .to_svg_string()
+        .contains(r#"data-name="entities" data-z-index="5""#));
+
+    // Items are rendered into their layer
+    renderer.render_to_layer("background", SVGDefault::rect(100, 100), (0.0, 0.0));
+    renderer.render_named_to_layer("entities", "player", SVGDefault::circle(5), (50.0, 50.0));
+    renderer.move_named("player", (60.0, 60.0));
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r#"data-z-index="5"><use x="60" y="60" href="#));
+    assert!(output.contains(r#"data-z-index="0"><use x="0" y="0" href="#));
+
+    // Layers are reordered as a unit
+    renderer.set_layer_z_index("entities", 20);
+    assert_eq!(
+        root_order(&renderer),
+        ["background", "ui", "overlay", "entities", "free"]
+    );
+    renderer.set_layer_z_index("overlay", -1);
+    assert_eq!(
+        root_order(&renderer),
+        ["overlay", "background", "ui", "entities", "free"]
+    );
+
+    // Layers are named containers
+    renderer.hide_named("entities");
+    assert!(!renderer.is_visible("player"));
+    renderer.clear_named_container("background");
+    assert_eq!(renderer.to_svg_string().matches("<use ").count(), 2);
+
+    match renderer.try_render_to_layer("free", SVGDefault::circle(10), (0.0, 0.0)) {
+        Err(NamedNotLayer(name)) => assert_eq!(name, "free"),
+        _ => panic!("Expected NamedNotLayer"),
+    }
+    match renderer.try_set_layer_z_index("player", 1) {
+        Err(NamedNotLayer(name)) => assert_eq!(name, "player"),
+        _ => panic!("Expected NamedNotLayer"),
+    }
+    match renderer.try_create_layer("ui", 1) {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "ui"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+
+    // Layers are only moved past items which are not layers and layers with the same z-index
+    match renderer.try_bring_to_front("ui") {
+        Err(LayerOutOfOrder(name)) => assert_eq!(name, "ui"),
+        _ => panic!("Expected LayerOutOfOrder"),
+    }
+    match renderer.try_send_to_back("background") {
+        Err(LayerOutOfOrder(name)) => assert_eq!(name, "background"),
+        _ => panic!("Expected LayerOutOfOrder"),
+    }
+    assert_eq!(
+        root_order(&renderer),
+        ["overlay", "background", "ui", "entities", "free"]
+    );
+    renderer.bring_to_front("entities");
+    assert_eq!(
+        root_order(&renderer),
+        ["overlay", "background", "ui", "free", "entities"]
+    );
+    renderer.create_layer("hud", 10);
+    renderer.insert_before("hud", "ui");
+    assert_eq!(
+        root_order(&renderer),
+        ["overlay", "background", "hud", "ui", "free", "entities"]
+    );
+    match renderer.try_insert_before("entities", "hud") {
+        Err(LayerOutOfOrder(name)) => assert_eq!(name, "entities"),
+        _ => panic!("Expected LayerOutOfOrder"),
+    }
+
+    // Items which are not layers are moved freely
+    renderer.raise_named("free");
+    renderer.send_to_back("free");
+    assert_eq!(
+        root_order(&renderer),
+        ["free", "overlay", "background", "hud", "ui", "entities"]
+    );
+}
+
+#[test]
+fn headless_named_in_container() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.create_named_container("car", "root");
+    renderer.create_named_container("wheels", "car");
+    renderer.render_named_in("car", "body", SVGDefault::rect(40, 10), (0.0, 0.0));
+    let wheel = renderer.define_render(SVGDefault::circle(5));
+    renderer.render_named_id_in("wheels", "front_wheel", wheel, (30.0, 10.0));
+    renderer.render_named_id_in("wheels", "back_wheel", wheel, (0.0, 10.0));
+
+    // Items within nested containers can be changed one by one
+    renderer.move_named("front_wheel", (32.0, 10.0));
+    renderer.transform_named("car", (100.0, 0.0));
+    let output = renderer.to_svg_string();
+    assert!(output.contains(r#"<g id="named-"#));
+    assert!(output.contains(r#"data-name="wheels"><use x="32" y="10" href="#));
+    assert!(output.contains(r#"transform="translate(100 0)""#));
+    assert_eq!(root_order(&renderer), ["car"]);
+
+    // The root can be used as a container as well
+    renderer.render_named_in("root", "sun", SVGDefault::circle(20), (0.0, 0.0));
+    assert_eq!(root_order(&renderer), ["car", "sun"]);
+
+    // Deleting a container removes the names of its nested items
+    renderer.delete_named("car");
+    assert!(!renderer.does_name_exist("wheels"));
+    assert!(!renderer.does_name_exist("front_wheel"));
+    renderer.render_named("front_wheel", SVGDefault::circle(5), (0.0, 0.0));
+
+    match renderer.try_render_named_in("sun", "ray", SVGDefault::circle(1), (0.0, 0.0)) {
+        Err(NamedNotContainer(name)) => assert_eq!(name, "sun"),
+        _ => panic!("Expected NamedNotContainer"),
+    }
+    match renderer.try_render_named_in("car", "ray", SVGDefault::circle(1), (0.0, 0.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "car"),
+        _ => panic!("Expected UnfindableName"),
+    }
+    match renderer.try_render_named_id_in("root", "sun", wheel, (0.0, 0.0)) {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "sun"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+}
+
+#[test]
+fn headless_name_cleanup() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.create_named_container("scene", "root");
+    renderer.create_named_container("enemies", "scene");
+    renderer.render_named_in("scene", "player", SVGDefault::circle(5), (0.0, 0.0));
+    renderer.render_named_in("enemies", "enemy_1", SVGDefault::circle(5), (10.0, 0.0));
+    renderer.render_named_in("enemies", "enemy_2", SVGDefault::circle(5), (20.0, 0.0));
+
+    // Clearing only removes the names within the container
+    let mut removed = renderer.clear_named_container_with_report("enemies");
+    removed.sort();
+    assert_eq!(removed, ["enemy_1", "enemy_2"]);
+    assert!(renderer.does_name_exist("enemies"));
+    assert!(!renderer.does_name_exist("enemy_1"));
+
+    // Names of items which are still queued are removed as well
+    renderer.set_batching(true);
+    renderer.render_named_in("enemies", "enemy_1", SVGDefault::circle(5), (10.0, 0.0));
+    renderer.delete_named("scene");
+    renderer.set_batching(false);
+    for name in ["scene", "enemies", "player", "enemy_1"].iter() {
+        assert!(!renderer.does_name_exist(*name));
+    }
+    assert_eq!(renderer.to_svg_string().matches("<use ").count(), 0);
+
+    // The relations of names are recovered by a resync
+    renderer.create_named_container("scene", "root");
+    renderer.create_named_container("enemies", "scene");
+    renderer.render_named_in("enemies", "enemy_1", SVGDefault::circle(5), (10.0, 0.0));
+    renderer.render_named("sun", SVGDefault::circle(20), (0.0, 0.0));
+    renderer.resync();
+    assert_eq!(
+        renderer.delete_named_with_report("scene"),
+        ["scene", "enemies", "enemy_1"]
+    );
+    assert!(renderer.does_name_exist("sun"));
+    assert_eq!(renderer.delete_named_with_report("sun"), ["sun"]);
+}
+
+#[test]
+fn headless_introspection() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    renderer.create_named_container("car", "root");
+    renderer.create_named_container("wheels", "car");
+    let wheel = renderer.define_render(SVGDefault::circle(5));
+    renderer.render_named_id_in("wheels", "front_wheel", wheel, (30.0, 10.0));
+    renderer.render_named_id_in("wheels", "back_wheel", wheel, (0.0, 10.0));
+    renderer.render_named_in("car", "body", SVGDefault::rect(40, 10), (0.0, 0.0));
+    renderer.append_to_container("car", SVGDefault::circle(1), (0.0, 0.0));
+    renderer.render_named("sun", SVGDefault::circle(20), (0.0, 0.0));
+
+    assert_eq!(
+        renderer.names(),
+        ["back_wheel", "body", "car", "front_wheel", "sun", "wheels"]
+    );
+
+    // Relations
+    assert_eq!(renderer.get_parent("front_wheel").unwrap().name(), "wheels");
+    assert_eq!(renderer.get_parent("wheels").unwrap().name(), "car");
+    assert_eq!(renderer.get_parent("car"), None);
+    let names = |handles: Vec<NodeHandle>| -> Vec<String> {
+        handles
+            .iter()
+            .map(|handle| String::from(handle.name()))
+            .collect()
+    };
+    assert_eq!(names(renderer.get_children("root")), ["car", "sun"]);
+    assert_eq!(names(renderer.get_children("car")), ["wheels", "body"]);
+
+    // The children are in the order in which they are drawn
+    renderer.send_to_back("body");
+    assert_eq!(names(renderer.get_children("car")), ["body", "wheels"]);
+
+    let tree: Vec<(String, usize, bool)> = renderer
+        .tree()
+        .iter()
+        .map(|item| (String::from(item.name()), item.depth(), item.is_container()))
+        .collect();
+    assert_eq!(
+        tree,
+        [
+            (String::from("car"), 0, true),
+            (String::from("body"), 1, false),
+            (String::from("wheels"), 1, true),
+            (String::from("front_wheel"), 2, false),
+            (String::from("back_wheel"), 2, false),
+            (String::from("sun"), 0, false),
+        ]
+    );
+
+    // State of named items
+    renderer.move_named("front_wheel", (32.0, 10.0));
+    renderer.transform_named("car", Transform::rotate(90.0));
+    assert_eq!(renderer.get_location("front_wheel"), (32.0, 10.0));
+    assert_eq!(renderer.get_transform("car"), Transform::rotate(90.0));
+    assert_eq!(
+        renderer.get_transform("back_wheel"),
+        Transform::translate(0.0, 10.0)
+    );
+    assert_eq!(renderer.get_figure_id("back_wheel"), wheel);
+    renderer.hide_named("wheels");
+    assert!(!renderer.is_visible("back_wheel"));
+
+    // Queued items are part of the tree as well
+    renderer.set_batching(true);
+    renderer.render_named_in("car", "roof", SVGDefault::rect(20, 5), (10.0, -5.0));
+    assert_eq!(
+        names(renderer.get_children("car")),
+        ["body", "wheels", "roof"]
+    );
+    renderer.set_batching(false);
+
+    match renderer.try_get_figure_id("car") {
+        Err(NamedNotUse(name)) => assert_eq!(name, "car"),
+        _ => panic!("Expected NamedNotUse"),
+    }
+    match renderer.try_get_children("sun") {
+        Err(NamedNotContainer(name)) => assert_eq!(name, "sun"),
+        _ => panic!("Expected NamedNotContainer"),
+    }
+    match renderer.try_get_parent("moon") {
+        Err(UnfindableName(name)) => assert_eq!(name, "moon"),
+        _ => panic!("Expected UnfindableName"),
+    }
+}
+
+#[test]
+fn headless_reparent_rename_duplicate() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    let names = |handles: Vec<NodeHandle>| -> Vec<String> {
+        handles
+            .iter()
+            .map(|handle| String::from(handle.name()))
+            .collect()
+    };
+
+    renderer.create_named_container("ground", "root");
+    renderer.create_named_container("car", "root");
+    renderer.create_named_container("wheels", "car");
+    renderer.render_named_in("wheels", "wheel", SVGDefault::circle(5), (0.0, 10.0));
+    renderer.render_named_in("ground", "box", SVGDefault::rect(10, 10), (0.0, 0.0));
+
+    // Reparenting
+    renderer.reparent_named("box", "car");
+    assert_eq!(names(renderer.get_children("ground")), Vec::<String>::new());
+    assert_eq!(names(renderer.get_children("car")), ["wheels", "box"]);
+    assert_eq!(renderer.get_parent("box").unwrap().name(), "car");
+    renderer.reparent_named("box", "root");
+    assert_eq!(renderer.get_parent("box"), None);
+    assert_eq!(root_order(&renderer), ["ground", "car", "box"]);
+    renderer.reparent_named("box", "car");
+
+    match renderer.try_reparent_named("car", "wheels") {
+        Err(NamedWithinItself(name, parent)) => {
+            assert_eq!(name, "car");
+            assert_eq!(parent, "wheels");
+        }
+        _ => panic!("Expected NamedWithinItself"),
+    }
+    match renderer.try_reparent_named("car", "wheel") {
+        Err(NamedNotContainer(name)) => assert_eq!(name, "wheel"),
+        _ => panic!("Expected NamedNotContainer"),
+    }
+
+    // Renaming
+    let old_handle = renderer.get_handle("wheels").unwrap();
+    let wheel = renderer.get_handle("wheel").unwrap();
+    renderer.rename_named("wheels", "axle");
+    assert!(!renderer.does_name_exist("wheels"));
+    assert!(!renderer.does_name_exist(&old_handle));
+    assert_eq!(renderer.get_parent(&wheel).unwrap().name(), "axle");
+    assert_eq!(names(renderer.get_children("car")), ["axle", "box"]);
+    assert!(renderer.to_svg_string().contains(r#"data-name="axle""#));
+    renderer.create_named_container("wheels", "root");
+
+    match renderer.try_rename_named("axle", "box") {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "box"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+
+    // Duplicating
+    renderer.append_to_container("car", SVGDefault::circle(1), (0.0, 0.0));
+    renderer.move_named("wheel", (5.0, 10.0));
+    let copy = renderer.duplicate_named("car", "second_");
+    assert_eq!(copy.name(), "second_car");
+    assert_eq!(
+        names(renderer.get_children("second_car")),
+        ["second_axle", "second_box"]
+    );
+    assert_eq!(renderer.get_location("second_wheel"), (5.0, 10.0));
+    assert_eq!(
+        root_order(&renderer),
+        ["ground", "car", "wheels", "second_car"]
+    );
+
+    // The copies are independent and share the definitions
+    renderer.move_named("second_wheel", (0.0, 0.0));
+    assert_eq!(renderer.get_location("wheel"), (5.0, 10.0));
+    let output = renderer.to_svg_string();
+    assert_eq!(output.matches("<use ").count(), 6);
+    assert_eq!(output.matches(r#"id="figure-"#).count(), 3);
+    assert_eq!(renderer.collect_garbage(), 0);
+
+    // Deleting the original keeps the definitions used by the copies
+    renderer.delete_named("car");
+    assert_eq!(renderer.collect_garbage(), 0);
+
+    match renderer.try_duplicate_named("second_car", "") {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "second_car"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+
+    // Nothing is copied when one of the names is in use
+    renderer.render_named("third_second_wheel", SVGDefault::circle(5), (0.0, 0.0));
+    let name_count = renderer.names().len();
+    match renderer.try_duplicate_named("second_car", "third_") {
+        Err(NameAlreadyExists(name)) => assert_eq!(name, "third_second_wheel"),
+        _ => panic!("Expected NameAlreadyExists"),
+    }
+    assert_eq!(renderer.names().len(), name_count);
+
+    // Layers stay layers when they are copied, but not when they are moved into a container
+    renderer.create_layer("ui", 10);
+    renderer.duplicate_named("ui", "copy_");
+    assert!(renderer.is_layer("copy_ui"));
+    renderer.reparent_named("ui", "wheels");
+    assert!(!renderer.is_layer("ui"));
+    assert!(!renderer
+        .to_svg_string()
+        .contains(r#"data-name="ui" data-z-index"#));
+}
+
+#[test]
+fn headless_keyed_definitions() {
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+
+    let player = renderer.define_as("player", SVGDefault::circle(5));
+    renderer.render_named_id("alice", player, (10.0, 10.0));
+    renderer.render_named_id("bob", player, (30.0, 10.0));
+    assert_eq!(renderer.get_definition("player"), Some(player));
+    assert_eq!(renderer.get_definition("enemy"), None);
+
+    // Equal figures do not share the keyed definition
+    let circle = renderer.define_render(SVGDefault::circle(5));
+    assert_ne!(circle, player);
+    renderer.render_id(circle, (0.0, 0.0));
+
+    // Replacing the figure keeps the id and the uses
+    let before = renderer.to_svg_string();
+    assert_eq!(
+        renderer.define_as("player", SVGDefault::rect(10, 10)),
+        player
+    );
+    let after = renderer.to_svg_string();
+    assert_eq!(after.matches(r#"data-key="player""#).count(), 1);
+    assert!(after.contains("<rect "));
+    assert_eq!(after.matches("<circle ").count(), 1);
+    assert_eq!(
+        before.split("</defs>").nth(1),
+        after.split("</defs>").nth(1)
+    );
+
+    // Keyed definitions are not collected
+    renderer.delete_named("alice");
+    renderer.delete_named("bob");
+    assert_eq!(renderer.collect_garbage(), 0);
+    renderer.set_auto_garbage_collection(true);
+    renderer.render_named_id("alice", player, (10.0, 10.0));
+    renderer.delete_named("alice");
+    assert_eq!(renderer.get_definition("player"), Some(player));
+
+    // The key is recovered by a resync
+    renderer.resync();
+    assert_eq!(renderer.get_definition("player"), Some(player));
+    renderer.define_as("player", SVGDefault::circle(8));
+    let output = renderer.to_svg_string();
+    assert_eq!(output.matches(r#"data-key="player""#).count(), 1);
+    assert!(!output.contains("<rect "));
+}
+
+#[test]
+fn headless_pointer_events() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    let events: Rc<RefCell<Vec<PointerEvent>>> = Rc::new(RefCell::new(Vec::new()));
+
+    renderer.create_named_container("car", "root");
+    renderer.render_named_in("car", "wheel", SVGDefault::circle(5), (0.0, 10.0));
+    for (name, kind) in [
+        ("car", PointerEventKind::Click),
+        ("car", PointerEventKind::PointerEnter),
+        ("wheel", PointerEventKind::Click),
+        ("wheel", PointerEventKind::PointerEnter),
+        ("wheel", PointerEventKind::Wheel),
+    ]
+    .iter()
+    {
+        let events = events.clone();
+        renderer.on_named(*name, *kind, move |event| {
+            events.borrow_mut().push(event.clone())
+        });
+    }
+    let take = || -> Vec<(String, PointerEventKind)> {
+        events
+            .borrow_mut()
+            .drain(..)
+            .map(|event| (String::from(event.name()), event.kind()))
+            .collect()
+    };
+
+    // Clicks are passed on to the container, entering is not
+    renderer.dispatch_pointer_event("wheel", PointerEventKind::Click, (1.0, 2.0), (0.0, 5.0));
+    assert_eq!(events.borrow()[0].position(), (1.0, 2.0));
+    assert_eq!(events.borrow()[0].wheel_delta(), (0.0, 0.0));
+    assert_eq!(
+        take(),
+        [
+            (String::from("wheel"), PointerEventKind::Click),
+            (String::from("car"), PointerEventKind::Click)
+        ]
+    );
+    renderer.dispatch_pointer_event(
+        "wheel",
+        PointerEventKind::PointerEnter,
+        (1.0, 2.0),
+        (0.0, 0.0),
+    );
+    assert_eq!(
+        take(),
+        [(String::from("wheel"), PointerEventKind::PointerEnter)]
+    );
+    renderer.dispatch_pointer_event("wheel", PointerEventKind::Wheel, (1.0, 2.0), (0.0, 5.0));
+    assert_eq!(events.borrow()[0].wheel_delta(), (0.0, 5.0));
+    take();
+
+    // Handlers receive the new name of a renamed item
+    renderer.rename_named("wheel", "front_wheel");
+    renderer.dispatch_pointer_event(
+        "front_wheel",
+        PointerEventKind::PointerEnter,
+        (0.0, 0.0),
+        (0.0, 0.0),
+    );
+    assert_eq!(
+        take(),
+        [(String::from("front_wheel"), PointerEventKind::PointerEnter)]
+    );
+
+    // Removed handlers are no longer called
+    renderer.off_named("car", PointerEventKind::Click);
+    renderer.dispatch_pointer_event(
+        "front_wheel",
+        PointerEventKind::Click,
+        (0.0, 0.0),
+        (0.0, 0.0),
+    );
+    assert_eq!(
+        take(),
+        [(String::from("front_wheel"), PointerEventKind::Click)]
+    );
+    renderer.delete_named("car");
+    assert_eq!(Rc::strong_count(&events), 1);
+}
+
+#[test]
+fn headless_pointer_events_of_replaced_elements() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use wasm_svg_graphics::backend::Backend;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    let clicks = Rc::new(Cell::new(0));
+
+    renderer.render_named("kept", SVGDefault::circle(5), (0.0, 0.0));
+    renderer.render_named("replaced", SVGDefault::circle(5), (20.0, 0.0));
+    for name in ["kept", "replaced"].iter() {
+        let clicks = clicks.clone();
+        renderer.on_named(*name, PointerEventKind::Click, move |_| {
+            clicks.set(clicks.get() + 1)
+        });
+    }
+
+    // The element of an item is replaced by an identical one without the renderer
+    {
+        let backend = renderer.backend();
+        let svg = backend.root().children()[0].clone();
+        let old_element = svg.children()[2].clone();
+        let new_element = backend.create_element("use").unwrap();
+        for (name, value) in backend.attributes(&old_element) {
+            backend
+                .set_attribute(&new_element, &name[..], &value[..])
+                .unwrap();
+        }
+        backend
+            .replace_child(&svg, &new_element, &old_element)
+            .unwrap();
+    }
+
+    renderer.resync();
+    assert_eq!(Rc::strong_count(&clicks), 2);
+    renderer.dispatch_pointer_event("kept", PointerEventKind::Click, (0.0, 0.0), (0.0, 0.0));
+    renderer.dispatch_pointer_event("replaced", PointerEventKind::Click, (20.0, 0.0), (0.0, 0.0));
+    assert_eq!(clicks.get(), 1);
+
+    // A handler dispatching the same event on its own item is not called again
+    let element = renderer.backend().root().children()[0].children()[1].clone();
+    let inner_clicks = Rc::new(Cell::new(0));
+    {
+        let inner_clicks = inner_clicks.clone();
+        renderer.on_named("kept", PointerEventKind::PointerDown, move |_| {
+            inner_clicks.set(inner_clicks.get() + 1);
+            element.dispatch_event("pointerdown", true, (0.0, 0.0), (0.0, 0.0));
+        });
+    }
+    renderer.dispatch_pointer_event(
+        "kept",
+        PointerEventKind::PointerDown,
+        (0.0, 0.0),
+        (0.0, 0.0),
+    );
+    assert_eq!(inner_clicks.get(), 1);
+}
+
+#[test]
+fn headless_coordinates() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    fn assert_close(left: Point2D, right: Point2D) {
+        assert!(
+            (left.0 - right.0).abs() < 1e-4 && (left.1 - right.1).abs() < 1e-4,
+            "{:?} != {:?}",
+            left,
+            right
+        );
+    }
+
+    fn sized(preserve_aspect_ratio: &str) -> SVGRenderer<HeadlessBackend> {
+        let mut svg = SVGElem::new(Tag::Svg)
+            .set(Attr::Width, "200px")
+            .set(Attr::Height, 100)
+            .set(Attr::ViewBox, "0 0 100 100");
+
+        if !preserve_aspect_ratio.is_empty() {
+            svg = svg.set(Attr::PreserveAspectRatio, preserve_aspect_ratio);
+        }
+
+        SVGRenderer::new_headless_from_svg(svg).expect("Failed to create renderer!")
+    }
+
+    // Without a size the viewbox is shown as is
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    assert_close(renderer.client_to_viewbox((3.0, 4.0)), (3.0, 4.0));
+
+    // By default the viewbox is centered and scaled to fit
+    let renderer_sized = sized("");
+    assert_close(renderer_sized.client_to_viewbox((60.0, 10.0)), (10.0, 10.0));
+    assert_close(renderer_sized.viewbox_to_client((10.0, 10.0)), (60.0, 10.0));
+
+    renderer_sized.adjust_viewbox(10, 10, 50, 50);
+    assert_close(renderer_sized.client_to_viewbox((70.0, 20.0)), (20.0, 20.0));
+    assert_close(renderer_sized.viewbox_to_client((20.0, 20.0)), (70.0, 20.0));
+
+    assert_close(sized("none").client_to_viewbox((20.0, 30.0)), (10.0, 30.0));
+    assert_close(
+        sized("xMinYMin slice").client_to_viewbox((20.0, 30.0)),
+        (10.0, 15.0),
+    );
+    assert_close(
+        sized("xMaxYMax slice").client_to_viewbox((20.0, 30.0)),
+        (10.0, 65.0),
+    );
+
+    // Nested containers are undone as well
+    renderer.create_named_container("map", "root");
+    renderer.transform_named(
+        "map",
+        Transform::scale(2.0, 2.0).then(Transform::translate(10.0, 0.0)),
+    );
+    renderer.render_named_in("map", "marker", SVGDefault::circle(1), (5.0, 5.0));
+
+    assert_close(renderer.client_to_local("map", (20.0, 10.0)), (5.0, 5.0));
+    assert_close(renderer.local_to_client("map", (5.0, 5.0)), (20.0, 10.0));
+    assert_close(renderer.local_to_client("marker", (0.0, 0.0)), (20.0, 10.0));
+    assert_close(renderer.client_to_local("marker", (22.0, 10.0)), (1.0, 0.0));
+    assert_close(renderer.client_to_local("root", (3.0, 4.0)), (3.0, 4.0));
+
+    match renderer.try_client_to_local("unknown", (0.0, 0.0)) {
+        Err(UnfindableName(name)) => assert_eq!(name, "unknown"),
+        _ => panic!("Expected UnfindableName"),
+    }
+
+    renderer.transform_named("map", Transform::scale(0.0, 2.0));
+    match renderer.try_client_to_local("marker", (0.0, 0.0)) {
+        Err(SingularTransform(name)) => assert_eq!(name, "marker"),
+        _ => panic!("Expected SingularTransform"),
+    }
+}
+
+#[test]
+fn headless_hit_testing() {
+    use wasm_svg_graphics::backend::Backend;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    let names = |handles: Vec<NodeHandle>| -> Vec<String> {
+        handles
+            .iter()
+            .map(|handle| String::from(handle.name()))
+            .collect()
+    };
+    let hit = |renderer: &SVGRenderer<HeadlessBackend>, position| {
+        renderer
+            .hit_test(position)
+            .map(|handle| String::from(handle.name()))
+    };
+
+    renderer.render_named("background", SVGDefault::rect(100, 100), (0.0, 0.0));
+    renderer.render_named("button", SVGDefault::circle(10), (50.0, 50.0));
+
+    // Transparent fills are hit, the topmost item comes first
+    assert_eq!(hit(&renderer, (50.0, 50.0)), Some(String::from("button")));
+    assert_eq!(
+        names(renderer.hit_test_all((50.0, 50.0))),
+        ["button", "background"]
+    );
+    assert_eq!(hit(&renderer, (5.0, 5.0)), Some(String::from("background")));
+    assert_eq!(hit(&renderer, (200.0, 200.0)), None);
+
+    renderer.send_to_back("button");
+    assert_eq!(
+        names(renderer.hit_test_all((50.0, 50.0))),
+        ["background", "button"]
+    );
+
+    renderer.bring_to_front("button");
+    renderer.hide_named("button");
+    assert_eq!(
+        hit(&renderer, (50.0, 50.0)),
+        Some(String::from("background"))
+    );
+    renderer.show_named("button");
+    assert_eq!(hit(&renderer, (50.0, 50.0)), Some(String::from("button")));
+
+    // The transforms of containers are taken into account, including the stroke
+    renderer.create_named_container("car", "root");
+    renderer.transform_named(
+        "car",
+        Transform::scale(2.0, 2.0).then(Transform::translate(200.0, 0.0)),
+    );
+    renderer.render_named_in("car", "wheel", SVGDefault::circle(5), (10.0, 10.0));
+    assert_eq!(hit(&renderer, (229.0, 20.0)), Some(String::from("wheel")));
+    assert_eq!(hit(&renderer, (230.5, 20.0)), Some(String::from("wheel")));
+    assert_eq!(hit(&renderer, (231.5, 20.0)), None);
+
+    // Figures which are not named count as their container
+    renderer.append_to_container("car", SVGDefault::rect(5, 5), (0.0, 0.0));
+    assert_eq!(hit(&renderer, (205.0, 5.0)), Some(String::from("car")));
+
+    // A fill of none is not hit, only its stroke
+    let outline = SVGElem::new(Tag::Path)
+        .set(Attr::D, "M 0 0 L 10 0 L 10 10 Z")
+        .set(Attr::Fill, "none")
+        .set(Attr::Stroke, "#000000");
+    renderer.render_named("outline", outline, (300.0, 0.0));
+    assert_eq!(hit(&renderer, (308.0, 2.0)), None);
+    assert_eq!(hit(&renderer, (305.0, 0.0)), Some(String::from("outline")));
+
+    // The stroke width of a style is used through the CSS variables
+    renderer.render_named_styled(
+        "thick",
+        SVGDefault::inherit_style(SVGDefault::circle(10)),
+        (400.0, 0.0),
+        Style::new().stroke_width(6.0),
+    );
+    assert_eq!(hit(&renderer, (412.5, 0.0)), Some(String::from("thick")));
+    assert_eq!(hit(&renderer, (413.5, 0.0)), None);
+
+    // A visible item within a container with a hidden visibility is still hit, unlike its siblings
+    renderer.create_named_container("menu", "root");
+    renderer.render_named_in("menu", "entry", SVGDefault::rect(10, 10), (600.0, 0.0));
+    renderer.render_named_in(
+        "menu",
+        "shown_entry",
+        SVGDefault::rect(10, 10),
+        (620.0, 0.0),
+    );
+    renderer.hide_named_with("menu", HideMode::Visibility);
+    let shown_entry = renderer.backend().root().children()[0]
+        .children()
+        .last()
+        .expect("Failed to find menu!")
+        .children()[1]
+        .clone();
+    renderer
+        .backend()
+        .set_attribute(&shown_entry, "style", "visibility: visible;")
+        .expect("Failed to set style!");
+    assert_eq!(hit(&renderer, (605.0, 5.0)), None);
+    assert!(!renderer.is_visible("entry"));
+    assert!(renderer.is_visible("shown_entry"));
+    assert_eq!(
+        hit(&renderer, (625.0, 5.0)),
+        Some(String::from("shown_entry"))
+    );
+
+    // Containers which are not displayed hide all items within them
+    renderer.hide_named("menu");
+    assert_eq!(hit(&renderer, (625.0, 5.0)), None);
+    assert!(!renderer.is_visible("shown_entry"));
+
+    // Queued items are hit as well
+    renderer.set_batching(true);
+    renderer.render_named("queued", SVGDefault::rect(10, 10), (500.0, 0.0));
+    assert_eq!(hit(&renderer, (505.0, 5.0)), Some(String::from("queued")));
+}
+
+#[test]
+fn headless_spatial_index() {
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    let names = |handles: Vec<NodeHandle>| -> Vec<String> {
+        handles
+            .iter()
+            .map(|handle| String::from(handle.name()))
+            .collect()
+    };
+
+    for index in 0..1000 {
+        let location = ((index % 100) as f32 * 10.0, (index / 100) as f32 * 10.0);
+        renderer.render_named(
+            &format!("marker_{:03}", index),
+            SVGDefault::circle(2),
+            location,
+        );
+    }
+
+    assert_eq!(
+        renderer.get_bounding_box("marker_000"),
+        Some(BoundingBox::from_rect(-2.5, -2.5, 5.0, 5.0))
+    );
+    assert_eq!(
+        names(renderer.query_area(BoundingBox::from_rect(1.0, 1.0, 10.0, 10.0))),
+        ["marker_000", "marker_001", "marker_100", "marker_101"]
+    );
+    assert_eq!(
+        names(renderer.query_radius((20.0, 20.0), 9.0)),
+        [
+            "marker_102",
+            "marker_201",
+            "marker_202",
+            "marker_203",
+            "marker_302"
+        ]
+    );
+    assert!(renderer
+        .query_area(BoundingBox::from_rect(-100.0, -100.0, 50.0, 50.0))
+        .is_empty());
+
+    // Moved and deleted items are updated
+    renderer.move_named("marker_000", (-75.0, -75.0));
+    renderer.delete_named("marker_001");
+    assert_eq!(
+        names(renderer.query_area(BoundingBox::from_rect(1.0, 1.0, 10.0, 10.0))),
+        ["marker_100", "marker_101"]
+    );
+    assert_eq!(
+        names(renderer.query_area(BoundingBox::from_rect(-100.0, -100.0, 50.0, 50.0))),
+        ["marker_000"]
+    );
+
+    // The transforms of containers are taken into account, containers are not indexed
+    renderer.create_named_container("layer", "root");
+    renderer.render_named_in("layer", "inner", SVGDefault::rect(10, 10), (0.0, 0.0));
+    renderer.transform_named("layer", Transform::translate(2000.0, 0.0));
+    assert_eq!(renderer.get_bounding_box("layer"), None);
+    assert_eq!(names(renderer.query_radius((2005.0, 5.0), 1.0)), ["inner"]);
+
+    // Nested items follow every container they are in, figures which are not named are not indexed
+    renderer.create_named_container("group", "layer");
+    renderer.render_named_in("group", "nested", SVGDefault::rect(10, 10), (0.0, 0.0));
+    renderer.append_to_container("group", SVGDefault::rect(10, 10), (0.0, 100.0));
+    renderer.transform_named("group", Transform::translate(0.0, 500.0));
+    assert_eq!(
+        names(renderer.query_radius((2005.0, 505.0), 1.0)),
+        ["nested"]
+    );
+    assert!(renderer.query_radius((2005.0, 605.0), 1.0).is_empty());
+
+    renderer.transform_named("layer", Transform::translate(3000.0, 0.0));
+    assert_eq!(
+        names(renderer.query_radius((3005.0, 505.0), 1.0)),
+        ["nested"]
+    );
+    assert_eq!(names(renderer.query_radius((3005.0, 5.0), 1.0)), ["inner"]);
+    assert!(renderer.query_radius((2005.0, 505.0), 1.0).is_empty());
+
+    renderer.delete_named("layer");
+    assert!(renderer.query_radius((3005.0, 5.0), 1.0).is_empty());
+
+    // Items are unfindable after clearing
+    renderer.clear();
+    assert!(renderer
+        .query_area(BoundingBox::from_rect(-100.0, -100.0, 2000.0, 2000.0))
+        .is_empty());
+
+    match renderer.try_get_bounding_box("marker_002") {
+        Err(UnfindableName(name)) => assert_eq!(name, "marker_002"),
+        _ => panic!("Expected UnfindableName"),
+    }
+}
+
+#[test]
+fn headless_backend_tree_errors() {
+    use wasm_svg_graphics::backend::Backend;
+    use wasm_svg_graphics::errors::DomError::*;
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let backend = HeadlessBackend::default();
+    let create = |tag_name| backend.create_element(tag_name).unwrap();
+
+    let (old_parent, parent, child, stranger) =
+        (create("g"), create("g"), create("rect"), create("circle"));
+    backend.append_child(&old_parent, &child).unwrap();
+
+    // A failed call leaves the child where it was
+    match backend.insert_before(&parent, &child, &stranger) {
+        Err(Dom(UnappendableElement)) => {}
+        _ => panic!("Expected UnappendableElement"),
+    }
+    match backend.replace_child(&parent, &child, &stranger) {
+        Err(Dom(UnappendableElement)) => {}
+        _ => panic!("Expected UnappendableElement"),
+    }
+    assert_eq!(old_parent.children().len(), 1);
+    assert!(child
+        .parent()
+        .is_some_and(|element| element.ptr_eq(&old_parent)));
+
+    // An element can not become a descendant of itself
+    backend.append_child(&parent, &old_parent).unwrap();
+    match backend.append_child(&child, &parent) {
+        Err(Dom(UnappendableElement)) => {}
+        _ => panic!("Expected UnappendableElement"),
+    }
+    match backend.append_child(&parent, &parent) {
+        Err(Dom(UnappendableElement)) => {}
+        _ => panic!("Expected UnappendableElement"),
+    }
+    backend.append_child(&parent, &stranger).unwrap();
+    match backend.insert_before(&old_parent, &parent, &child) {
+        Err(Dom(UnappendableElement)) => {}
+        _ => panic!("Expected UnappendableElement"),
+    }
+    assert!(parent.parent().is_none());
+    assert_eq!(parent.to_svg_string(), "<g><g><rect/></g><circle/></g>");
+
+    // Moving an element before its current next sibling keeps the order
+    backend
+        .insert_before(&parent, &old_parent, &stranger)
+        .unwrap();
+    backend
+        .replace_child(&parent, &stranger, &stranger)
+        .unwrap();
+    assert_eq!(parent.to_svg_string(), "<g><g><rect/></g><circle/></g>");
+}
+
+#[test]
+fn headless_adopt_under_other_parent() {
+    use wasm_svg_graphics::backend::Backend;
+    use wasm_svg_graphics::errors::RendererError::*;
+
+    let mut renderer = SVGRenderer::new_headless().expect("Failed to create renderer!");
+    renderer.render_named("dot", SVGDefault::circle(5), (10.0, 10.0));
+    let svg = renderer.backend().root().children()[0].clone();
+
+    // The svg is moved to a parent with another id
+    let backend = HeadlessBackend::new("app");
+    backend.append_child(backend.root(), &svg).unwrap();
+    let mut adopted =
+        SVGRenderer::with_backend_adopt(backend, "app").expect("Failed to adopt svg!");
+
+    assert_eq!(adopted.namespace(), "headless-root");
+    assert!(adopted.does_name_exist("dot"));
+    adopted.move_named("dot", (20.0, 20.0));
+    adopted.render_named("other_dot", SVGDefault::circle(5), (30.0, 30.0));
+    assert_eq!(svg.children()[0].children().len(), 1);
+
+    // The namespace can be given explicitly
+    let backend = HeadlessBackend::new("app");
+    backend.append_child(backend.root(), &svg).unwrap();
+    match SVGRenderer::with_backend_adopt_with_namespace(backend, "app", "app") {
+        Err(UnusableNamespace(namespace)) => assert_eq!(namespace, "app"),
+        _ => panic!("Expected UnusableNamespace"),
+    }
+
+    let backend = HeadlessBackend::new("app");
+    backend.append_child(backend.root(), &svg).unwrap();
+    let adopted = SVGRenderer::with_backend_adopt_with_namespace(backend, "app", "headless-root")
+        .expect("Failed to adopt svg!");
+    assert!(adopted.does_name_exist("other_dot"));
+
+    // Named items of multiple renderers cannot be told apart
+    let backend = HeadlessBackend::new("app");
+    backend.append_child(backend.root(), &svg).unwrap();
+    let foreign = backend.create_element("g").unwrap();
+    backend
+        .set_attribute(&foreign, "id", "named-legend-1f")
+        .unwrap();
+    backend.set_attribute(&foreign, "data-name", "key").unwrap();
+    backend.append_child(&svg, &foreign).unwrap();
+    match SVGRenderer::with_backend_adopt(backend, "app") {
+        Err(AmbiguousNamespace(first, second)) => {
+            assert_eq!(first, "headless-root");
+            assert_eq!(second, "legend");
+        }
+        _ => panic!("Expected AmbiguousNamespace"),
+    }
+}
+
+#[test]
+fn headless_backend_text() {
+    use wasm_svg_graphics::backend::Backend;
+
+    let backend = HeadlessBackend::default();
+    let text = backend.create_element("text").unwrap();
+    let child = backend.create_element("tspan").unwrap();
+    backend.append_child(&text, &child).unwrap();
+
+    // The text replaces the children, like in the DOM
+    backend.set_text(&text, "a & b").unwrap();
+    assert!(text.children().is_empty());
+    assert!(child.parent().is_none());
+    assert_eq!(text.to_svg_string(), "<text>a &amp; b</text>");
+}
@@ -0,0 +1,102 @@
+//! Test suite for transforms, which runs natively.
+
+use wasm_svg_graphics::prelude::*;
+
+fn assert_close(left: (f32, f32), right: (f32, f32)) {
+    assert!(
+        (left.0 - right.0).abs() < 1e-4 && (left.1 - right.1).abs() < 1e-4,
+        "{:?} != {:?}",
+        left,
+        right
+    );
+}
+
+#[test]
+fn transform_to_string() {
+    assert_eq!(Transform::identity().to_string(), "");
+    assert_eq!(
+        Transform::translate(1.5, 2.0).to_string(),
+        "translate(1.5 2)"
+    );
+    assert_eq!(Transform::rotate(90.0).to_string(), "rotate(90)");
+    assert_eq!(
+        Transform::rotate_around(45.0, (5.0, 5.0)).to_string(),
+        "rotate(45 5 5)"
+    );
+    assert_eq!(Transform::scale(2.0, 3.0).to_string(), "scale(2 3)");
+    assert_eq!(Transform::skew_x(10.0).to_string(), "skewX(10)");
+    assert_eq!(Transform::skew_y(10.0).to_string(), "skewY(10)");
+    assert_eq!(
+        Transform::matrix([1.0, 0.0, 0.0, 1.0, 5.0, 6.0]).to_string(),
+        "matrix(1 0 0 1 5 6)"
+    );
+
+    // The first transform is applied first, so it is the last in the list
+    assert_eq!(
+        Transform::scale(2.0, 2.0)
+            .then(Transform::translate(10.0, 0.0))
+            .to_string(),
+        "translate(10 0) scale(2 2)"
+    );
+}
+
+#[test]
+fn transform_apply() {
+    assert_close(Transform::identity().apply((3.0, 4.0)), (3.0, 4.0));
+    assert_close(Transform::translate(1.0, 2.0).apply((3.0, 4.0)), (4.0, 6.0));
+    assert_close(Transform::rotate(90.0).apply((1.0, 0.0)), (0.0, 1.0));
+    assert_close(
+        Transform::rotate_around(180.0, (5.0, 5.0)).apply((0.0, 0.0)),
+        (10.0, 10.0),
+    );
+    assert_close(Transform::scale(2.0, 3.0).apply((1.0, 1.0)), (2.0, 3.0));
+    assert_close(Transform::skew_x(45.0).apply((0.0, 1.0)), (1.0, 1.0));
+    assert_close(Transform::skew_y(45.0).apply((1.0, 0.0)), (1.0, 1.0));
+    assert_close(
+        Transform::matrix([0.0, 1.0, -1.0, 0.0, 5.0, 0.0]).apply((1.0, 0.0)),
+        (5.0, 1.0),
+    );
+
+    // Composition applies the first transform first
+    let scale_then_move = Transform::scale(2.0, 2.0).then(Transform::translate(10.0, 0.0));
+    let move_then_scale = Transform::translate(10.0, 0.0).then(Transform::scale(2.0, 2.0));
+    assert_close(scale_then_move.apply((1.0, 1.0)), (12.0, 2.0));
+    assert_close(move_then_scale.apply((1.0, 1.0)), (22.0, 2.0));
+
+    let matrix = scale_then_move.to_matrix();
+    assert_close(
+        Transform::matrix(matrix).apply((3.0, -1.0)),
+        scale_then_move.apply((3.0, -1.0)),
+    );
+}
+
+#[test]
+fn transform_translation() {
+    assert_eq!(Transform::from((1.0, 2.0)), Transform::translate(1.0, 2.0));
+    assert_eq!(Transform::identity().as_translation(), Some((0.0, 0.0)));
+    assert_eq!(
+        Transform::translate(1.0, 2.0).as_translation(),
+        Some((1.0, 2.0))
+    );
+    assert_eq!(Transform::rotate(90.0).as_translation(), None);
+
+    assert!(Transform::identity().is_identity());
+    assert!(Transform::rotate(0.0)
+        .then(Transform::scale(1.0, 1.0))
+        .is_identity());
+    assert!(!Transform::translate(1.0, 0.0).is_identity());
+}
+
+#[test]
+fn transform_inverse() {
+    let transform = Transform::rotate_around(30.0, (5.0, 5.0))
+        .then(Transform::scale(2.0, 0.5))
+        .then(Transform::translate(-3.0, 7.0));
+    let inverse = transform.inverse().expect("Transform should be invertible");
+
+    assert_close(inverse.apply(transform.apply((4.0, -2.0))), (4.0, -2.0));
+    assert_close(transform.apply(inverse.apply((4.0, -2.0))), (4.0, -2.0));
+    assert!(Transform::identity().inverse().unwrap().is_identity());
+
+    assert_eq!(Transform::scale(0.0, 1.0).inverse(), None);
+}
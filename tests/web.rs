@@ -530,3 +530,38 @@ fn renderer_adjust_viewbox() {
     // Adjust the viewbox
     renderer.adjust_viewbox(0, 0, 50, 50);
 }
+
+#[wasm_bindgen_test]
+fn renderer_try_render_named() {
+    add_svg_parent();
+
+    use wasm_svg_graphics::prelude::*;
+
+    // Declare renderer (must be mutable)
+    let mut renderer = SVGRenderer::new("svg_parent_id").expect("Failed to create renderer!");
+
+    renderer
+        .try_render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0))
+        .expect("Failed to render named circle!");
+
+    // Declaring the same name twice will return an error instead of panicking
+    assert!(renderer
+        .try_render_named("named_circle", SVGDefault::circle(10), (10.0, 10.0))
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+fn renderer_try_move_named() {
+    add_svg_parent();
+
+    use wasm_svg_graphics::errors::RendererError;
+    use wasm_svg_graphics::prelude::*;
+
+    // Declare renderer (must be mutable)
+    let mut renderer = SVGRenderer::new("svg_parent_id").expect("Failed to create renderer!");
+
+    match renderer.try_move_named("not_in_use_name", (5.0, 5.0)) {
+        Err(RendererError::UnfindableName(_)) => {}
+        _ => panic!("Expected UnfindableName"),
+    }
+}